@@ -13,7 +13,10 @@ use sql_types::HasSqlType;
 
 #[doc(hidden)]
 pub use self::statement_cache::{MaybeCached, StatementCache, StatementCacheKey};
-pub use self::transaction_manager::{AnsiTransactionManager, TransactionManager};
+pub use self::transaction_manager::{
+    AnsiTransactionManager, BeginTransactionStatementProvider, NestedTransactionMode,
+    SavepointExt, TransactionBehavior, TransactionManager, TransactionState, TransactionStateExt,
+};
 
 /// Perform simple operations on a backend.
 ///
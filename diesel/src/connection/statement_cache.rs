@@ -130,6 +130,7 @@ where
         &self,
         source: &T,
         bind_types: &[DB::TypeMetadata],
+        schema_salt: u64,
         prepare_fn: F,
     ) -> QueryResult<MaybeCached<Statement>>
     where
@@ -138,7 +139,7 @@ where
     {
         use std::collections::hash_map::Entry::{Occupied, Vacant};
 
-        let cache_key = try!(StatementCacheKey::for_source(source, bind_types));
+        let cache_key = try!(StatementCacheKey::for_source(source, bind_types, schema_salt));
 
         if !source.is_safe_to_cache_prepared()? {
             let sql = try!(cache_key.sql(source));
@@ -192,7 +193,7 @@ impl<'a, T> DerefMut for MaybeCached<'a, T> {
 #[allow(missing_debug_implementations)]
 #[derive(Hash, PartialEq, Eq)]
 pub enum StatementCacheKey<DB: Backend> {
-    Type(TypeId),
+    Type(TypeId, u64),
     Sql {
         sql: String,
         bind_types: Vec<DB::TypeMetadata>,
@@ -205,12 +206,22 @@ where
     DB::QueryBuilder: Default,
     DB::TypeMetadata: Clone,
 {
-    pub fn for_source<T>(source: &T, bind_types: &[DB::TypeMetadata]) -> QueryResult<Self>
+    /// Builds the cache key for `source`.
+    ///
+    /// `schema_salt` is mixed into type-based keys so that connections which can point the same
+    /// query type at different schemas at runtime (see `PgConnection::set_search_path`) don't
+    /// hand back a prepared statement that was planned against a different schema. Backends
+    /// without that concept always pass `0`.
+    pub fn for_source<T>(
+        source: &T,
+        bind_types: &[DB::TypeMetadata],
+        schema_salt: u64,
+    ) -> QueryResult<Self>
     where
         T: QueryFragment<DB> + QueryId,
     {
         match T::query_id() {
-            Some(id) => Ok(StatementCacheKey::Type(id)),
+            Some(id) => Ok(StatementCacheKey::Type(id, schema_salt)),
             None => {
                 let sql = try!(Self::construct_sql(source));
                 Ok(StatementCacheKey::Sql {
@@ -223,7 +234,7 @@ where
 
     pub fn sql<T: QueryFragment<DB>>(&self, source: &T) -> QueryResult<Cow<str>> {
         match *self {
-            StatementCacheKey::Type(_) => Self::construct_sql(source).map(Cow::Owned),
+            StatementCacheKey::Type(..) => Self::construct_sql(source).map(Cow::Owned),
             StatementCacheKey::Sql { ref sql, .. } => Ok(Cow::Borrowed(sql)),
         }
     }
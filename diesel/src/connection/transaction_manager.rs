@@ -1,6 +1,6 @@
 use backend::UsesAnsiSavepointSyntax;
 use connection::{Connection, SimpleConnection};
-use result::QueryResult;
+use result::{Error, QueryResult};
 
 /// Manages the internal transaction state for a connection.
 ///
@@ -35,14 +35,212 @@ pub trait TransactionManager<Conn: Connection> {
     fn get_transaction_depth(&self) -> u32;
 }
 
-use std::cell::Cell;
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+type Hook = Box<Fn() + Send + Sync>;
+
+/// A callback queued by [`AnsiTransactionManager::on_transaction_commit`], along with the
+/// transaction depth it was registered at.
+struct PendingCommitCallback {
+    depth: i32,
+    callback: Hook,
+}
+
+/// A unit of work queued by [`AnsiTransactionManager::queue_deferred_work`], run against the
+/// connection as part of the same transaction that's about to commit.
+type DeferredWork = Box<Fn(&SimpleConnection) -> QueryResult<()> + Send + Sync>;
+
+/// A [`DeferredWork`] item, along with the transaction depth it was queued at.
+struct PendingDeferredWork {
+    depth: i32,
+    work: DeferredWork,
+}
+
+/// Controls what happens when `transaction()` (or [`SavepointExt::savepoint`]) is called while a
+/// transaction is already open on the connection.
+///
+/// [`SavepointExt::savepoint`]: trait.SavepointExt.html#method.savepoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NestedTransactionMode {
+    /// Open a real `SAVEPOINT`, so the nested transaction can commit or roll back independently
+    /// of whatever it's nested inside. This is the default, and matches Diesel's historical
+    /// behavior.
+    Savepoint,
+    /// Treat the nested call as a no-op: no `SAVEPOINT` is issued, and releasing it has no SQL
+    /// effect of its own. Because there is no savepoint to roll back to, rolling back a nested
+    /// transaction in this mode rolls back everything all the way out to the outermost
+    /// transaction -- there's no partial undo available once the boundary has been flattened
+    /// away.
+    Join,
+    /// Return `Err(Error::AlreadyInTransaction)` instead of opening a nested transaction or
+    /// savepoint.
+    Error,
+}
+
+impl Default for NestedTransactionMode {
+    fn default() -> Self {
+        NestedTransactionMode::Savepoint
+    }
+}
+
+/// Warns if a transaction is left open longer than `threshold`, since e.g. an open SQLite write
+/// transaction silently blocks every other writer for as long as it stays open.
+///
+/// There is no background timer -- staying dependency-free and not spawning threads behind a
+/// connection's back matters more here than catching a transaction that's simply idle and never
+/// touched again, so the elapsed time is only checked opportunistically, at each subsequent
+/// `begin`/`commit`/`rollback` on the connection.
+struct LongRunningTransactionWatchdog {
+    threshold: Duration,
+    callback: Box<Fn(Duration, Option<&Backtrace>) + Send + Sync>,
+    started_at: Option<Instant>,
+    opened_at: Option<Backtrace>,
+}
+
+impl LongRunningTransactionWatchdog {
+    fn mark_started(&mut self) {
+        self.started_at = Some(Instant::now());
+        self.opened_at = if cfg!(debug_assertions) {
+            Some(Backtrace::force_capture())
+        } else {
+            None
+        };
+    }
+
+    fn mark_closed(&mut self) {
+        self.started_at = None;
+        self.opened_at = None;
+    }
+
+    fn check(&self) {
+        if let Some(started_at) = self.started_at {
+            let elapsed = started_at.elapsed();
+            if elapsed >= self.threshold {
+                (self.callback)(elapsed, self.opened_at.as_ref());
+            }
+        }
+    }
+}
+
+/// How a transaction opened via [`AnsiTransactionManager::begin_transaction_sql`] was told to
+/// take (or release) its lock -- SQLite's three `BEGIN` variants.
+///
+/// See [`TransactionState::behavior`](struct.TransactionState.html#structfield.behavior) for how
+/// this is (and isn't) populated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionBehavior {
+    /// `BEGIN DEFERRED` -- no lock is taken until the transaction's first read or write.
+    Deferred,
+    /// `BEGIN IMMEDIATE` -- a write lock is taken immediately.
+    Immediate,
+    /// `BEGIN EXCLUSIVE` -- an exclusive lock is taken immediately, blocking other readers too.
+    Exclusive,
+}
+
+impl TransactionBehavior {
+    fn from_begin_sql(sql: &str) -> Option<Self> {
+        if sql.contains("IMMEDIATE") {
+            Some(TransactionBehavior::Immediate)
+        } else if sql.contains("EXCLUSIVE") {
+            Some(TransactionBehavior::Exclusive)
+        } else if sql.contains("DEFERRED") {
+            Some(TransactionBehavior::Deferred)
+        } else {
+            None
+        }
+    }
+}
+
+/// A point-in-time snapshot of a connection's transaction state, returned by
+/// [`TransactionStateExt::transaction_state`](trait.TransactionStateExt.html#method.transaction_state),
+/// for middleware and debugging tools that want to check whether code is unexpectedly running
+/// inside (or outside) a transaction.
+#[derive(Debug, Clone, Copy)]
+pub struct TransactionState {
+    /// How many transactions/savepoints deep the connection currently is. `0` means no
+    /// transaction is open.
+    pub depth: u32,
+    /// The [`TransactionBehavior`](enum.TransactionBehavior.html) the outermost transaction was
+    /// opened with, if that's known.
+    ///
+    /// This is only populated when the outermost transaction's `BEGIN` SQL named
+    /// `DEFERRED`/`IMMEDIATE`/`EXCLUSIVE` explicitly -- either SQL passed to
+    /// [`AnsiTransactionManager::begin_transaction_sql`] (which is what
+    /// [`SqliteConnection::immediate_transaction`]/[`exclusive_transaction`] do), or SQL returned
+    /// by an installed `BeginTransactionStatementProvider`. A plain `transaction()` call with no
+    /// provider installed leaves this `None`, even though SQLite still applies its own
+    /// (deferred) default.
+    ///
+    /// [`AnsiTransactionManager::begin_transaction_sql`]: struct.AnsiTransactionManager.html#method.begin_transaction_sql
+    /// [`SqliteConnection::immediate_transaction`]: ../sqlite/struct.SqliteConnection.html#method.immediate_transaction
+    /// [`exclusive_transaction`]: ../sqlite/struct.SqliteConnection.html#method.exclusive_transaction
+    pub behavior: Option<TransactionBehavior>,
+    /// Whether the outermost transaction was opened as read-only.
+    ///
+    /// Populated by scanning for a `READ ONLY` marker in the SQL passed to
+    /// [`begin_transaction_sql`](struct.AnsiTransactionManager.html#method.begin_transaction_sql)
+    /// (or returned by an installed `BeginTransactionStatementProvider`), which is how [`PgConnection::build_transaction`](../pg/struct.TransactionBuilder.html#method.read_only)
+    /// requests it. This does *not* see SQLite's own
+    /// [`read_only_transaction`](../sqlite/struct.SqliteConnection.html#method.read_only_transaction),
+    /// which enforces read-only separately from the `BEGIN` statement -- check
+    /// [`SqliteConnection::is_readonly_tx`](../sqlite/struct.SqliteConnection.html#method.is_readonly_tx)
+    /// for that instead.
+    pub read_only: bool,
+    /// When the outermost transaction began, or `None` if no transaction is open.
+    pub started_at: Option<Instant>,
+}
+
+/// Supplies the SQL used to open a transaction or savepoint, so a connection or wrapper can
+/// customize it -- e.g. always issuing `BEGIN IMMEDIATE` for a write-heavy SQLite app, or adding
+/// `SET TRANSACTION` options for Postgres -- without hand-rolling the begin/commit/rollback
+/// bookkeeping [`AnsiTransactionManager::begin_transaction_sql`] already does, the way
+/// [`SqliteConnection::immediate_transaction`] does today.
+///
+/// Install one with [`AnsiTransactionManager::set_begin_statement_provider`]; it's consulted by
+/// the ordinary [`transaction()`](../trait.Connection.html#method.transaction) call, so existing
+/// call sites pick up the custom SQL without switching to a dedicated wrapper method.
+///
+/// [`AnsiTransactionManager::begin_transaction_sql`]: struct.AnsiTransactionManager.html#method.begin_transaction_sql
+/// [`AnsiTransactionManager::set_begin_statement_provider`]: struct.AnsiTransactionManager.html#method.set_begin_statement_provider
+/// [`SqliteConnection::immediate_transaction`]: ../sqlite/struct.SqliteConnection.html#method.immediate_transaction
+pub trait BeginTransactionStatementProvider: Send + Sync {
+    /// Returns the SQL to run to open a transaction or savepoint.
+    ///
+    /// `depth` is the transaction depth *before* this call, so `0` means this is opening the
+    /// outermost transaction (the returned SQL should be `BEGIN`-shaped), and any other value
+    /// means it's opening a nested savepoint (the returned SQL must still be valid `SAVEPOINT`
+    /// syntax, or nested `transaction()`/`savepoint()` calls will break).
+    fn begin_transaction_sql(&self, depth: u32) -> String;
+}
 
 /// An implementation of `TransactionManager` which can be used for backends
 /// which use ANSI standard syntax for savepoints such as SQLite and PostgreSQL.
+///
+/// Since every ANSI-savepoint backend's connection is built around one of these, it's also the
+/// place `on_transaction_begin`/`on_transaction_commit`/`on_transaction_rollback` hooks are
+/// registered (see [`on_begin`](#method.on_begin), [`on_commit`](#method.on_commit) and
+/// [`on_rollback`](#method.on_rollback)) — a hook registered here runs for that connection's
+/// transactions and savepoints alike, wherever the connection was created. See
+/// [`on_transaction_commit`](#method.on_transaction_commit) for a one-shot alternative that only
+/// fires once, and only once the *outermost* transaction actually commits.
 #[allow(missing_debug_implementations)]
 #[derive(Default)]
 pub struct AnsiTransactionManager {
     transaction_depth: Cell<i32>,
+    on_begin: RefCell<Vec<Hook>>,
+    on_commit: RefCell<Vec<Hook>>,
+    on_rollback: RefCell<Vec<Hook>>,
+    pending_after_commit: RefCell<Vec<PendingCommitCallback>>,
+    pending_deferred_work: RefCell<Vec<PendingDeferredWork>>,
+    nested_transaction_mode: Cell<NestedTransactionMode>,
+    open_levels: RefCell<Vec<NestedTransactionMode>>,
+    long_running_watchdog: RefCell<Option<LongRunningTransactionWatchdog>>,
+    transaction_started_at: Cell<Option<Instant>>,
+    current_behavior: Cell<Option<TransactionBehavior>>,
+    current_read_only: Cell<bool>,
+    begin_statement_provider: RefCell<Option<Box<BeginTransactionStatementProvider>>>,
 }
 
 impl AnsiTransactionManager {
@@ -51,6 +249,192 @@ impl AnsiTransactionManager {
         AnsiTransactionManager::default()
     }
 
+    /// Registers `hook` to run every time a transaction or savepoint on this connection begins.
+    pub fn on_begin<F: Fn() + Send + Sync + 'static>(&self, hook: F) {
+        self.on_begin.borrow_mut().push(Box::new(hook));
+    }
+
+    /// Registers `hook` to run every time a transaction or savepoint on this connection commits
+    /// (or, for a savepoint, releases).
+    pub fn on_commit<F: Fn() + Send + Sync + 'static>(&self, hook: F) {
+        self.on_commit.borrow_mut().push(Box::new(hook));
+    }
+
+    /// Registers `hook` to run every time a transaction or savepoint on this connection rolls
+    /// back.
+    pub fn on_rollback<F: Fn() + Send + Sync + 'static>(&self, hook: F) {
+        self.on_rollback.borrow_mut().push(Box::new(hook));
+    }
+
+    /// Queues `callback` to run exactly once, only after the *outermost* transaction on this
+    /// connection actually commits.
+    ///
+    /// Unlike [`on_commit`](#method.on_commit), which is a permanent hook that runs on every
+    /// commit or savepoint release, `callback` here runs at most once and is discarded -- never
+    /// run -- if the transaction (or savepoint) it was registered inside rolls back instead of
+    /// committing. This makes it suited to side effects that must not fire for rolled-back work,
+    /// such as sending events or invalidating caches.
+    ///
+    /// If called outside of any transaction, `callback` runs the next time the transaction that's
+    /// about to begin commits.
+    pub fn on_transaction_commit<F: Fn() + Send + Sync + 'static>(&self, callback: F) {
+        let depth = self.transaction_depth.get().max(1);
+        self.pending_after_commit
+            .borrow_mut()
+            .push(PendingCommitCallback {
+                depth,
+                callback: Box::new(callback),
+            });
+    }
+
+    /// Sets what `transaction()` (and [`SavepointExt::savepoint`]) should do when called while a
+    /// transaction is already open on this connection. Defaults to
+    /// [`NestedTransactionMode::Savepoint`].
+    ///
+    /// [`SavepointExt::savepoint`]: trait.SavepointExt.html#method.savepoint
+    /// [`NestedTransactionMode::Savepoint`]: enum.NestedTransactionMode.html#variant.Savepoint
+    pub fn set_nested_transaction_mode(&self, mode: NestedTransactionMode) {
+        self.nested_transaction_mode.set(mode);
+    }
+
+    /// The [`NestedTransactionMode`](enum.NestedTransactionMode.html) currently in effect for
+    /// this connection.
+    pub fn nested_transaction_mode(&self) -> NestedTransactionMode {
+        self.nested_transaction_mode.get()
+    }
+
+    /// Registers `callback` to run whenever the outermost transaction on this connection has been
+    /// open for at least `threshold`, so a transaction that's blocking every other writer (an
+    /// open SQLite write transaction, for example) doesn't go unnoticed.
+    ///
+    /// `callback` is passed how long the transaction has actually been open, and, in debug
+    /// builds, the backtrace captured when it began (`None` in release builds, since capturing
+    /// one on every `begin_transaction` isn't free).
+    ///
+    /// The check runs opportunistically, each time `begin_transaction`, `commit_transaction`, or
+    /// `rollback_transaction` is next called on this connection -- there is no background timer,
+    /// so a transaction left open with no further queries run against it won't trigger a warning
+    /// until something on the connection finally does.
+    pub fn warn_on_long_running_transactions<F>(&self, threshold: Duration, callback: F)
+    where
+        F: Fn(Duration, Option<&Backtrace>) + Send + Sync + 'static,
+    {
+        *self.long_running_watchdog.borrow_mut() = Some(LongRunningTransactionWatchdog {
+            threshold,
+            callback: Box::new(callback),
+            started_at: None,
+            opened_at: None,
+        });
+    }
+
+    /// Installs `provider` to supply the SQL used to open every transaction and savepoint on
+    /// this connection from now on, in place of the default `BEGIN`/`SAVEPOINT
+    /// diesel_savepoint_N` statements.
+    pub fn set_begin_statement_provider<P>(&self, provider: P)
+    where
+        P: BeginTransactionStatementProvider + 'static,
+    {
+        *self.begin_statement_provider.borrow_mut() = Some(Box::new(provider));
+    }
+
+    /// The SQL to use to open a transaction/savepoint at `depth`, from the installed
+    /// [`BeginTransactionStatementProvider`](trait.BeginTransactionStatementProvider.html) if one
+    /// is set, or `default` otherwise.
+    fn begin_sql_for_depth(&self, depth: u32, default: String) -> String {
+        match self.begin_statement_provider.borrow().as_ref() {
+            Some(provider) => provider.begin_transaction_sql(depth),
+            None => default,
+        }
+    }
+
+    fn mark_transaction_started(&self) {
+        self.transaction_started_at.set(Some(Instant::now()));
+        if let Some(watchdog) = self.long_running_watchdog.borrow_mut().as_mut() {
+            watchdog.mark_started();
+        }
+    }
+
+    fn mark_transaction_closed(&self) {
+        self.transaction_started_at.set(None);
+        self.current_behavior.set(None);
+        self.current_read_only.set(false);
+        if let Some(watchdog) = self.long_running_watchdog.borrow_mut().as_mut() {
+            watchdog.mark_closed();
+        }
+    }
+
+    fn check_long_running_transaction(&self) {
+        if let Some(watchdog) = self.long_running_watchdog.borrow().as_ref() {
+            watchdog.check();
+        }
+    }
+
+    /// Queues `work` to run against the connection right before the *outermost* transaction on
+    /// this connection commits -- as part of that same transaction, so it commits or rolls back
+    /// atomically with everything else the transaction did. This is the building block for a
+    /// transactional outbox: queue an `INSERT` of an outgoing event alongside the rest of a
+    /// transaction's statements, and it either lands with the transaction or not at all.
+    ///
+    /// Dropped without running if the transaction (or savepoint) it was queued inside rolls back
+    /// instead of committing. Queued work runs in the order it was queued, and stops at the first
+    /// one that returns `Err`, which then becomes the error [`commit_transaction`] returns.
+    ///
+    /// [`commit_transaction`]: trait.TransactionManager.html#tymethod.commit_transaction
+    pub fn queue_deferred_work<F>(&self, work: F)
+    where
+        F: Fn(&SimpleConnection) -> QueryResult<()> + Send + Sync + 'static,
+    {
+        let depth = self.transaction_depth.get().max(1);
+        self.pending_deferred_work
+            .borrow_mut()
+            .push(PendingDeferredWork {
+                depth,
+                work: Box::new(work),
+            });
+    }
+
+    fn run_hooks(hooks: &RefCell<Vec<Hook>>) {
+        for hook in hooks.borrow().iter() {
+            hook();
+        }
+    }
+
+    /// Drops any pending after-commit callbacks and deferred work registered at
+    /// `rolled_back_depth` or deeper, since the transaction or savepoint they were waiting on
+    /// will now never commit.
+    fn discard_pending_after(&self, rolled_back_depth: i32) {
+        self.pending_after_commit
+            .borrow_mut()
+            .retain(|pending| pending.depth < rolled_back_depth);
+        self.pending_deferred_work
+            .borrow_mut()
+            .retain(|pending| pending.depth < rolled_back_depth);
+    }
+
+    /// Runs and clears every pending after-commit callback, but only once `new_depth` shows the
+    /// outermost transaction has actually committed.
+    fn run_pending_after_commit_if_top_level(&self, new_depth: i32) {
+        if new_depth != 0 {
+            return;
+        }
+        for pending in self.pending_after_commit.borrow_mut().drain(..) {
+            (pending.callback)();
+        }
+    }
+
+    /// Runs and clears every queued deferred work item against `conn`, stopping at (and
+    /// returning) the first error. Only called just before the outermost `COMMIT`, so this work
+    /// still runs inside the transaction that's about to commit.
+    fn flush_deferred_work<Conn>(&self, conn: &Conn) -> QueryResult<()>
+    where
+        Conn: SimpleConnection,
+    {
+        for pending in self.pending_deferred_work.borrow_mut().drain(..) {
+            (pending.work)(conn)?;
+        }
+        Ok(())
+    }
+
     fn change_transaction_depth(&self, by: i32, query: QueryResult<()>) -> QueryResult<()> {
         if query.is_ok() {
             self.transaction_depth
@@ -71,11 +455,71 @@ impl AnsiTransactionManager {
         use result::Error::AlreadyInTransaction;
 
         if self.transaction_depth.get() == 0 {
-            self.change_transaction_depth(1, conn.batch_execute(sql))
+            let result = self.change_transaction_depth(1, conn.batch_execute(sql));
+            if result.is_ok() {
+                self.current_behavior.set(TransactionBehavior::from_begin_sql(sql));
+                self.current_read_only.set(sql.contains("READ ONLY"));
+                self.mark_transaction_started();
+                Self::run_hooks(&self.on_begin);
+            }
+            result
         } else {
             Err(AlreadyInTransaction)
         }
     }
+
+    /// Issues `SAVEPOINT quoted_name`, incrementing the transaction depth the same way
+    /// [`begin_transaction`](trait.TransactionManager.html#tymethod.begin_transaction) does, so
+    /// nested `transaction`/`savepoint` calls made afterwards still roll back to the right place.
+    /// Used by [`SavepointExt::savepoint`](trait.SavepointExt.html#method.savepoint).
+    pub fn begin_savepoint_sql<Conn>(&self, conn: &Conn, quoted_name: &str) -> QueryResult<()>
+    where
+        Conn: SimpleConnection,
+    {
+        let result = self.change_transaction_depth(
+            1,
+            conn.batch_execute(&format!("SAVEPOINT {}", quoted_name)),
+        );
+        if result.is_ok() {
+            Self::run_hooks(&self.on_begin);
+        }
+        result
+    }
+
+    /// Issues `ROLLBACK TO SAVEPOINT quoted_name`, decrementing the transaction depth. Used by
+    /// [`SavepointExt::savepoint`](trait.SavepointExt.html#method.savepoint).
+    pub fn rollback_savepoint_sql<Conn>(&self, conn: &Conn, quoted_name: &str) -> QueryResult<()>
+    where
+        Conn: SimpleConnection,
+    {
+        let depth_before = self.transaction_depth.get();
+        let result = self.change_transaction_depth(
+            -1,
+            conn.batch_execute(&format!("ROLLBACK TO SAVEPOINT {}", quoted_name)),
+        );
+        if result.is_ok() {
+            self.discard_pending_after(depth_before);
+            Self::run_hooks(&self.on_rollback);
+        }
+        result
+    }
+
+    /// Issues `RELEASE SAVEPOINT quoted_name`, decrementing the transaction depth. Used by
+    /// [`SavepointExt::savepoint`](trait.SavepointExt.html#method.savepoint).
+    pub fn commit_savepoint_sql<Conn>(&self, conn: &Conn, quoted_name: &str) -> QueryResult<()>
+    where
+        Conn: SimpleConnection,
+    {
+        let result = self.change_transaction_depth(
+            -1,
+            conn.batch_execute(&format!("RELEASE SAVEPOINT {}", quoted_name)),
+        );
+        if result.is_ok() {
+            self.run_pending_after_commit_if_top_level(self.transaction_depth.get());
+            Self::run_hooks(&self.on_commit);
+        }
+        result
+    }
 }
 
 impl<Conn> TransactionManager<Conn> for AnsiTransactionManager
@@ -85,47 +529,505 @@ where
 {
     fn begin_transaction(&self, conn: &Conn) -> QueryResult<()> {
         let transaction_depth = self.transaction_depth.get();
-        self.change_transaction_depth(
-            1,
-            if transaction_depth == 0 {
-                conn.batch_execute("BEGIN")
-            } else {
-                conn.batch_execute(&format!("SAVEPOINT diesel_savepoint_{}", transaction_depth))
-            },
-        )
+        if transaction_depth == 0 {
+            let sql = self.begin_sql_for_depth(0, String::from("BEGIN"));
+            let result = self.change_transaction_depth(1, conn.batch_execute(&sql));
+            if result.is_ok() {
+                self.current_behavior
+                    .set(TransactionBehavior::from_begin_sql(&sql));
+                self.current_read_only.set(sql.contains("READ ONLY"));
+                self.mark_transaction_started();
+                Self::run_hooks(&self.on_begin);
+            }
+            return result;
+        }
+
+        self.check_long_running_transaction();
+        match self.nested_transaction_mode.get() {
+            NestedTransactionMode::Savepoint => {
+                let sql = self.begin_sql_for_depth(
+                    transaction_depth as u32,
+                    format!("SAVEPOINT diesel_savepoint_{}", transaction_depth),
+                );
+                let result = self.change_transaction_depth(1, conn.batch_execute(&sql));
+                if result.is_ok() {
+                    self.open_levels
+                        .borrow_mut()
+                        .push(NestedTransactionMode::Savepoint);
+                    Self::run_hooks(&self.on_begin);
+                }
+                result
+            }
+            NestedTransactionMode::Join => {
+                self.transaction_depth.set(transaction_depth + 1);
+                self.open_levels
+                    .borrow_mut()
+                    .push(NestedTransactionMode::Join);
+                Self::run_hooks(&self.on_begin);
+                Ok(())
+            }
+            NestedTransactionMode::Error => Err(Error::AlreadyInTransaction),
+        }
     }
 
     fn rollback_transaction(&self, conn: &Conn) -> QueryResult<()> {
         let transaction_depth = self.transaction_depth.get();
-        self.change_transaction_depth(
-            -1,
-            if transaction_depth == 1 {
-                conn.batch_execute("ROLLBACK")
-            } else {
+        self.check_long_running_transaction();
+        if transaction_depth <= 1 {
+            let result = self.change_transaction_depth(-1, conn.batch_execute("ROLLBACK"));
+            if result.is_ok() {
+                self.open_levels.borrow_mut().clear();
+                self.discard_pending_after(transaction_depth);
+                self.mark_transaction_closed();
+                Self::run_hooks(&self.on_rollback);
+            }
+            return result;
+        }
+
+        if self.open_levels.borrow().last().map(|m| *m) == Some(NestedTransactionMode::Join) {
+            // There's no savepoint backing this level (or any `Join` level below it that's
+            // still open), so the only honest option is to roll back everything all the way out.
+            let result = self.change_transaction_depth(
+                -transaction_depth,
+                conn.batch_execute("ROLLBACK"),
+            );
+            if result.is_ok() {
+                self.open_levels.borrow_mut().clear();
+                self.discard_pending_after(1);
+                self.mark_transaction_closed();
+                Self::run_hooks(&self.on_rollback);
+            }
+            result
+        } else {
+            let result = self.change_transaction_depth(
+                -1,
                 conn.batch_execute(&format!(
                     "ROLLBACK TO SAVEPOINT diesel_savepoint_{}",
                     transaction_depth - 1
-                ))
-            },
-        )
+                )),
+            );
+            if result.is_ok() {
+                self.open_levels.borrow_mut().pop();
+                self.discard_pending_after(transaction_depth);
+                Self::run_hooks(&self.on_rollback);
+            }
+            result
+        }
     }
 
     fn commit_transaction(&self, conn: &Conn) -> QueryResult<()> {
         let transaction_depth = self.transaction_depth.get();
-        self.change_transaction_depth(
-            -1,
-            if transaction_depth <= 1 {
-                conn.batch_execute("COMMIT")
-            } else {
+        self.check_long_running_transaction();
+        if transaction_depth <= 1 {
+            self.flush_deferred_work(conn)?;
+            let result = self.change_transaction_depth(-1, conn.batch_execute("COMMIT"));
+            if result.is_ok() {
+                self.open_levels.borrow_mut().clear();
+                self.mark_transaction_closed();
+                self.run_pending_after_commit_if_top_level(self.transaction_depth.get());
+                Self::run_hooks(&self.on_commit);
+            }
+            return result;
+        }
+
+        if self.open_levels.borrow().last().map(|m| *m) == Some(NestedTransactionMode::Join) {
+            self.transaction_depth.set(transaction_depth - 1);
+            self.open_levels.borrow_mut().pop();
+            Self::run_hooks(&self.on_commit);
+            Ok(())
+        } else {
+            let result = self.change_transaction_depth(
+                -1,
                 conn.batch_execute(&format!(
                     "RELEASE SAVEPOINT diesel_savepoint_{}",
                     transaction_depth - 1
-                ))
-            },
-        )
+                )),
+            );
+            if result.is_ok() {
+                self.open_levels.borrow_mut().pop();
+                self.run_pending_after_commit_if_top_level(self.transaction_depth.get());
+                Self::run_hooks(&self.on_commit);
+            }
+            result
+        }
     }
 
     fn get_transaction_depth(&self) -> u32 {
         self.transaction_depth.get() as u32
     }
 }
+
+/// Adds [`savepoint`](#tymethod.savepoint), a public, *named* counterpart to the anonymous
+/// savepoints [`transaction`](../trait.Connection.html#method.transaction) already creates
+/// automatically whenever it's nested inside another transaction.
+///
+/// Implemented for every [`Connection`](../trait.Connection.html) built on
+/// [`AnsiTransactionManager`](struct.AnsiTransactionManager.html) -- which is every backend
+/// Diesel ships (Pg, MySQL, SQLite).
+pub trait SavepointExt: Connection<TransactionManager = AnsiTransactionManager> {
+    /// Runs `f` inside a `SAVEPOINT` named `name`, releasing the savepoint if `f` returns `Ok`
+    /// and rolling back to it (without rolling back whatever transaction it's nested in) if `f`
+    /// returns `Err`.
+    ///
+    /// Must be called with an outer [`transaction`](../trait.Connection.html#method.transaction)
+    /// (or another `savepoint`) already open, since a bare `SAVEPOINT` outside of a transaction
+    /// is rejected by every backend Diesel supports; that failure is returned as a
+    /// `DatabaseError` from `savepoint` itself, and `f` is not run.
+    ///
+    /// `name` is used verbatim in the emitted SQL and is not quoted or otherwise escaped, so
+    /// don't build it from untrusted input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../doctest_setup.rs");
+    /// use diesel::connection::SavepointExt;
+    /// use diesel::result::Error;
+    ///
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use schema::users::dsl::*;
+    /// #     let conn = establish_connection();
+    /// conn.transaction::<_, Error, _>(|| {
+    ///     diesel::insert_into(users).values(name.eq("Ruby")).execute(&conn)?;
+    ///
+    ///     let attempt: Result<(), Error> = conn.savepoint("before_pearl", || {
+    ///         diesel::insert_into(users).values(name.eq("Pearl")).execute(&conn)?;
+    ///         Err(Error::RollbackTransaction)
+    ///     });
+    ///     assert!(attempt.is_err());
+    ///
+    ///     let all_names = users.select(name).load::<String>(&conn)?;
+    ///     assert!(all_names.contains(&"Ruby".to_string()));
+    ///     assert!(!all_names.contains(&"Pearl".to_string()));
+    ///
+    ///     Ok(())
+    /// })
+    /// # }
+    /// ```
+    fn savepoint<T, E, F>(&self, name: &str, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+        E: From<Error>,
+    {
+        let transaction_manager = self.transaction_manager();
+        try!(transaction_manager.begin_savepoint_sql(self, name));
+        match f() {
+            Ok(value) => {
+                try!(transaction_manager.commit_savepoint_sql(self, name));
+                Ok(value)
+            }
+            Err(e) => {
+                try!(transaction_manager.rollback_savepoint_sql(self, name));
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<Conn> SavepointExt for Conn
+where
+    Conn: Connection<TransactionManager = AnsiTransactionManager>,
+{
+}
+
+/// Adds [`transaction_state`](#method.transaction_state), exposing a snapshot of the
+/// connection's [`TransactionState`](struct.TransactionState.html) for middleware and debugging
+/// tools.
+///
+/// Implemented for every [`Connection`](../trait.Connection.html) built on
+/// [`AnsiTransactionManager`](struct.AnsiTransactionManager.html) -- which is every backend
+/// Diesel ships (Pg, MySQL, SQLite).
+pub trait TransactionStateExt: Connection<TransactionManager = AnsiTransactionManager>
+where
+    <Self as Connection>::Backend: UsesAnsiSavepointSyntax,
+{
+    /// Returns a snapshot of this connection's current transaction state.
+    fn transaction_state(&self) -> TransactionState {
+        let transaction_manager = self.transaction_manager();
+        TransactionState {
+            depth: <AnsiTransactionManager as TransactionManager<Self>>::get_transaction_depth(
+                transaction_manager,
+            ),
+            behavior: transaction_manager.current_behavior.get(),
+            read_only: transaction_manager.current_read_only.get(),
+            started_at: transaction_manager.transaction_started_at.get(),
+        }
+    }
+}
+
+impl<Conn> TransactionStateExt for Conn
+where
+    Conn: Connection<TransactionManager = AnsiTransactionManager>,
+    Conn::Backend: UsesAnsiSavepointSyntax,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NestedTransactionMode;
+    use dsl::sql;
+    use prelude::*;
+    use result::Error;
+    use sql_types::Integer;
+
+    #[cfg(feature = "sqlite")]
+    fn connection_with_table() -> ::sqlite::SqliteConnection {
+        let conn = ::sqlite::SqliteConnection::establish(":memory:").unwrap();
+        conn.execute("CREATE TABLE t (id INTEGER)").unwrap();
+        conn
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn row_count(conn: &::sqlite::SqliteConnection) -> i32 {
+        ::select(sql::<Integer>("(SELECT COUNT(*) FROM t)"))
+            .get_result(conn)
+            .unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn savepoint_releases_on_ok_and_keeps_its_work() {
+        use connection::SavepointExt;
+
+        let conn = connection_with_table();
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            conn.execute("INSERT INTO t VALUES (1)")?;
+            conn.savepoint("inner", || -> Result<(), Error> {
+                conn.execute("INSERT INTO t VALUES (2)")?;
+                Ok(())
+            })?;
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(2, row_count(&conn));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn savepoint_rolls_back_to_itself_without_rolling_back_the_outer_transaction() {
+        use connection::SavepointExt;
+
+        let conn = connection_with_table();
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            conn.execute("INSERT INTO t VALUES (1)")?;
+
+            let attempt: Result<(), Error> = conn.savepoint("inner", || {
+                conn.execute("INSERT INTO t VALUES (2)")?;
+                Err(Error::RollbackTransaction)
+            });
+            assert!(attempt.is_err());
+
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(1, row_count(&conn));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn on_transaction_commit_fires_once_the_outermost_transaction_commits() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let conn = connection_with_table();
+        let fired = Arc::new(AtomicBool::new(false));
+        let fired_in_callback = Arc::clone(&fired);
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            conn.transaction_manager()
+                .on_transaction_commit(move || fired_in_callback.store(true, Ordering::SeqCst));
+            assert!(!fired.load(Ordering::SeqCst));
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert!(fired.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn on_transaction_commit_is_discarded_if_the_transaction_rolls_back() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let conn = connection_with_table();
+        let fired = Arc::new(AtomicBool::new(false));
+        let fired_in_callback = Arc::clone(&fired);
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            conn.transaction_manager()
+                .on_transaction_commit(move || fired_in_callback.store(true, Ordering::SeqCst));
+            Err(Error::RollbackTransaction)
+        });
+
+        assert!(result.is_err());
+        assert!(!fired.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn on_transaction_commit_registered_inside_a_savepoint_waits_for_the_outer_commit() {
+        use connection::SavepointExt;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let conn = connection_with_table();
+        let fired = Arc::new(AtomicBool::new(false));
+        let fired_in_callback = Arc::clone(&fired);
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            conn.savepoint("inner", || -> Result<(), Error> {
+                conn.transaction_manager().on_transaction_commit(move || {
+                    fired_in_callback.store(true, Ordering::SeqCst)
+                });
+                Ok(())
+            })?;
+            assert!(!fired.load(Ordering::SeqCst));
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert!(fired.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn queue_deferred_work_runs_as_part_of_the_commit_it_was_queued_before() {
+        let conn = connection_with_table();
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            conn.execute("INSERT INTO t VALUES (1)")?;
+            conn.transaction_manager()
+                .queue_deferred_work(|c| c.batch_execute("INSERT INTO t VALUES (2)"));
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(2, row_count(&conn));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn queue_deferred_work_is_dropped_without_running_if_the_transaction_rolls_back() {
+        let conn = connection_with_table();
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            conn.execute("INSERT INTO t VALUES (1)")?;
+            conn.transaction_manager()
+                .queue_deferred_work(|c| c.batch_execute("INSERT INTO t VALUES (2)"));
+            Err(Error::RollbackTransaction)
+        });
+
+        assert!(result.is_err());
+        assert_eq!(0, row_count(&conn));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn join_mode_treats_nested_transactions_as_no_ops_and_cascades_rollback() {
+        let conn = connection_with_table();
+        conn.transaction_manager()
+            .set_nested_transaction_mode(NestedTransactionMode::Join);
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            conn.execute("INSERT INTO t VALUES (1)")?;
+            let inner: Result<(), Error> = conn.transaction(|| {
+                conn.execute("INSERT INTO t VALUES (2)")?;
+                Err(Error::RollbackTransaction)
+            });
+            assert!(inner.is_err());
+            Ok(())
+        });
+
+        // There's no savepoint to roll back to in `Join` mode, so the inner rollback takes the
+        // outer transaction down with it, even though the outer closure itself returned `Ok`.
+        assert!(result.is_err());
+        assert_eq!(0, row_count(&conn));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn error_mode_rejects_a_nested_transaction_instead_of_opening_one() {
+        let conn = connection_with_table();
+        conn.transaction_manager()
+            .set_nested_transaction_mode(NestedTransactionMode::Error);
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            conn.execute("INSERT INTO t VALUES (1)")?;
+            let inner: Result<(), Error> = conn.transaction(|| Ok(()));
+            assert!(match inner {
+                Err(Error::AlreadyInTransaction) => true,
+                _ => false,
+            });
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(1, row_count(&conn));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn watchdog_fires_once_a_transaction_has_been_open_at_least_the_threshold() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::thread::sleep;
+        use std::time::Duration;
+
+        let conn = connection_with_table();
+        let fired = Arc::new(AtomicBool::new(false));
+        let fired_in_callback = Arc::clone(&fired);
+
+        conn.transaction_manager()
+            .warn_on_long_running_transactions(Duration::from_millis(1), move |_elapsed, _bt| {
+                fired_in_callback.store(true, Ordering::SeqCst);
+            });
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            sleep(Duration::from_millis(20));
+            // The watchdog only checks opportunistically, at the next begin/commit/rollback --
+            // this nested transaction's begin/commit pair is what actually triggers the check.
+            let _: Result<(), Error> = conn.transaction(|| Ok(()));
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert!(fired.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn watchdog_does_not_fire_for_a_transaction_that_stays_under_the_threshold() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let conn = connection_with_table();
+        let fired = Arc::new(AtomicBool::new(false));
+        let fired_in_callback = Arc::clone(&fired);
+
+        conn.transaction_manager().warn_on_long_running_transactions(
+            Duration::from_secs(3600),
+            move |_elapsed, _bt| {
+                fired_in_callback.store(true, Ordering::SeqCst);
+            },
+        );
+
+        let result: Result<(), Error> = conn.transaction(|| {
+            let _: Result<(), Error> = conn.transaction(|| Ok(()));
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert!(!fired.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,131 @@
+//! A [`Connection`] wrapper that runs an `on_connect`/`on_disconnect` pair around the wrapped
+//! connection's lifetime.
+//!
+//! `on_transaction_begin`/`on_transaction_commit`/`on_transaction_rollback` hooks don't need this
+//! wrapper — register them directly on `conn.transaction_manager()`'s
+//! [`AnsiTransactionManager`](../connection/struct.AnsiTransactionManager.html), which every
+//! ANSI-savepoint backend's connection already carries. Connect/disconnect are different:
+//! [`Connection::establish`] is a static method with no instance yet to hang a hook off of, and
+//! there's no `Drop` hook available for `on_disconnect` without adding a field to every backend's
+//! connection struct. [`HookedConnection`] sidesteps both by wrapping an already-established
+//! connection, running `on_connect` immediately and `on_disconnect` from its own `Drop` impl.
+//!
+//! [`Connection`]: ../connection/trait.Connection.html
+//! [`Connection::establish`]: ../connection/trait.Connection.html#tymethod.establish
+
+use backend::UsesAnsiSavepointSyntax;
+use connection::{AnsiTransactionManager, Connection, SimpleConnection};
+use deserialize::{Queryable, QueryableByName};
+use query_builder::{AsQuery, QueryFragment, QueryId};
+use result::{ConnectionError, ConnectionResult, QueryResult};
+use sql_types::HasSqlType;
+
+/// Wraps `conn`, having already run `on_connect` against it, and running `on_disconnect` when
+/// this wrapper is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../doctest_setup.rs");
+/// use diesel::connection::Connection;
+/// use diesel::connection_hooks::HookedConnection;
+///
+/// # fn main() {
+/// #     run_test().unwrap();
+/// # }
+/// #
+/// # fn run_test() -> QueryResult<()> {
+/// let raw_conn = establish_connection();
+/// let conn = HookedConnection::new(
+///     raw_conn,
+///     |conn| conn.execute("SELECT 1").map(|_| ()),
+///     |_conn| println!("connection dropped"),
+/// )?;
+/// #     let _ = conn;
+/// #     Ok(())
+/// # }
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct HookedConnection<C> {
+    conn: C,
+    on_disconnect: Box<Fn(&C) + Send + Sync>,
+}
+
+impl<C: Connection> HookedConnection<C> {
+    /// Wraps an already-established `conn`, immediately running `on_connect` against it.
+    ///
+    /// If `on_connect` returns an error, `conn` is dropped without `on_disconnect` running (it
+    /// never successfully "connected" from this wrapper's point of view).
+    pub fn new<F, G>(conn: C, on_connect: F, on_disconnect: G) -> QueryResult<Self>
+    where
+        F: FnOnce(&C) -> QueryResult<()>,
+        G: Fn(&C) + Send + Sync + 'static,
+    {
+        on_connect(&conn)?;
+        Ok(HookedConnection {
+            conn,
+            on_disconnect: Box::new(on_disconnect),
+        })
+    }
+}
+
+impl<C> Drop for HookedConnection<C> {
+    fn drop(&mut self) {
+        (self.on_disconnect)(&self.conn);
+    }
+}
+
+impl<C: Connection> SimpleConnection for HookedConnection<C> {
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        self.conn.batch_execute(query)
+    }
+}
+
+impl<C> Connection for HookedConnection<C>
+where
+    C: Connection<TransactionManager = AnsiTransactionManager>,
+    C::Backend: UsesAnsiSavepointSyntax,
+{
+    type Backend = C::Backend;
+    type TransactionManager = AnsiTransactionManager;
+
+    fn establish(_: &str) -> ConnectionResult<Self> {
+        Err(ConnectionError::BadConnection(String::from(
+            "HookedConnection cannot be established from a single database URL, use HookedConnection::new",
+        )))
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.conn.execute(query)
+    }
+
+    fn query_by_index<T, U>(&self, source: T) -> QueryResult<Vec<U>>
+    where
+        T: AsQuery,
+        T::Query: QueryFragment<Self::Backend> + QueryId,
+        Self::Backend: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Self::Backend>,
+    {
+        self.conn.query_by_index(source)
+    }
+
+    fn query_by_name<T, U>(&self, source: &T) -> QueryResult<Vec<U>>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+        U: QueryableByName<Self::Backend>,
+    {
+        self.conn.query_by_name(source)
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+    {
+        self.conn.execute_returning_count(source)
+    }
+
+    fn transaction_manager(&self) -> &Self::TransactionManager {
+        self.conn.transaction_manager()
+    }
+}
@@ -164,6 +164,20 @@ where
 /// - For third party backends, consult that backend's documentation.
 ///
 /// [`MysqlType`]: ../mysql/enum.MysqlType.html
+///
+/// ### A note on zero-copy deserialization
+///
+/// `Self: Sized` here, with no lifetime tying it back to `bytes`, so
+/// `from_sql` must return an owned value -- there is no supported way to
+/// implement this trait for `&str` or `&[u8]` and borrow directly from the
+/// row. Backends that already hand back a borrowed buffer (e.g. SQLite's
+/// `SqliteValue::read_text`/`read_blob`) can still avoid an extra
+/// allocation *inside* an impl that ultimately returns an owned type -- see
+/// the `bigdecimal`/`rust_decimal` numeric impls for an example that parses
+/// straight out of the borrowed `&str` instead of first collecting it into a
+/// `String`. Getting an actual `&'row str` out of `Queryable` would require
+/// a lifetime on this trait (and `Queryable`/`FromSqlRow` in turn), which is
+/// a breaking change we haven't taken on.
 pub trait FromSql<A, DB: Backend>: Sized {
     /// See the trait documentation.
     fn from_sql(bytes: Option<&DB::RawValue>) -> Result<Self>;
@@ -0,0 +1,138 @@
+//! Support for deserializing a single column into a dynamically-typed value.
+//!
+//! See [`DynamicValue`] for details.
+
+#[cfg(feature = "serde")]
+extern crate serde;
+
+use backend::Backend;
+use deserialize::{self, FromSql};
+use sql_types::{BigInt, Binary, Bool, Double, Nullable, Text};
+
+/// A single column value of unknown static SQL type, deserialized from a raw
+/// database row.
+///
+/// This covers the scalar SQL types that all of Diesel's backends can
+/// decode, for use by ad-hoc admin/reporting code that runs
+/// [`sql_query`](../fn.sql_query.html) without declaring a
+/// [`QueryableByName`](../deserialize/trait.QueryableByName.html) struct with
+/// a concrete Rust type for every column.
+///
+/// Each variant has a matching `FromSql` impl for the corresponding
+/// `Nullable` SQL type, so a field can be declared with whichever nullable
+/// type matches the column, for example `#[sql_type = "Nullable<Text>"] col:
+/// DynamicValue`.
+///
+/// Note that this only covers the case where the *value's Rust type* is
+/// unknown until runtime -- the column's *SQL type* still has to be named at
+/// compile time (as `Text`, `BigInt`, and so on), same as any other
+/// `QueryableByName` field. [`NamedRow::column_names`] can be used to discover
+/// *which* columns a query returned, but Postgres and MySQL hand back column
+/// values as untagged bytes, so decoding one still requires knowing its SQL
+/// type up front -- there's no way to build a fully typeless `Vec<HashMap<String,
+/// DynamicValue>>` loader that works across all backends.
+///
+/// [`NamedRow::column_names`]: ../row/trait.NamedRow.html#method.column_names
+///
+/// With the `serde` feature enabled, `DynamicValue` also implements
+/// `serde::Serialize`, so a loaded row can be handed straight to a JSON API
+/// without an intermediate conversion step. See also
+/// [`serde_row`](../serde_row/index.html) for loading rows into an arbitrary
+/// `serde::Deserialize` type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynamicValue {
+    /// A `TEXT` (or similar) column value.
+    Text(String),
+    /// An integral column value, widened to `i64`.
+    Integer(i64),
+    /// A floating point column value, widened to `f64`.
+    Double(f64),
+    /// A `BOOLEAN` column value.
+    Bool(bool),
+    /// A `BLOB`/`BYTEA` (or similar) column value.
+    Binary(Vec<u8>),
+    /// A SQL `NULL`.
+    Null,
+}
+
+impl<DB> FromSql<Nullable<Text>, DB> for DynamicValue
+where
+    DB: Backend,
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: Option<&DB::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            None => Ok(DynamicValue::Null),
+            Some(_) => String::from_sql(bytes).map(DynamicValue::Text),
+        }
+    }
+}
+
+impl<DB> FromSql<Nullable<BigInt>, DB> for DynamicValue
+where
+    DB: Backend,
+    i64: FromSql<BigInt, DB>,
+{
+    fn from_sql(bytes: Option<&DB::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            None => Ok(DynamicValue::Null),
+            Some(_) => i64::from_sql(bytes).map(DynamicValue::Integer),
+        }
+    }
+}
+
+impl<DB> FromSql<Nullable<Double>, DB> for DynamicValue
+where
+    DB: Backend,
+    f64: FromSql<Double, DB>,
+{
+    fn from_sql(bytes: Option<&DB::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            None => Ok(DynamicValue::Null),
+            Some(_) => f64::from_sql(bytes).map(DynamicValue::Double),
+        }
+    }
+}
+
+impl<DB> FromSql<Nullable<Bool>, DB> for DynamicValue
+where
+    DB: Backend,
+    bool: FromSql<Bool, DB>,
+{
+    fn from_sql(bytes: Option<&DB::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            None => Ok(DynamicValue::Null),
+            Some(_) => bool::from_sql(bytes).map(DynamicValue::Bool),
+        }
+    }
+}
+
+impl<DB> FromSql<Nullable<Binary>, DB> for DynamicValue
+where
+    DB: Backend,
+    Vec<u8>: FromSql<Binary, DB>,
+{
+    fn from_sql(bytes: Option<&DB::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            None => Ok(DynamicValue::Null),
+            Some(_) => Vec::from_sql(bytes).map(DynamicValue::Binary),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl self::serde::Serialize for DynamicValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: self::serde::Serializer,
+    {
+        match *self {
+            DynamicValue::Text(ref x) => serializer.serialize_str(x),
+            DynamicValue::Integer(x) => serializer.serialize_i64(x),
+            DynamicValue::Double(x) => serializer.serialize_f64(x),
+            DynamicValue::Bool(x) => serializer.serialize_bool(x),
+            DynamicValue::Binary(ref x) => serializer.serialize_bytes(x),
+            DynamicValue::Null => serializer.serialize_none(),
+        }
+    }
+}
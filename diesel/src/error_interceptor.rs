@@ -0,0 +1,164 @@
+//! A [`Connection`] wrapper that runs a hook on every `QueryResult::Err` before it's returned,
+//! so applications can centrally add metrics, redact sensitive data, or translate errors without
+//! wrapping every call site.
+//!
+//! [`Connection`]: ../connection/trait.Connection.html
+
+use backend::{Backend, UsesAnsiSavepointSyntax};
+use connection::{AnsiTransactionManager, Connection, SimpleConnection};
+use deserialize::{Queryable, QueryableByName};
+use query_builder::{debug_query, AsQuery, QueryFragment, QueryId};
+use result::{ConnectionError, ConnectionResult, Error, QueryResult};
+use sql_types::HasSqlType;
+
+/// The kind of operation an [`ExecutionContext`](struct.ExecutionContext.html) describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionKind {
+    /// `execute`/`batch_execute` (raw SQL text, no bind values).
+    Execute,
+    /// `query_by_index`/`query_by_name` (a read).
+    Query,
+    /// `execute_returning_count` (a write sent through the query builder).
+    ExecuteReturningCount,
+}
+
+/// What [`ErrorInterceptor`](struct.ErrorInterceptor.html) was doing when the error it hands to
+/// its hook occurred.
+#[derive(Debug, Clone)]
+pub struct ExecutionContext {
+    /// The SQL that was being executed, if any was available. For `execute`/`batch_execute` this
+    /// is the raw query text passed in; for query-builder-based calls it's the
+    /// [`debug_query`](../query_builder/fn.debug_query.html) output: the rendered SQL followed
+    /// by a ` -- binds: [...]` list of the bind values.
+    pub sql: String,
+    /// What kind of operation failed.
+    pub kind: ExecutionKind,
+}
+
+/// Wraps `C`, calling `on_error` with each error and the [`ExecutionContext`] it occurred in
+/// before returning it.
+///
+/// `on_error` returns the error to actually hand back to the caller, so it can translate or wrap
+/// the error as well as merely observe it (return it unchanged to leave it as-is).
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../doctest_setup.rs");
+/// use diesel::error_interceptor::ErrorInterceptor;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// # fn main() {
+/// #     run_test().unwrap();
+/// # }
+/// #
+/// # fn run_test() -> QueryResult<()> {
+/// let errors_seen = Arc::new(AtomicUsize::new(0));
+/// let errors_seen_for_hook = errors_seen.clone();
+/// let conn = ErrorInterceptor::new(establish_connection(), move |error, _context| {
+///     errors_seen_for_hook.fetch_add(1, Ordering::SeqCst);
+///     error
+/// });
+/// let result = diesel::sql_query("SELECT this_is_not_a_column").execute(&conn);
+/// assert!(result.is_err());
+/// assert_eq!(1, errors_seen.load(Ordering::SeqCst));
+/// #     Ok(())
+/// # }
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct ErrorInterceptor<C> {
+    conn: C,
+    on_error: Box<Fn(Error, ExecutionContext) -> Error + Send + Sync>,
+}
+
+impl<C: Connection> ErrorInterceptor<C> {
+    /// Wraps `conn`, calling `on_error` on every error before it's returned.
+    pub fn new<F>(conn: C, on_error: F) -> Self
+    where
+        F: Fn(Error, ExecutionContext) -> Error + Send + Sync + 'static,
+    {
+        ErrorInterceptor {
+            conn,
+            on_error: Box::new(on_error),
+        }
+    }
+
+    fn intercept<T>(&self, result: QueryResult<T>, sql: String, kind: ExecutionKind) -> QueryResult<T> {
+        result.map_err(|error| (self.on_error)(error, ExecutionContext { sql, kind }))
+    }
+
+    fn render<T, DB>(source: &T) -> String
+    where
+        DB: Backend,
+        DB::QueryBuilder: Default,
+        T: QueryFragment<DB>,
+    {
+        debug_query::<DB, _>(source).to_string()
+    }
+}
+
+impl<C: Connection> SimpleConnection for ErrorInterceptor<C> {
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        let result = self.conn.batch_execute(query);
+        self.intercept(result, query.to_string(), ExecutionKind::Execute)
+    }
+}
+
+impl<C> Connection for ErrorInterceptor<C>
+where
+    C: Connection<TransactionManager = AnsiTransactionManager>,
+    C::Backend: UsesAnsiSavepointSyntax,
+    <C::Backend as Backend>::QueryBuilder: Default,
+{
+    type Backend = C::Backend;
+    type TransactionManager = AnsiTransactionManager;
+
+    fn establish(_: &str) -> ConnectionResult<Self> {
+        Err(ConnectionError::BadConnection(String::from(
+            "ErrorInterceptor cannot be established from a single database URL, use ErrorInterceptor::new",
+        )))
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        let result = self.conn.execute(query);
+        self.intercept(result, query.to_string(), ExecutionKind::Execute)
+    }
+
+    fn query_by_index<T, U>(&self, source: T) -> QueryResult<Vec<U>>
+    where
+        T: AsQuery,
+        T::Query: QueryFragment<Self::Backend> + QueryId,
+        Self::Backend: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Self::Backend>,
+    {
+        let query = source.as_query();
+        let sql = Self::render(&query);
+        let result = self.conn.query_by_index(query);
+        self.intercept(result, sql, ExecutionKind::Query)
+    }
+
+    fn query_by_name<T, U>(&self, source: &T) -> QueryResult<Vec<U>>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+        U: QueryableByName<Self::Backend>,
+    {
+        let sql = Self::render(source);
+        let result = self.conn.query_by_name(source);
+        self.intercept(result, sql, ExecutionKind::Query)
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+    {
+        let sql = Self::render(source);
+        let result = self.conn.execute_returning_count(source);
+        self.intercept(result, sql, ExecutionKind::ExecuteReturningCount)
+    }
+
+    fn transaction_manager(&self) -> &Self::TransactionManager {
+        self.conn.transaction_manager()
+    }
+}
@@ -0,0 +1,110 @@
+use std::marker::PhantomData;
+
+use backend::Backend;
+use expression::{AppearsOnTable, Expression, NonAggregate, SelectableExpression};
+use query_builder::{AstPass, QueryFragment};
+use result::QueryResult;
+
+/// Maps a Diesel SQL type to the name a given backend uses for it inside a
+/// `CAST(expr AS type)` expression.
+///
+/// This is implemented for the common scalar SQL types on each backend.
+/// Implement it for your own types if you need to `cast` to them.
+pub trait SqlTypeName<DB: Backend> {
+    /// The name of this type as it appears in a `CAST` expression on `DB`.
+    const SQL_TYPE_NAME: &'static str;
+}
+
+/// Represents a SQL `CAST(expr AS type)` expression.
+///
+/// See [`CastExpressionMethods::cast`](trait.CastExpressionMethods.html#method.cast)
+/// for details.
+#[derive(Debug, Clone, Copy, QueryId)]
+#[doc(hidden)]
+pub struct Cast<Expr, ST> {
+    expr: Expr,
+    _marker: PhantomData<ST>,
+}
+
+impl<Expr, ST> Cast<Expr, ST> {
+    pub fn new(expr: Expr) -> Self {
+        Cast {
+            expr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Expr, ST> Expression for Cast<Expr, ST>
+where
+    Expr: Expression,
+{
+    type SqlType = ST;
+}
+
+impl<Expr, ST, QS> SelectableExpression<QS> for Cast<Expr, ST>
+where
+    Expr: SelectableExpression<QS>,
+    Cast<Expr, ST>: AppearsOnTable<QS>,
+{
+}
+
+impl<Expr, ST, QS> AppearsOnTable<QS> for Cast<Expr, ST>
+where
+    Expr: AppearsOnTable<QS>,
+    Cast<Expr, ST>: Expression,
+{
+}
+
+impl<Expr, ST> NonAggregate for Cast<Expr, ST>
+where
+    Expr: NonAggregate,
+    Cast<Expr, ST>: Expression,
+{
+}
+
+impl<Expr, ST, DB> QueryFragment<DB> for Cast<Expr, ST>
+where
+    DB: Backend,
+    Expr: QueryFragment<DB>,
+    ST: SqlTypeName<DB>,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.push_sql("CAST(");
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(" AS ");
+        out.push_sql(ST::SQL_TYPE_NAME);
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+/// Adds the `cast` method to `Expression` types, generating a SQL
+/// `CAST(expr AS type)` expression.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../doctest_setup.rs");
+/// # use diesel::dsl::*;
+/// #
+/// # fn main() {
+/// #     use schema::animals::dsl::*;
+/// #     use diesel::sql_types::Text;
+/// #     let connection = establish_connection();
+/// let result = animals
+///     .select(legs.cast::<Text>())
+///     .filter(name.eq("Jack"))
+///     .first(&connection);
+/// assert_eq!(Ok("4".to_string()), result);
+/// # }
+/// ```
+pub trait CastExpressionMethods: Expression + Sized {
+    /// Casts `self` to `ST`, generating a SQL `CAST(self AS ST)` expression.
+    fn cast<ST>(self) -> Cast<Self, ST> {
+        Cast::new(self)
+    }
+}
+
+impl<T: Expression> CastExpressionMethods for T {}
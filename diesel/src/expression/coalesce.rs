@@ -0,0 +1,49 @@
+//! Support for `COALESCE` and `NULLIF`.
+use sql_types::{IntoNullable, SingleValue};
+
+sql_function! {
+    /// Represents a SQL `NULLIF` expression, evaluating to `NULL` if `x` and
+    /// `y` are equal, and to `x` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../doctest_setup.rs");
+    /// # use diesel::dsl::*;
+    /// #
+    /// # fn main() {
+    /// #     use schema::animals::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let result = animals.select(nullif(legs, 4)).load(&connection);
+    /// assert_eq!(Ok(vec![None, Some(8)]), result);
+    /// # }
+    /// ```
+    fn nullif<ST: SingleValue + IntoNullable>(x: ST, y: ST) -> ST::Nullable;
+}
+
+sql_function! {
+    /// Represents a SQL `COALESCE` expression, evaluating to `x` if it is not
+    /// `NULL`, and to `y` otherwise.
+    ///
+    /// Because `y` is required to be non-null, the result of this function is
+    /// always non-null. To coalesce more than two expressions, nest calls to
+    /// this function -- `COALESCE` is associative, so
+    /// `coalesce(a, coalesce(b, c))` is equivalent to `coalesce(a, b, c)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../doctest_setup.rs");
+    /// # use diesel::dsl::*;
+    /// #
+    /// # fn main() {
+    /// #     use schema::animals::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let result = animals.select(coalesce(name, "unknown")).load(&connection);
+    /// assert_eq!(Ok(vec!["Jack".to_string(), "unknown".to_string()]), result);
+    /// # }
+    /// ```
+    fn coalesce<ST: SingleValue + IntoNullable>(x: <ST as IntoNullable>::Nullable, y: ST) -> ST;
+}
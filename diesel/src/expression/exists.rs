@@ -8,7 +8,10 @@ use sql_types::Bool;
 /// Creates a SQL `EXISTS` expression.
 ///
 /// The argument must be a complete SQL query. The query may reference columns
-/// from the outer table.
+/// from the outer table, and may be a [`BoxedSelectStatement`] for cases
+/// where the subquery is built up dynamically at runtime.
+///
+/// [`BoxedSelectStatement`]: ../query_builder/struct.BoxedSelectStatement.html
 ///
 /// # Example
 ///
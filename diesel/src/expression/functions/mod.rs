@@ -678,3 +678,5 @@ pub mod aggregate_folding;
 pub mod aggregate_ordering;
 pub mod date_and_time;
 pub mod helper_types;
+pub mod numeric;
+pub mod string;
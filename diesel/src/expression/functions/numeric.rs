@@ -0,0 +1,50 @@
+use sql_types::Double;
+
+sql_function! {
+    /// Represents a SQL `ABS` function, returning the absolute value of
+    /// `expr`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// # use diesel::dsl::*;
+    /// #
+    /// # fn main() {
+    /// #     use schema::animals::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let result = animals.select(abs(legs)).first(&connection);
+    /// assert_eq!(Ok(4), result);
+    /// # }
+    /// ```
+    fn abs<ST: ::sql_types::SingleValue>(expr: ST) -> ST;
+}
+
+sql_function! {
+    /// Represents a SQL `CEIL` function, rounding `expr` up to the nearest
+    /// integer.
+    fn ceil(expr: Double) -> Double;
+}
+
+sql_function! {
+    /// Represents a SQL `FLOOR` function, rounding `expr` down to the
+    /// nearest integer.
+    fn floor(expr: Double) -> Double;
+}
+
+sql_function! {
+    /// Represents a SQL `ROUND` function, rounding `expr` to the nearest
+    /// integer.
+    fn round(expr: Double) -> Double;
+}
+
+sql_function! {
+    /// Represents a SQL `SQRT` function.
+    fn sqrt(expr: Double) -> Double;
+}
+
+sql_function! {
+    /// Represents a SQL `POWER` function, raising `base` to `exponent`.
+    fn power(base: Double, exponent: Double) -> Double;
+}
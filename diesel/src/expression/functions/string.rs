@@ -0,0 +1,54 @@
+use sql_types::{Integer, Text};
+
+sql_function! {
+    /// Represents a SQL `LOWER` function, lower-casing `expr`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// # use diesel::dsl::*;
+    /// #
+    /// # fn main() {
+    /// #     use schema::users::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let result = users.select(lower(name)).first(&connection);
+    /// assert_eq!(Ok("sean".to_string()), result);
+    /// # }
+    /// ```
+    fn lower(expr: Text) -> Text;
+}
+
+sql_function! {
+    /// Represents a SQL `UPPER` function, upper-casing `expr`.
+    fn upper(expr: Text) -> Text;
+}
+
+sql_function! {
+    /// Represents a SQL `LENGTH` function, returning the length of `expr` in
+    /// characters.
+    fn length(expr: Text) -> Integer;
+}
+
+sql_function! {
+    /// Represents a SQL `TRIM` function, stripping leading and trailing
+    /// whitespace from `expr`.
+    fn trim(expr: Text) -> Text;
+}
+
+sql_function! {
+    /// Represents a SQL `REPLACE` function.
+    fn replace(expr: Text, pattern: Text, replacement: Text) -> Text;
+}
+
+sql_function! {
+    /// Represents a SQL `SUBSTR` function.
+    fn substr(expr: Text, start: Integer) -> Text;
+}
+
+sql_function! {
+    /// Represents a three argument SQL `SUBSTR` function.
+    #[sql_name = "substr"]
+    fn substr_with_length(expr: Text, start: Integer, length: Integer) -> Text;
+}
@@ -0,0 +1,65 @@
+//! Marker types used to track whether an expression is an aggregate.
+//!
+//! These types are the building blocks for validating that a `SELECT`
+//! clause only refers to columns that appear in the `GROUP BY` clause, or to
+//! aggregate expressions of other columns. They intentionally mirror the
+//! `Yes`/`No`/`Never` markers used elsewhere in Diesel (e.g.
+//! [`NonAggregate`](../trait.NonAggregate.html)), so that a future
+//! `ValidGrouping` bound on [`GroupByDsl`](../../query_dsl/trait.GroupByDsl.html)
+//! can be added without changing how existing expressions are written.
+
+/// Indicates that an expression is aggregate for all possible group by
+/// clauses (e.g. `count(*)`).
+#[derive(Debug, Clone, Copy)]
+pub struct Yes;
+
+/// Indicates that an expression is never aggregate (e.g. a bare column).
+#[derive(Debug, Clone, Copy)]
+pub struct No;
+
+/// Indicates that an expression can never appear in a valid query, regardless
+/// of grouping (used for expressions that mix aggregate and non-aggregate
+/// operands without any window/grouping context).
+#[derive(Debug, Clone, Copy)]
+pub struct Never;
+
+/// Determines the `IsAggregate` type for an expression which contains two
+/// or more expressions which each specify their own aggregate-ness (for
+/// example, a binary operator).
+///
+/// Two `No`s combine to `No`, a `Yes` and a `No` are only valid if the
+/// context allows it (mirroring how SQL forbids mixing aggregate and
+/// non-aggregate columns outside of `GROUP BY`), and anything involving
+/// `Never` is `Never`.
+pub trait MixedAggregates<Other = Self> {
+    /// The `IsAggregate` type of the resulting expression.
+    type Output;
+}
+
+impl MixedAggregates<No> for No {
+    type Output = No;
+}
+
+impl MixedAggregates<Yes> for Yes {
+    type Output = Yes;
+}
+
+impl MixedAggregates<No> for Yes {
+    type Output = Yes;
+}
+
+impl MixedAggregates<Yes> for No {
+    type Output = Yes;
+}
+
+impl<T> MixedAggregates<Never> for T {
+    type Output = Never;
+}
+
+impl MixedAggregates<Yes> for Never {
+    type Output = Never;
+}
+
+impl MixedAggregates<No> for Never {
+    type Output = Never;
+}
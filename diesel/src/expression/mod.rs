@@ -25,6 +25,9 @@ pub mod functions;
 pub mod array_comparison;
 #[doc(hidden)]
 pub mod bound;
+pub mod cast;
+#[doc(hidden)]
+pub mod coalesce;
 #[doc(hidden)]
 pub mod coerce;
 #[doc(hidden)]
@@ -35,6 +38,7 @@ pub mod exists;
 pub mod grouped;
 #[doc(hidden)]
 pub mod helper_types;
+pub mod is_aggregate;
 mod not;
 #[doc(hidden)]
 pub mod nullable;
@@ -51,6 +55,8 @@ pub mod subselect;
 pub mod dsl {
     use dsl::SqlTypeOf;
 
+    #[doc(inline)]
+    pub use super::coalesce::{coalesce, nullif};
     #[doc(inline)]
     pub use super::count::*;
     #[doc(inline)]
@@ -62,6 +68,10 @@ pub mod dsl {
     #[doc(inline)]
     pub use super::functions::date_and_time::*;
     #[doc(inline)]
+    pub use super::functions::numeric::*;
+    #[doc(inline)]
+    pub use super::functions::string::*;
+    #[doc(inline)]
     pub use super::not::not;
     #[doc(inline)]
     pub use super::sql_literal::sql;
@@ -69,6 +79,9 @@ pub mod dsl {
     #[cfg(feature = "postgres")]
     pub use pg::expression::dsl::*;
 
+    #[cfg(feature = "sqlite")]
+    pub use sqlite::expression::dsl::*;
+
     /// The return type of [`count(expr)`](../dsl/fn.count.html)
     pub type count<Expr> = super::count::count::HelperType<SqlTypeOf<Expr>, Expr>;
 
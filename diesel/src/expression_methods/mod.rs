@@ -12,6 +12,8 @@ mod text_expression_methods;
 
 #[doc(inline)]
 pub use self::bool_expression_methods::BoolExpressionMethods;
+#[doc(inline)]
+pub use expression::cast::CastExpressionMethods;
 #[doc(hidden)]
 pub use self::eq_all::EqAll;
 #[doc(inline)]
@@ -24,3 +26,10 @@ pub use self::text_expression_methods::TextExpressionMethods;
 #[cfg(feature = "postgres")]
 #[doc(inline)]
 pub use pg::expression::expression_methods::*;
+#[cfg(feature = "postgres")]
+#[doc(inline)]
+pub use pg::expression::filter::FilterAggregateExpressionMethods;
+
+#[cfg(feature = "sqlite")]
+#[doc(inline)]
+pub use sqlite::expression::SqliteExpressionMethods;
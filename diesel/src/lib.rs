@@ -172,24 +172,38 @@ pub mod test_helpers;
 pub mod associations;
 pub mod backend;
 pub mod connection;
+pub mod connection_hooks;
 pub mod data_types;
 pub mod deserialize;
+pub mod dynamic_value;
+pub mod error_interceptor;
 #[macro_use]
 pub mod expression;
 pub mod expression_methods;
 #[doc(hidden)]
 pub mod insertable;
+#[cfg(feature = "sqlite")]
+pub mod mock_connection;
+pub mod optimistic_lock;
 pub mod query_builder;
+pub mod query_capture;
 pub mod query_dsl;
+pub mod query_log;
 pub mod query_source;
 #[cfg(feature = "r2d2")]
 pub mod r2d2;
+pub mod read_write_split;
 pub mod result;
+pub mod retry;
 pub mod serialize;
+#[cfg(feature = "serde")]
+pub mod serde_row;
+pub mod soft_delete;
 #[macro_use]
 pub mod sql_types;
 pub mod migration;
 pub mod row;
+pub mod timestamps;
 pub mod types;
 
 #[cfg(feature = "mysql")]
@@ -331,7 +345,8 @@ pub mod prelude {
     pub use insertable::Insertable;
     #[doc(hidden)]
     pub use query_dsl::GroupByDsl;
-    pub use query_dsl::{BelongingToDsl, JoinOnDsl, QueryDsl, RunQueryDsl, SaveChangesDsl};
+    pub use query_dsl::{AsTableDsl, BelongingToDsl, CombineDsl, JoinOnDsl, QueryDsl, RunQueryDsl,
+                        SaveChangesDsl};
 
     pub use query_source::{Column, JoinTo, QuerySource, Table};
     pub use result::{ConnectionError, ConnectionResult, OptionalExtension, QueryResult};
@@ -348,8 +363,9 @@ pub use prelude::*;
 #[doc(inline)]
 pub use query_builder::debug_query;
 #[doc(inline)]
-pub use query_builder::functions::{delete, insert_into, insert_or_ignore_into, replace_into,
-                                   select, sql_query, update};
+pub use query_builder::functions::{create_index, delete, drop_index, insert_into,
+                                   insert_or_ignore_into, replace_into, select, sql_query, truncate,
+                                   update, with};
 pub use result::Error::NotFound;
 
 pub(crate) mod diesel {
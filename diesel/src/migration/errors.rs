@@ -22,6 +22,9 @@ pub enum MigrationError {
     UnknownMigrationVersion(String),
     /// No migrations had to be/ could be run
     NoMigrationRun,
+    /// An already-applied migration's checksum no longer matches the checksum recorded when it
+    /// was run, meaning its `up.sql` was edited after the fact. Contains the migration version.
+    ChecksumMismatch(String),
     ///
     #[doc(hidden)]
     __NonExhaustive,
@@ -44,6 +47,10 @@ impl Error for MigrationError {
             MigrationError::NoMigrationRun => {
                 "No migrations have been run. Did you forget `diesel migration run`?"
             }
+            MigrationError::ChecksumMismatch(_) => {
+                "The checksum of an already-applied migration no longer matches the checksum \
+                 recorded when it was run. Did you edit a migration after it had already run?"
+            }
             MigrationError::__NonExhaustive => unreachable!(),
         }
     }
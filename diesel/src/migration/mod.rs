@@ -18,6 +18,20 @@ pub trait Migration {
     fn file_path(&self) -> Option<&Path> {
         None
     }
+    /// Get a checksum of this migration's `up.sql`, used to detect that an
+    /// already-applied migration has been edited since it was run. Migrations
+    /// with no fixed SQL source to check (e.g. one generated purely in code)
+    /// may return an empty string to opt out of tamper detection.
+    fn checksum(&self) -> String {
+        String::new()
+    }
+    /// Whether the migration runner should wrap this migration's `run`/`revert` in a
+    /// transaction. Defaults to `true`. Migrations that use statements which cannot run inside a
+    /// transaction (e.g. `CREATE INDEX CONCURRENTLY` on Pg, or certain `PRAGMA` changes on
+    /// SQLite) should return `false` here.
+    fn run_in_transaction(&self) -> bool {
+        true
+    }
 }
 
 impl Migration for Box<Migration> {
@@ -35,6 +49,12 @@ impl Migration for Box<Migration> {
     fn file_path(&self) -> Option<&Path> {
         (&**self).file_path()
     }
+    fn checksum(&self) -> String {
+        (&**self).checksum()
+    }
+    fn run_in_transaction(&self) -> bool {
+        (&**self).run_in_transaction()
+    }
 }
 
 impl<'a> Migration for &'a Migration {
@@ -52,4 +72,10 @@ impl<'a> Migration for &'a Migration {
     fn file_path(&self) -> Option<&Path> {
         (&**self).file_path()
     }
+    fn checksum(&self) -> String {
+        (&**self).checksum()
+    }
+    fn run_in_transaction(&self) -> bool {
+        (&**self).run_in_transaction()
+    }
 }
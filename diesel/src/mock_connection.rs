@@ -0,0 +1,244 @@
+//! A [`Connection`] that returns canned results for expected queries, so service-layer unit
+//! tests can run without a real database.
+//!
+//! Diesel deserializes query results by asking the backend to hand back raw column bytes and
+//! decoding those with `FromSql` — there is no way to build a `Queryable` value straight from a
+//! Rust literal without a real backend behind it. So rather than accepting Rust values directly,
+//! [`MockConnection`] matches incoming queries against registered [`expect`](MockConnection::expect)
+//! calls and, on a match, runs a *substitute* SQL statement (whose result rows are the canned
+//! data, e.g. `"SELECT 1 AS id, 'Sean' AS name"`) against a private `:memory:`
+//! [`SqliteConnection`] instead of the real one, reusing SQLite's own deserialization rather than
+//! reimplementing it. This is why `MockConnection`'s backend is always
+//! [`Sqlite`](../sqlite/struct.Sqlite.html), regardless of what backend the code under test is
+//! normally used with.
+//!
+//! [`Connection`]: ../connection/trait.Connection.html
+//! [`SqliteConnection`]: ../sqlite/struct.SqliteConnection.html
+
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+use connection::{AnsiTransactionManager, Connection, SimpleConnection};
+use deserialize::{Queryable, QueryableByName};
+use query_builder::functions::sql_query;
+use query_builder::{debug_query, AsQuery, AstPass, Query, QueryFragment, QueryId};
+use result::{ConnectionError, ConnectionResult, QueryResult};
+use sql_types::HasSqlType;
+use sqlite::{Sqlite, SqliteConnection};
+
+/// What an incoming query is matched against to decide which [`expect`](MockConnection::expect)
+/// call, if any, applies to it.
+pub enum Matcher {
+    /// Matches a query whose rendered SQL equals this string exactly. The rendered SQL is the
+    /// [`debug_query`](../query_builder/fn.debug_query.html) output, so it ends with a
+    /// ` -- binds: [...]` list of the bind values.
+    Sql(String),
+    /// Matches any query of a type with this static `QueryId`, regardless of bind values. Build
+    /// one with [`Matcher::query_id`].
+    QueryId(TypeId),
+}
+
+impl Matcher {
+    /// Matches on rendered SQL text.
+    pub fn sql<S: Into<String>>(sql: S) -> Self {
+        Matcher::Sql(sql.into())
+    }
+
+    /// Matches any query of type `T`, provided `T` has a static `QueryId` (most queries built
+    /// from `table!` do; queries built with [`sql_query`](../fn.sql_query.html) don't, and can
+    /// only be matched with [`Matcher::sql`]).
+    pub fn query_id<T: QueryId>() -> Self {
+        Matcher::QueryId(
+            T::query_id().expect("Matcher::query_id requires a type with a static QueryId"),
+        )
+    }
+}
+
+struct Expectation {
+    matcher: Matcher,
+    substitute_sql: String,
+}
+
+/// A [`Connection`](../connection/trait.Connection.html) that returns canned results for
+/// expected queries. See the [module docs](index.html) for how canned results work.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// #
+/// table! {
+///     users {
+///         id -> Integer,
+///         name -> Text,
+///     }
+/// }
+///
+/// use diesel::mock_connection::{Matcher, MockConnection};
+/// use diesel::prelude::*;
+/// use diesel::query_builder::AsQuery;
+///
+/// # fn main() {
+/// let conn = MockConnection::new();
+/// conn.expect(
+///     Matcher::query_id::<<users::table as AsQuery>::Query>(),
+///     "SELECT 1, 'Sean'",
+/// );
+///
+/// let results = users::table.load::<(i32, String)>(&conn).unwrap();
+/// assert_eq!(vec![(1, String::from("Sean"))], results);
+/// # }
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct MockConnection {
+    real: SqliteConnection,
+    expectations: RefCell<Vec<Expectation>>,
+    calls: RefCell<Vec<String>>,
+    transaction_manager: AnsiTransactionManager,
+}
+
+impl MockConnection {
+    /// Creates a `MockConnection` with no expectations registered yet.
+    pub fn new() -> Self {
+        MockConnection {
+            real: SqliteConnection::establish(":memory:")
+                .expect("failed to open the in-memory SQLite connection backing MockConnection"),
+            expectations: RefCell::new(Vec::new()),
+            calls: RefCell::new(Vec::new()),
+            transaction_manager: AnsiTransactionManager::new(),
+        }
+    }
+
+    /// Registers `substitute_sql` to run (against the private in-memory SQLite connection) in
+    /// place of any query matching `matcher`.
+    pub fn expect<S: Into<String>>(&self, matcher: Matcher, substitute_sql: S) {
+        self.expectations.borrow_mut().push(Expectation {
+            matcher,
+            substitute_sql: substitute_sql.into(),
+        });
+    }
+
+    /// The rendered SQL of every query run through this connection so far, oldest first.
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.borrow().clone()
+    }
+
+    fn record_call(&self, sql: String) {
+        self.calls.borrow_mut().push(sql);
+    }
+
+    fn find_substitute(&self, query_id: Option<TypeId>, sql: &str) -> String {
+        self.expectations
+            .borrow()
+            .iter()
+            .find(|e| match e.matcher {
+                Matcher::Sql(ref expected) => expected == sql,
+                Matcher::QueryId(expected) => query_id == Some(expected),
+            })
+            .map(|e| e.substitute_sql.clone())
+            .unwrap_or_else(|| {
+                panic!(
+                    "MockConnection received an unexpected query, no `expect` call matches it:\n{}",
+                    sql
+                )
+            })
+    }
+}
+
+impl Default for MockConnection {
+    fn default() -> Self {
+        MockConnection::new()
+    }
+}
+
+impl SimpleConnection for MockConnection {
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        self.record_call(query.to_string());
+        Ok(())
+    }
+}
+
+/// A query fragment that renders as a fixed, pre-rendered SQL string, used internally to run a
+/// [`MockConnection`] expectation's substitute SQL through the real backend's positional
+/// (`Queryable`-based) deserialization.
+struct RawSql<'a, ST> {
+    sql: &'a str,
+    _marker: PhantomData<ST>,
+}
+
+impl<'a, ST> Query for RawSql<'a, ST> {
+    type SqlType = ST;
+}
+
+impl<'a, ST> QueryFragment<Sqlite> for RawSql<'a, ST> {
+    fn walk_ast(&self, mut out: AstPass<Sqlite>) -> QueryResult<()> {
+        out.unsafe_to_cache_prepared();
+        out.push_sql(self.sql);
+        Ok(())
+    }
+}
+
+impl<'a, ST> QueryId for RawSql<'a, ST> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl Connection for MockConnection {
+    type Backend = Sqlite;
+    type TransactionManager = AnsiTransactionManager;
+
+    fn establish(_: &str) -> ConnectionResult<Self> {
+        Err(ConnectionError::BadConnection(String::from(
+            "MockConnection cannot be established from a database URL, use MockConnection::new",
+        )))
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.record_call(query.to_string());
+        Ok(0)
+    }
+
+    fn query_by_index<T, U>(&self, source: T) -> QueryResult<Vec<U>>
+    where
+        T: AsQuery,
+        T::Query: QueryFragment<Self::Backend> + QueryId,
+        Self::Backend: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Self::Backend>,
+    {
+        let query = source.as_query();
+        let sql = debug_query::<Sqlite, _>(&query).to_string();
+        self.record_call(sql.clone());
+        let substitute = self.find_substitute(T::Query::query_id(), &sql);
+        let raw = RawSql::<T::SqlType> {
+            sql: &substitute,
+            _marker: PhantomData,
+        };
+        self.real.query_by_index(raw)
+    }
+
+    fn query_by_name<T, U>(&self, source: &T) -> QueryResult<Vec<U>>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+        U: QueryableByName<Self::Backend>,
+    {
+        let sql = debug_query::<Sqlite, _>(source).to_string();
+        self.record_call(sql.clone());
+        let substitute = self.find_substitute(T::query_id(), &sql);
+        self.real.query_by_name(&sql_query(substitute))
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+    {
+        let sql = debug_query::<Sqlite, _>(source).to_string();
+        self.record_call(sql);
+        Ok(0)
+    }
+
+    fn transaction_manager(&self) -> &Self::TransactionManager {
+        &self.transaction_manager
+    }
+}
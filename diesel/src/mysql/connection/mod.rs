@@ -25,6 +25,27 @@ pub struct MysqlConnection {
 
 unsafe impl Send for MysqlConnection {}
 
+/// Connects, retrying up to `connection_options.connect_retries()` extra times (with a brief
+/// pause between attempts) if the first attempt fails. A fresh `RawConnection` is used for each
+/// attempt, since a MySQL handle that failed to connect can't be reused.
+fn connect_with_retries(connection_options: &ConnectionOptions) -> ConnectionResult<RawConnection> {
+    use std::thread;
+    use std::time::Duration;
+
+    let mut attempt = 0;
+    loop {
+        let raw_connection = RawConnection::new();
+        match raw_connection.connect(connection_options) {
+            Ok(()) => return Ok(raw_connection),
+            Err(_) if attempt < connection_options.connect_retries() => {
+                attempt += 1;
+                thread::sleep(Duration::from_millis(200));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 impl SimpleConnection for MysqlConnection {
     fn batch_execute(&self, query: &str) -> QueryResult<()> {
         self.raw_connection
@@ -39,9 +60,8 @@ impl Connection for MysqlConnection {
     fn establish(database_url: &str) -> ConnectionResult<Self> {
         use result::ConnectionError::CouldntSetupConfiguration;
 
-        let raw_connection = RawConnection::new();
         let connection_options = try!(ConnectionOptions::parse(database_url));
-        try!(raw_connection.connect(&connection_options));
+        let raw_connection = try!(connect_with_retries(&connection_options));
         let conn = MysqlConnection {
             raw_connection: raw_connection,
             transaction_manager: AnsiTransactionManager::new(),
@@ -117,7 +137,7 @@ impl MysqlConnection {
         T: QueryFragment<Mysql> + QueryId,
     {
         let mut stmt = self.statement_cache
-            .cached_statement(source, &[], |sql| self.raw_connection.prepare(sql))?;
+            .cached_statement(source, &[], 0, |sql| self.raw_connection.prepare(sql))?;
         let mut bind_collector = MysqlBindCollector::new();
         try!(source.collect_binds(&mut bind_collector, &()));
         try!(stmt.bind(bind_collector.binds));
@@ -47,6 +47,16 @@ impl RawConnection {
         let database = connection_options.database();
         let port = connection_options.port();
 
+        if let Some(connect_timeout_secs) = connection_options.connect_timeout_secs() {
+            unsafe {
+                ffi::mysql_options(
+                    self.0.as_ptr(),
+                    ffi::mysql_option::MYSQL_OPT_CONNECT_TIMEOUT,
+                    &connect_timeout_secs as *const u32 as *const libc::c_void,
+                );
+            }
+        }
+
         unsafe {
             // Make sure you don't use the fake one!
             ffi::mysql_real_connect(
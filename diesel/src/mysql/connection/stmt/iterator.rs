@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::ffi::CStr;
 
 use super::{ffi, libc, Binds, Statement, StatementMetadata};
 use mysql::{Mysql, MysqlType};
@@ -100,7 +100,7 @@ impl<'a> NamedStatementIterator<'a> {
         match populate_row_buffers(self.stmt, &mut self.output_binds) {
             Ok(Some(())) => Some(Ok(NamedMysqlRow {
                 binds: &self.output_binds,
-                column_indices: self.metadata.column_indices(),
+                metadata: &self.metadata,
             })),
             Ok(None) => None,
             Err(e) => Some(Err(e)),
@@ -110,17 +110,26 @@ impl<'a> NamedStatementIterator<'a> {
 
 pub struct NamedMysqlRow<'a> {
     binds: &'a Binds,
-    column_indices: &'a HashMap<&'a str, usize>,
+    metadata: &'a StatementMetadata,
 }
 
 impl<'a> NamedRow<Mysql> for NamedMysqlRow<'a> {
     fn index_of(&self, column_name: &str) -> Option<usize> {
-        self.column_indices.get(column_name).cloned()
+        self.metadata.column_indices().get(column_name).cloned()
     }
 
     fn get_raw_value(&self, idx: usize) -> Option<&[u8]> {
         self.binds.field_data(idx)
     }
+
+    fn column_count(&self) -> usize {
+        self.metadata.fields().len()
+    }
+
+    fn column_name(&self, index: usize) -> Option<&str> {
+        let field = &self.metadata.fields()[index];
+        unsafe { CStr::from_ptr(field.name) }.to_str().ok()
+    }
 }
 
 fn execute_statement(stmt: &mut Statement, binds: &mut Binds) -> QueryResult<()> {
@@ -12,6 +12,8 @@ pub struct ConnectionOptions {
     password: Option<CString>,
     database: Option<CString>,
     port: Option<u16>,
+    connect_timeout_secs: Option<u32>,
+    connect_retries: u32,
 }
 
 impl ConnectionOptions {
@@ -44,12 +46,28 @@ impl ConnectionOptions {
             Some(segment) => Some(try!(CString::new(segment.as_bytes()))),
         };
 
+        let mut connect_timeout_secs = None;
+        let mut connect_retries = 0;
+        for (key, value) in url.query_pairs() {
+            match &*key {
+                "connect_timeout" => {
+                    connect_timeout_secs = value.parse().ok();
+                }
+                "connect_retries" => {
+                    connect_retries = value.parse().unwrap_or(0);
+                }
+                _ => {}
+            }
+        }
+
         Ok(ConnectionOptions {
             host: host,
             user: user,
             password: password,
             database: database,
             port: url.port(),
+            connect_timeout_secs: connect_timeout_secs,
+            connect_retries: connect_retries,
         })
     }
 
@@ -72,6 +90,18 @@ impl ConnectionOptions {
     pub fn port(&self) -> Option<u16> {
         self.port
     }
+
+    /// The connect timeout requested via the `connect_timeout` URL query parameter (in seconds),
+    /// if any. Passed to `mysql_options(MYSQL_OPT_CONNECT_TIMEOUT)` before connecting.
+    pub fn connect_timeout_secs(&self) -> Option<u32> {
+        self.connect_timeout_secs
+    }
+
+    /// The number of extra connection attempts requested via the `connect_retries` URL query
+    /// parameter. Defaults to `0`, meaning a single attempt.
+    pub fn connect_retries(&self) -> u32 {
+        self.connect_retries
+    }
 }
 
 fn decode_into_cstring(s: &str) -> ConnectionResult<CString> {
@@ -151,6 +181,22 @@ fn userinfo_should_be_percent_decode() {
     assert_eq!(password, conn_opts.password.unwrap());
 }
 
+#[test]
+fn connect_timeout_and_retries_default_to_none_and_zero() {
+    let conn_opts = ConnectionOptions::parse("mysql://localhost/foo").unwrap();
+    assert_eq!(None, conn_opts.connect_timeout_secs());
+    assert_eq!(0, conn_opts.connect_retries());
+}
+
+#[test]
+fn connect_timeout_and_retries_are_parsed_from_query_params() {
+    let conn_opts =
+        ConnectionOptions::parse("mysql://localhost/foo?connect_timeout=5&connect_retries=3")
+            .unwrap();
+    assert_eq!(Some(5), conn_opts.connect_timeout_secs());
+    assert_eq!(3, conn_opts.connect_retries());
+}
+
 #[test]
 fn ipv6_host_not_wrapped_in_brackets() {
     let host1 = CString::new("::1").unwrap();
@@ -0,0 +1,21 @@
+use expression::cast::SqlTypeName;
+use mysql::Mysql;
+use sql_types::*;
+
+macro_rules! impl_mysql_sql_type_name {
+    ($ty:ty, $name:expr) => {
+        impl SqlTypeName<Mysql> for $ty {
+            const SQL_TYPE_NAME: &'static str = $name;
+        }
+    };
+}
+
+impl_mysql_sql_type_name!(Bool, "SIGNED");
+impl_mysql_sql_type_name!(SmallInt, "SIGNED");
+impl_mysql_sql_type_name!(Integer, "SIGNED");
+impl_mysql_sql_type_name!(BigInt, "SIGNED");
+impl_mysql_sql_type_name!(Text, "CHAR");
+impl_mysql_sql_type_name!(Binary, "BINARY");
+impl_mysql_sql_type_name!(Date, "DATE");
+impl_mysql_sql_type_name!(Time, "TIME");
+impl_mysql_sql_type_name!(Timestamp, "DATETIME");
@@ -0,0 +1,3 @@
+//! MySQL specific expression DSL methods.
+
+mod cast;
@@ -8,6 +8,7 @@ mod backend;
 mod bind_collector;
 mod connection;
 
+mod expression;
 mod query_builder;
 pub mod types;
 
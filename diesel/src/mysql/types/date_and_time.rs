@@ -143,6 +143,148 @@ impl FromSql<Date, Mysql> for NaiveDate {
     }
 }
 
+#[cfg(feature = "time_03")]
+mod time_03 {
+    extern crate mysqlclient_sys as ffi;
+    extern crate time_03;
+
+    use self::time_03::{Date, OffsetDateTime, PrimitiveDateTime, Time as ClockTime};
+    use std::io::Write;
+    use std::os::raw as libc;
+    use std::mem;
+
+    use deserialize::{self, FromSql};
+    use mysql::Mysql;
+    use serialize::{self, Output, ToSql};
+    use sql_types::{Date as DateTy, Datetime, Time, Timestamp};
+
+    impl ToSql<Datetime, Mysql> for PrimitiveDateTime {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Mysql>) -> serialize::Result {
+            <PrimitiveDateTime as ToSql<Timestamp, Mysql>>::to_sql(self, out)
+        }
+    }
+
+    impl FromSql<Datetime, Mysql> for PrimitiveDateTime {
+        fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+            <PrimitiveDateTime as FromSql<Timestamp, Mysql>>::from_sql(bytes)
+        }
+    }
+
+    impl ToSql<Timestamp, Mysql> for PrimitiveDateTime {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Mysql>) -> serialize::Result {
+            let mut mysql_time: ffi::MYSQL_TIME = unsafe { mem::zeroed() };
+
+            mysql_time.year = self.year() as libc::c_uint;
+            mysql_time.month = libc::c_uint::from(u8::from(self.month()));
+            mysql_time.day = libc::c_uint::from(self.day());
+            mysql_time.hour = libc::c_uint::from(self.hour());
+            mysql_time.minute = libc::c_uint::from(self.minute());
+            mysql_time.second = libc::c_uint::from(self.second());
+            mysql_time.second_part = libc::c_ulong::from(self.microsecond());
+
+            <ffi::MYSQL_TIME as ToSql<Timestamp, Mysql>>::to_sql(&mysql_time, out)
+        }
+    }
+
+    impl FromSql<Timestamp, Mysql> for PrimitiveDateTime {
+        fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+            let mysql_time = <ffi::MYSQL_TIME as FromSql<Timestamp, Mysql>>::from_sql(bytes)?;
+
+            let date = Date::from_calendar_date(
+                mysql_time.year as i32,
+                u8_to_month(mysql_time.month as u8)?,
+                mysql_time.day as u8,
+            )?;
+            let time = ClockTime::from_hms_micro(
+                mysql_time.hour as u8,
+                mysql_time.minute as u8,
+                mysql_time.second as u8,
+                mysql_time.second_part as u32,
+            )?;
+            Ok(PrimitiveDateTime::new(date, time))
+        }
+    }
+
+    impl ToSql<Timestamp, Mysql> for OffsetDateTime {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Mysql>) -> serialize::Result {
+            let naive = PrimitiveDateTime::new(self.date(), self.time());
+            <PrimitiveDateTime as ToSql<Timestamp, Mysql>>::to_sql(&naive, out)
+        }
+    }
+
+    impl FromSql<Timestamp, Mysql> for OffsetDateTime {
+        fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+            let naive = <PrimitiveDateTime as FromSql<Timestamp, Mysql>>::from_sql(bytes)?;
+            Ok(naive.assume_utc())
+        }
+    }
+
+    impl ToSql<Time, Mysql> for ClockTime {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Mysql>) -> serialize::Result {
+            let mut mysql_time: ffi::MYSQL_TIME = unsafe { mem::zeroed() };
+
+            mysql_time.hour = libc::c_uint::from(self.hour());
+            mysql_time.minute = libc::c_uint::from(self.minute());
+            mysql_time.second = libc::c_uint::from(self.second());
+
+            <ffi::MYSQL_TIME as ToSql<Time, Mysql>>::to_sql(&mysql_time, out)
+        }
+    }
+
+    impl FromSql<Time, Mysql> for ClockTime {
+        fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+            let mysql_time = <ffi::MYSQL_TIME as FromSql<Time, Mysql>>::from_sql(bytes)?;
+            Ok(ClockTime::from_hms(
+                mysql_time.hour as u8,
+                mysql_time.minute as u8,
+                mysql_time.second as u8,
+            )?)
+        }
+    }
+
+    impl ToSql<DateTy, Mysql> for Date {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Mysql>) -> serialize::Result {
+            let mut mysql_time: ffi::MYSQL_TIME = unsafe { mem::zeroed() };
+
+            mysql_time.year = self.year() as libc::c_uint;
+            mysql_time.month = libc::c_uint::from(u8::from(self.month()));
+            mysql_time.day = libc::c_uint::from(self.day());
+
+            <ffi::MYSQL_TIME as ToSql<DateTy, Mysql>>::to_sql(&mysql_time, out)
+        }
+    }
+
+    impl FromSql<DateTy, Mysql> for Date {
+        fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+            let mysql_time = <ffi::MYSQL_TIME as FromSql<DateTy, Mysql>>::from_sql(bytes)?;
+            Ok(Date::from_calendar_date(
+                mysql_time.year as i32,
+                u8_to_month(mysql_time.month as u8)?,
+                mysql_time.day as u8,
+            )?)
+        }
+    }
+
+    fn u8_to_month(month: u8) -> deserialize::Result<self::time_03::Month> {
+        use self::time_03::Month::*;
+        Ok(match month {
+            1 => January,
+            2 => February,
+            3 => March,
+            4 => April,
+            5 => May,
+            6 => June,
+            7 => July,
+            8 => August,
+            9 => September,
+            10 => October,
+            11 => November,
+            12 => December,
+            _ => return Err(format!("Invalid month: {}", month).into()),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate chrono;
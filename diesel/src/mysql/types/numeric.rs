@@ -28,3 +28,36 @@ pub mod bigdecimal {
         }
     }
 }
+
+#[cfg(feature = "decimal")]
+pub mod decimal {
+    extern crate rust_decimal;
+
+    use self::rust_decimal::Decimal;
+    use std::io::prelude::*;
+    use std::str::FromStr;
+
+    use backend::Backend;
+    use deserialize::{self, FromSql};
+    use mysql::Mysql;
+    use serialize::{self, IsNull, Output, ToSql};
+    use sql_types::{Binary, Numeric};
+
+    impl ToSql<Numeric, Mysql> for Decimal {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Mysql>) -> serialize::Result {
+            write!(out, "{}", *self)
+                .map(|_| IsNull::No)
+                .map_err(|e| e.into())
+        }
+    }
+
+    impl FromSql<Numeric, Mysql> for Decimal {
+        fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+            let bytes_ptr = <*const [u8] as FromSql<Binary, Mysql>>::from_sql(bytes)?;
+            let bytes = unsafe { &*bytes_ptr };
+            let text = ::std::str::from_utf8(bytes)?;
+            Decimal::from_str(text)
+                .map_err(|_| Box::from(format!("{:?} is not valid decimal number ", bytes)))
+        }
+    }
+}
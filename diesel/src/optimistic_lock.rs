@@ -0,0 +1,151 @@
+//! Support for the "optimistic locking via a version column" pattern, where an `UPDATE` is
+//! guarded by a `WHERE version = <the version this record was loaded with>` clause, and the
+//! version column is bumped as part of the same statement.
+//!
+//! Diesel's derives don't know which column (if any) a table uses for this, and threading an
+//! extra `WHERE` predicate and a `SET version = version + 1` through every `update(...)` or
+//! [`save_changes`](../query_dsl/trait.SaveChangesDsl.html) call automatically would mean
+//! guessing at that. Instead, a record opts in by implementing [`OptimisticLockable`], and calls
+//! [`update_with_version_check`] explicitly wherever the check matters.
+
+use associations::HasTable;
+use dsl::{Eq, Filter};
+use expression::AppearsOnTable;
+use expression_methods::*;
+use query_builder::{AsChangeset, IntoUpdateTarget, UpdateStatement};
+use query_dsl::methods::{ExecuteDsl, FilterDsl};
+use query_source::Column;
+use result::{Error, QueryResult};
+use sql_types::Integer;
+
+/// Opt-in marker for records using the "optimistic locking via a version column" pattern.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # use diesel::optimistic_lock::OptimisticLockable;
+/// #
+/// table! {
+///     posts {
+///         id -> Integer,
+///         title -> Text,
+///         version -> Integer,
+///     }
+/// }
+///
+/// #[derive(AsChangeset, Identifiable, Copy, Clone)]
+/// #[table_name = "posts"]
+/// struct Post<'a> {
+///     id: i32,
+///     title: &'a str,
+///     version: i32,
+/// }
+///
+/// impl<'a> OptimisticLockable for Post<'a> {
+///     type VersionColumn = posts::version;
+///     const TABLE_NAME: &'static str = "posts";
+///
+///     fn version(&self) -> i32 {
+///         self.version
+///     }
+/// }
+/// #
+/// # fn main() {}
+/// ```
+pub trait OptimisticLockable: IntoUpdateTarget + HasTable + Copy {
+    /// The column tracking how many times this row has been updated.
+    ///
+    /// Bounded by `Copy` and `AppearsOnTable<Self::Table>` (in addition to the usual `Column`
+    /// bound the [`table!`](../macro.table.html) macro already gives every generated column) so
+    /// [`update_with_version_check`] can read it twice -- once for the `SET`, once for the
+    /// `WHERE` -- and build both sides of the update against `Self::Table`.
+    type VersionColumn: Column<Table = <Self as HasTable>::Table, SqlType = Integer>
+        + Default
+        + Copy
+        + AppearsOnTable<<Self as HasTable>::Table>;
+
+    /// The name of the table this record belongs to, used to build
+    /// [`Error::StaleObject`](../result/enum.Error.html#variant.StaleObject) if the check fails.
+    const TABLE_NAME: &'static str;
+
+    /// The value of `VersionColumn` this record was loaded with.
+    fn version(&self) -> i32;
+}
+
+/// Builds the `UPDATE ... SET version = version + 1 WHERE ... AND version = ?` statement for a
+/// record.
+///
+/// This is a separate trait (with a blanket impl for every eligible `T`) rather than a single
+/// generic function bounded directly on `FilterDsl` because the `SET` tuple `(T, Eq<T::VersionColumn,
+/// i32>)`'s `AsChangeset::Changeset` is itself a tuple type, and the trait solver only accepts a
+/// `where` clause naming that association as an axiom within the same declaration that states it
+/// -- at any other call site (including a `let`-binding's type ascription, or a second function
+/// bounded the same way) it insists on re-deriving the tuple's `Changeset` from the concrete
+/// `AsChangeset` impl instead, which doesn't unify with a type variable asserted equal to it from
+/// outside. `C` exists so that axiom has somewhere to live: it's a free parameter of this trait
+/// (not of the blanket impl alone, which an unconstrained type parameter isn't allowed to be),
+/// so every caller that names `UpdateWithVersionCheck<C>` for a concrete `C` gets the exact same
+/// already-resolved `Changeset`, instead of asking the solver to re-derive it. [`SaveChangesDsl`]
+/// uses the same per-`Self` associated-type trick for the analogous problem.
+///
+/// [`SaveChangesDsl`]: ../query_dsl/trait.SaveChangesDsl.html
+pub trait UpdateWithVersionCheck<C>: OptimisticLockable + Sized {
+    /// The type of the statement built by [`update_with_version_check`].
+    type Output;
+
+    #[doc(hidden)]
+    fn update_with_version_check(self) -> Self::Output;
+}
+
+impl<T, C> UpdateWithVersionCheck<C> for T
+where
+    T: OptimisticLockable + AsChangeset<Target = <T as HasTable>::Table>,
+    (T, Eq<T::VersionColumn, i32>): AsChangeset<Target = <T as HasTable>::Table, Changeset = C>,
+    UpdateStatement<<T as HasTable>::Table, <T as IntoUpdateTarget>::WhereClause, C>:
+        FilterDsl<Eq<T::VersionColumn, i32>>,
+{
+    type Output = Filter<
+        UpdateStatement<<T as HasTable>::Table, <T as IntoUpdateTarget>::WhereClause, C>,
+        Eq<T::VersionColumn, i32>,
+    >;
+
+    fn update_with_version_check(self) -> Self::Output {
+        let version_column = T::VersionColumn::default();
+        let loaded_version = self.version();
+        ::update(self)
+            .set((self, version_column.eq(loaded_version + 1)))
+            .filter(version_column.eq(loaded_version))
+    }
+}
+
+/// Builds the `UPDATE ... SET version = version + 1 WHERE ... AND version = ?` statement for
+/// `record`, without running it.
+///
+/// The `SET` clause always includes `record` itself, so any other changed fields are saved at
+/// the same time as the version bump.
+pub fn update_with_version_check<T, C>(record: T) -> T::Output
+where
+    T: UpdateWithVersionCheck<C>,
+{
+    record.update_with_version_check()
+}
+
+/// Runs the same `UPDATE` [`update_with_version_check`] builds for `record`, returning
+/// [`Error::StaleObject`](../result/enum.Error.html#variant.StaleObject) instead of `Ok(0)` if
+/// no row matched (because `record`'s version is no longer current).
+pub fn save_with_version_check<T, C, Conn>(record: T, connection: &Conn) -> QueryResult<usize>
+where
+    Conn: ::connection::Connection,
+    T: UpdateWithVersionCheck<C>,
+    T::Output: ExecuteDsl<Conn>,
+{
+    let affected_rows = ExecuteDsl::execute(update_with_version_check(record), connection)?;
+    if affected_rows == 0 {
+        Err(Error::StaleObject {
+            table_name: T::TABLE_NAME,
+        })
+    } else {
+        Ok(affected_rows)
+    }
+}
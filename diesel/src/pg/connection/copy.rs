@@ -0,0 +1,165 @@
+extern crate byteorder;
+extern crate pq_sys;
+
+use self::byteorder::{NetworkEndian, WriteBytesExt};
+use std::ffi::CString;
+use std::io;
+
+use super::raw::RawConnection;
+use super::result::PgResult;
+use pg::{Pg, PgMetadataLookup};
+use result::Error::SerializationError;
+use result::*;
+use serialize::{IsNull, Output, ToSql};
+
+/// The signature every binary-format COPY stream starts with.
+///
+/// See <https://www.postgresql.org/docs/9.6/static/sql-copy.html#AEN77618>.
+const COPY_BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// Flush the internal buffer once it grows past this size, so a `COPY` of
+/// millions of rows doesn't build up one giant `Vec` before anything is sent
+/// to the server.
+const FLUSH_THRESHOLD: usize = 64 * 1024;
+
+/// A handle to an in-progress `COPY ... FROM STDIN (FORMAT BINARY)`.
+///
+/// Obtained through [`PgConnection::copy_in`](../struct.PgConnection.html#method.copy_in).
+/// Rows are appended with [`write_row`](#method.write_row), and the copy is
+/// committed by calling [`finish`](#method.finish); dropping a `CopyIn`
+/// without calling `finish` aborts the copy.
+#[allow(missing_debug_implementations)]
+pub struct CopyIn<'conn> {
+    raw_connection: &'conn RawConnection,
+    metadata_lookup: &'conn PgMetadataLookup,
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+impl<'conn> CopyIn<'conn> {
+    pub(crate) fn new(
+        raw_connection: &'conn RawConnection,
+        metadata_lookup: &'conn PgMetadataLookup,
+        copy_target: &str,
+    ) -> QueryResult<Self> {
+        let statement = format!("COPY {} FROM STDIN (FORMAT BINARY)", copy_target);
+        let statement = CString::new(statement)?;
+        let raw_result = unsafe { raw_connection.exec(statement.as_ptr())? };
+
+        use self::pq_sys::ExecStatusType::*;
+
+        let status = unsafe { pq_sys::PQresultStatus(raw_result.as_ptr()) };
+        if status != PGRES_COPY_IN {
+            let message = raw_result.error_message().to_string();
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(message),
+            ));
+        }
+
+        let mut buffer = Vec::with_capacity(FLUSH_THRESHOLD);
+        buffer.extend_from_slice(COPY_BINARY_SIGNATURE);
+        buffer.extend_from_slice(&[0; 4]); // no flags set
+        buffer.extend_from_slice(&[0; 4]); // no header extension
+
+        Ok(CopyIn {
+            raw_connection,
+            metadata_lookup,
+            buffer,
+            finished: false,
+        })
+    }
+
+    /// Appends a single row, writing `num_columns` fields through the given
+    /// closure by calling [`CopyRowWriter::write_field`] once per column, in
+    /// order.
+    pub fn write_row<F>(&mut self, num_columns: i16, f: F) -> QueryResult<()>
+    where
+        F: FnOnce(&mut CopyRowWriter) -> QueryResult<()>,
+    {
+        self.buffer
+            .write_i16::<NetworkEndian>(num_columns)
+            .map_err(io_error)?;
+        let mut writer = CopyRowWriter {
+            buffer: &mut self.buffer,
+            metadata_lookup: self.metadata_lookup,
+        };
+        f(&mut writer)?;
+
+        if self.buffer.len() >= FLUSH_THRESHOLD {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> QueryResult<()> {
+        if !self.buffer.is_empty() {
+            self.raw_connection.put_copy_data(&self.buffer)?;
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+
+    /// Ends the `COPY`, returning the number of rows the server reports
+    /// having received.
+    pub fn finish(mut self) -> QueryResult<usize> {
+        self.buffer
+            .write_i16::<NetworkEndian>(-1)
+            .map_err(io_error)?;
+        self.flush()?;
+        self.finished = true;
+
+        self.raw_connection.put_copy_end()?;
+        let raw_result = unsafe { self.raw_connection.get_copy_result()? };
+        PgResult::new(raw_result).map(|r| r.rows_affected())
+    }
+}
+
+impl<'conn> Drop for CopyIn<'conn> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.raw_connection.cancel_copy_in();
+            let _ = unsafe { self.raw_connection.get_copy_result() };
+        }
+    }
+}
+
+/// Writes the fields of a single row during [`CopyIn::write_row`].
+#[allow(missing_debug_implementations)]
+pub struct CopyRowWriter<'a> {
+    buffer: &'a mut Vec<u8>,
+    metadata_lookup: &'a PgMetadataLookup,
+}
+
+impl<'a> CopyRowWriter<'a> {
+    /// Serializes `value` as the next field of the row, using the same
+    /// `ToSql` impl that would be used for a regular bind parameter of SQL
+    /// type `ST`.
+    pub fn write_field<ST, T>(&mut self, value: &T) -> QueryResult<()>
+    where
+        T: ToSql<ST, Pg>,
+    {
+        let mut output = Output::new(Vec::new(), self.metadata_lookup);
+        let is_null = value.to_sql(&mut output).map_err(SerializationError)?;
+        match is_null {
+            IsNull::Yes => self.buffer
+                .write_i32::<NetworkEndian>(-1)
+                .map_err(io_error)?,
+            IsNull::No => {
+                let bytes = output.into_inner();
+                self.buffer
+                    .write_i32::<NetworkEndian>(bytes.len() as i32)
+                    .map_err(io_error)?;
+                self.buffer.extend_from_slice(&bytes);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writing into an in-memory `Vec<u8>` can never actually fail, but
+/// `byteorder`'s `WriteBytesExt` methods return `io::Result` regardless --
+/// map that down to our own `Error` type so it composes with `?` here.
+fn io_error(e: io::Error) -> Error {
+    SerializationError(Box::new(e))
+}
@@ -76,4 +76,12 @@ impl NamedCursor {
     pub fn get_value(&self, row: usize, column: usize) -> Option<&[u8]> {
         self.db_result.get(row, column)
     }
+
+    pub fn num_columns(&self) -> usize {
+        self.db_result.num_fields()
+    }
+
+    pub fn column_name(&self, column: usize) -> Option<&str> {
+        self.db_result.field_name(column)
+    }
 }
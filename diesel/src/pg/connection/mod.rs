@@ -1,3 +1,4 @@
+mod copy;
 mod cursor;
 pub mod raw;
 #[doc(hidden)]
@@ -5,9 +6,13 @@ pub mod result;
 mod row;
 mod stmt;
 
+use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
 use std::ffi::CString;
+use std::hash::{Hash, Hasher};
 use std::os::raw as libc;
 
+pub use self::copy::{CopyIn, CopyRowWriter};
 use self::cursor::*;
 use self::raw::RawConnection;
 use self::result::PgResult;
@@ -24,11 +29,16 @@ use sql_types::HasSqlType;
 /// The connection string expected by `PgConnection::establish`
 /// should be a PostgreSQL connection string, as documented at
 /// <https://www.postgresql.org/docs/9.4/static/libpq-connect.html#LIBPQ-CONNSTRING>
+///
+/// That connection string format already has its own `connect_timeout` parameter (in seconds),
+/// which is passed straight through to libpq since diesel doesn't otherwise parse this URL, e.g.
+/// `postgres://localhost/my_db?connect_timeout=5`.
 #[allow(missing_debug_implementations)]
 pub struct PgConnection {
     raw_connection: RawConnection,
     transaction_manager: AnsiTransactionManager,
     statement_cache: StatementCache<Pg, Statement>,
+    schema_salt: Cell<u64>,
 }
 
 unsafe impl Send for PgConnection {}
@@ -52,6 +62,7 @@ impl Connection for PgConnection {
                 raw_connection: raw_conn,
                 transaction_manager: AnsiTransactionManager::new(),
                 statement_cache: StatementCache::new(),
+                schema_salt: Cell::new(0),
             };
             conn.set_config_options()
                 .map_err(CouldntSetupConfiguration)?;
@@ -136,6 +147,138 @@ impl PgConnection {
         TransactionBuilder::new(self)
     }
 
+    /// Runs `f` inside a `SERIALIZABLE` transaction (see [`build_transaction`]), automatically
+    /// starting a brand new transaction and retrying it up to `max_retries` extra times if it
+    /// fails with a serialization failure or deadlock (SQLSTATE `40001` or `40P01`) -- the two
+    /// errors a correct `SERIALIZABLE` client is expected to retry, per the
+    /// [Postgres documentation on serializable isolation][pg-docs]. Any other error is returned
+    /// immediately without retrying.
+    ///
+    /// Since `f` may be called more than once, it must be a `Fn` rather than an `FnOnce`, and
+    /// should avoid side effects that aren't safe to repeat (e.g. sending an email) other than
+    /// through the database itself.
+    ///
+    /// [`build_transaction`]: #method.build_transaction
+    /// [pg-docs]: https://www.postgresql.org/docs/current/static/transaction-iso.html#XACT-SERIALIZABLE
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     let conn = connection_no_transaction();
+    /// let result = conn.serializable_transaction_with_retries(3, || Ok(()));
+    /// assert_eq!(Ok(()), result);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn serializable_transaction_with_retries<T, F>(
+        &self,
+        max_retries: u32,
+        f: F,
+    ) -> QueryResult<T>
+    where
+        F: Fn() -> QueryResult<T>,
+    {
+        let mut attempt = 0;
+        loop {
+            match self.build_transaction().serializable().run(&f) {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    if attempt >= max_retries || !e.is_retriable() {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Sets this connection's `search_path`, so that unqualified table names in `table!`
+    /// definitions resolve against `schemas` instead of the database's default.
+    ///
+    /// This is the standard way to do schema-per-tenant multi-tenancy in Postgres: the same
+    /// `table!` definitions are reused for every tenant, and which physical schema they read
+    /// and write is decided per-connection at runtime.
+    ///
+    /// Diesel caches most prepared statements by Rust type rather than by SQL text, which would
+    /// otherwise let a statement prepared for one tenant's schema get reused, unprepared, against
+    /// a different tenant. Calling this method changes the cache key salt for all statements
+    /// prepared afterwards, so a change of `search_path` can never hand back another tenant's
+    /// prepared statement; it does mean each distinct `search_path` used on a connection gets its
+    /// own cache entries, so pick a value with a bounded number of variants (e.g. once per tenant
+    /// per pooled connection) rather than something with unbounded cardinality.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     let conn = connection_no_transaction();
+    /// conn.set_search_path(&["tenant_1", "public"])?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_search_path(&self, schemas: &[&str]) -> QueryResult<()> {
+        let quoted_schemas = schemas
+            .iter()
+            .map(|schema| format!("\"{}\"", schema.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.execute(&format!("SET search_path TO {}", quoted_schemas))?;
+
+        let mut hasher = DefaultHasher::new();
+        schemas.hash(&mut hasher);
+        self.schema_salt.set(hasher.finish());
+
+        Ok(())
+    }
+
+    /// Starts a `COPY ... FROM STDIN (FORMAT BINARY)` for `copy_target`
+    /// (typically `"table_name"` or `"table_name (col1, col2)"`), returning
+    /// a handle that rows can be streamed into.
+    ///
+    /// This is significantly faster than issuing individual `INSERT`
+    /// statements, and -- being a binary format -- sidesteps the
+    /// text-escaping pitfalls of `bytea` and timestamp columns that the
+    /// text `COPY` format has.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use diesel::sql_types::{Integer, Text};
+    /// #     use schema::users::dsl::*;
+    /// #     let conn = connection_no_transaction();
+    /// #     conn.execute("DELETE FROM users").unwrap();
+    /// let mut copy = conn.copy_in("users (id, name)")?;
+    /// copy.write_row(2, |row| {
+    ///     row.write_field::<Integer, _>(&3)?;
+    ///     row.write_field::<Text, _>(&"Ruby")?;
+    ///     Ok(())
+    /// })?;
+    /// let rows_copied = copy.finish()?;
+    /// assert_eq!(1, rows_copied);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn copy_in(&self, copy_target: &str) -> QueryResult<CopyIn> {
+        CopyIn::new(&self.raw_connection, PgMetadataLookup::new(self), copy_target)
+    }
+
     #[cfg_attr(feature = "cargo-clippy", allow(type_complexity))]
     fn prepare_query<T: QueryFragment<Pg> + QueryId>(
         &self,
@@ -148,7 +291,7 @@ impl PgConnection {
 
         let cache_len = self.statement_cache.len();
         let query = self.statement_cache
-            .cached_statement(source, &metadata, |sql| {
+            .cached_statement(source, &metadata, self.schema_salt.get(), |sql| {
                 let query_name = if source.is_safe_to_cache_prepared()? {
                     Some(format!("__diesel_stmt_{}", cache_len))
                 } else {
@@ -253,6 +396,41 @@ mod tests {
         assert_eq!(0, connection.statement_cache.len());
     }
 
+    #[test]
+    fn serializable_transaction_with_retries_succeeds_without_retrying_on_the_happy_path() {
+        use std::cell::Cell;
+
+        let connection = connection();
+        let attempts = Cell::new(0);
+
+        let result = connection.serializable_transaction_with_retries(3, || {
+            attempts.set(attempts.get() + 1);
+            Ok(1)
+        });
+
+        assert_eq!(Ok(1), result);
+        assert_eq!(1, attempts.get());
+    }
+
+    #[test]
+    fn serializable_transaction_with_retries_does_not_retry_a_non_retriable_error() {
+        use std::cell::Cell;
+
+        let connection = connection();
+        let attempts = Cell::new(0);
+
+        let result: QueryResult<()> = connection.serializable_transaction_with_retries(3, || {
+            attempts.set(attempts.get() + 1);
+            Err(Error::DatabaseError(
+                DatabaseErrorKind::UnableToSendCommand,
+                Box::new(String::from("not a serialization failure")),
+            ))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(1, attempts.get());
+    }
+
     fn connection() -> PgConnection {
         dotenv().ok();
         let database_url = env::var("PG_DATABASE_URL")
@@ -76,6 +76,55 @@ impl RawConnection {
         RawResult::new(ptr, self)
     }
 
+    pub fn put_copy_data(&self, data: &[u8]) -> QueryResult<()> {
+        let result = unsafe {
+            PQputCopyData(
+                self.internal_connection.as_ptr(),
+                data.as_ptr() as *const libc::c_char,
+                data.len() as libc::c_int,
+            )
+        };
+        if result > 0 {
+            Ok(())
+        } else {
+            Err(Error::DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(self.last_error_message()),
+            ))
+        }
+    }
+
+    pub fn put_copy_end(&self) -> QueryResult<()> {
+        let result =
+            unsafe { PQputCopyEnd(self.internal_connection.as_ptr(), ptr::null()) };
+        if result > 0 {
+            Ok(())
+        } else {
+            Err(Error::DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(self.last_error_message()),
+            ))
+        }
+    }
+
+    pub fn cancel_copy_in(&self) -> QueryResult<()> {
+        let message = CString::new("aborted by client").expect("no nul bytes in literal");
+        let result =
+            unsafe { PQputCopyEnd(self.internal_connection.as_ptr(), message.as_ptr()) };
+        if result > 0 {
+            Ok(())
+        } else {
+            Err(Error::DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(self.last_error_message()),
+            ))
+        }
+    }
+
+    pub unsafe fn get_copy_result(&self) -> QueryResult<RawResult> {
+        RawResult::new(PQgetResult(self.internal_connection.as_ptr()), self)
+    }
+
     pub unsafe fn prepare(
         &self,
         stmt_name: *const libc::c_char,
@@ -1,6 +1,7 @@
 extern crate pq_sys;
 
 use self::pq_sys::*;
+use std::any::Any;
 use std::ffi::{CStr, CString};
 use std::os::raw as libc;
 use std::{slice, str};
@@ -36,6 +37,10 @@ impl PgResult {
                         Some(error_codes::FOREIGN_KEY_VIOLATION) => {
                             DatabaseErrorKind::ForeignKeyViolation
                         }
+                        Some(error_codes::SERIALIZATION_FAILURE) => {
+                            DatabaseErrorKind::SerializationFailure
+                        }
+                        Some(error_codes::DEADLOCK_DETECTED) => DatabaseErrorKind::DeadlockDetected,
                         _ => DatabaseErrorKind::__Unknown,
                     };
                 let error_information = Box::new(PgErrorInformation(internal_result));
@@ -91,6 +96,21 @@ impl PgResult {
         }
     }
 
+    pub fn num_fields(&self) -> usize {
+        unsafe { PQnfields(self.internal_result.as_ptr()) as usize }
+    }
+
+    pub fn field_name(&self, col_idx: usize) -> Option<&str> {
+        unsafe {
+            let ptr = PQfname(self.internal_result.as_ptr(), col_idx as libc::c_int);
+            if ptr.is_null() {
+                None
+            } else {
+                CStr::from_ptr(ptr).to_str().ok()
+            }
+        }
+    }
+
     pub fn field_number(&self, column_name: &str) -> Option<usize> {
         let cstr = CString::new(column_name).unwrap_or_default();
         let fnum = unsafe { PQfnumber(self.internal_result.as_ptr(), cstr.as_ptr()) };
@@ -128,6 +148,10 @@ impl DatabaseErrorInformation for PgErrorInformation {
     fn constraint_name(&self) -> Option<&str> {
         get_result_field(self.0.as_ptr(), ResultField::ConstraintName)
     }
+
+    fn as_any(&self) -> &Any {
+        self
+    }
 }
 
 /// Represents valid options to
@@ -162,4 +186,6 @@ mod error_codes {
     //! They are not exposed programmatically through libpq.
     pub const UNIQUE_VIOLATION: &str = "23505";
     pub const FOREIGN_KEY_VIOLATION: &str = "23503";
+    pub const SERIALIZATION_FAILURE: &str = "40001";
+    pub const DEADLOCK_DETECTED: &str = "40P01";
 }
@@ -50,4 +50,12 @@ impl<'a> NamedRow<Pg> for PgNamedRow<'a> {
     fn index_of(&self, column_name: &str) -> Option<usize> {
         self.cursor.index_of_column(column_name)
     }
+
+    fn column_count(&self) -> usize {
+        self.cursor.num_columns()
+    }
+
+    fn column_name(&self, index: usize) -> Option<&str> {
+        self.cursor.column_name(index)
+    }
 }
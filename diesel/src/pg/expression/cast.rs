@@ -0,0 +1,25 @@
+use expression::cast::SqlTypeName;
+use pg::types::sql_types::Timestamptz;
+use pg::Pg;
+use sql_types::*;
+
+macro_rules! impl_pg_sql_type_name {
+    ($ty:ty, $name:expr) => {
+        impl SqlTypeName<Pg> for $ty {
+            const SQL_TYPE_NAME: &'static str = $name;
+        }
+    };
+}
+
+impl_pg_sql_type_name!(Bool, "BOOL");
+impl_pg_sql_type_name!(SmallInt, "SMALLINT");
+impl_pg_sql_type_name!(Integer, "INTEGER");
+impl_pg_sql_type_name!(BigInt, "BIGINT");
+impl_pg_sql_type_name!(Float, "REAL");
+impl_pg_sql_type_name!(Double, "DOUBLE PRECISION");
+impl_pg_sql_type_name!(Text, "TEXT");
+impl_pg_sql_type_name!(Binary, "BYTEA");
+impl_pg_sql_type_name!(Date, "DATE");
+impl_pg_sql_type_name!(Time, "TIME");
+impl_pg_sql_type_name!(Timestamp, "TIMESTAMP");
+impl_pg_sql_type_name!(Timestamptz, "TIMESTAMPTZ");
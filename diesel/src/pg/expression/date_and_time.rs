@@ -2,7 +2,7 @@ use expression::{Expression, NonAggregate};
 use pg::Pg;
 use query_builder::*;
 use result::QueryResult;
-use sql_types::{Date, Timestamp, Timestamptz, VarChar};
+use sql_types::{Date, Text, Timestamp, Timestamptz, VarChar};
 
 /// Marker trait for types which are valid in `AT TIME ZONE` expressions
 pub trait DateTimeLike {}
@@ -54,3 +54,26 @@ where
 }
 
 impl_selectable_expression!(AtTimeZone<Ts, Tz>);
+
+sql_function! {
+    /// Represents the PostgreSQL `date_trunc` function, truncating
+    /// `timestamp` to the precision named by `field` (e.g. `"hour"`,
+    /// `"day"`, `"month"`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # extern crate chrono;
+    /// # include!("../../doctest_setup.rs");
+    /// # use diesel::dsl::*;
+    /// #
+    /// # fn main() {
+    /// #     let connection = establish_connection();
+    /// let result: chrono::NaiveDateTime =
+    ///     diesel::select(date_trunc("month", now)).first(&connection).unwrap();
+    /// # let _ = result;
+    /// # }
+    /// ```
+    fn date_trunc(field: VarChar, timestamp: Timestamp) -> Timestamp;
+}
@@ -1,11 +1,59 @@
 //! PostgreSQL specific expression methods
 
+use super::array_comparison::{all, any, All, Any, AsArrayExpression};
 use super::operators::*;
+use expression::operators::{Eq, NotEq};
 use expression::{AsExpression, Expression};
-use sql_types::{Array, Text};
+use sql_types::{Array, Inet, Text};
 
 /// PostgreSQL specific methods which are present on all expressions.
 pub trait PgExpressionMethods: Expression + Sized {
+    /// Creates a PostgreSQL `= ANY(...)` expression.
+    ///
+    /// Unlike [`eq_any`], which expands to an `IN (a, b, c, ...)` list with
+    /// one bind parameter per element, this binds the whole collection as a
+    /// single PostgreSQL array parameter. The generated SQL is identical
+    /// regardless of how many elements are passed in, so the prepared
+    /// statement can be reused from the statement cache.
+    ///
+    /// [`eq_any`]: ../expression_methods/trait.ExpressionMethods.html#method.eq_any
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     use schema::users::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let sean_and_tess = users
+    ///     .select(name)
+    ///     .filter(name.eq_any_array(vec!["Sean", "Tess"]))
+    ///     .load::<String>(&connection);
+    /// assert_eq!(Ok(vec!["Sean".to_string(), "Tess".to_string()]), sean_and_tess);
+    /// # }
+    /// ```
+    fn eq_any_array<T>(self, values: T) -> Eq<Self, Any<T::Expression>>
+    where
+        T: AsArrayExpression<Self::SqlType>,
+    {
+        Eq::new(self, any(values))
+    }
+
+    /// Creates a PostgreSQL `!= ALL(...)` expression.
+    ///
+    /// See [`eq_any_array`](#method.eq_any_array) for why this is preferable
+    /// to [`ne_all`] when the collection may have a variable number of
+    /// elements across calls.
+    ///
+    /// [`ne_all`]: ../expression_methods/trait.ExpressionMethods.html#method.ne_all
+    fn ne_all_array<T>(self, values: T) -> NotEq<Self, All<T::Expression>>
+    where
+        T: AsArrayExpression<Self::SqlType>,
+    {
+        NotEq::new(self, all(values))
+    }
     /// Creates a PostgreSQL `IS NOT DISTINCT FROM` expression.
     ///
     /// This behaves identically to the `=` operator, except that `NULL` is
@@ -475,6 +523,109 @@ pub trait PgTextExpressionMethods: Expression<SqlType = Text> + Sized {
     fn not_ilike<T: AsExpression<Text>>(self, other: T) -> NotILike<Self, T::Expression> {
         NotILike::new(self.as_expression(), other.as_expression())
     }
+
+    /// Creates a PostgreSQL `~` (case-sensitive regexp match) expression.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     use schema::users::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let starts_with_s = users
+    ///     .select(name)
+    ///     .filter(name.matches_regex("^S"))
+    ///     .get_results::<String>(&connection);
+    /// assert_eq!(Ok(vec!["Sean".to_string()]), starts_with_s);
+    /// # }
+    /// ```
+    fn matches_regex<T: AsExpression<Text>>(self, other: T) -> Matches<Self, T::Expression> {
+        Matches::new(self.as_expression(), other.as_expression())
+    }
+
+    /// Creates a PostgreSQL `!~` (negated case-sensitive regexp match) expression.
+    fn not_matches_regex<T: AsExpression<Text>>(self, other: T) -> NotMatches<Self, T::Expression> {
+        NotMatches::new(self.as_expression(), other.as_expression())
+    }
+
+    /// Creates a PostgreSQL `~*` (case-insensitive regexp match) expression.
+    fn imatches_regex<T: AsExpression<Text>>(self, other: T) -> IMatches<Self, T::Expression> {
+        IMatches::new(self.as_expression(), other.as_expression())
+    }
+
+    /// Creates a PostgreSQL `!~*` (negated case-insensitive regexp match) expression.
+    fn not_imatches_regex<T: AsExpression<Text>>(
+        self,
+        other: T,
+    ) -> NotIMatches<Self, T::Expression> {
+        NotIMatches::new(self.as_expression(), other.as_expression())
+    }
 }
 
 impl<T: Expression<SqlType = Text>> PgTextExpressionMethods for T {}
+
+/// PostgreSQL specific methods present on network address expressions.
+pub trait PgNetExpressionMethods: Expression<SqlType = Inet> + Sized {
+    /// Creates a PostgreSQL `<<` expression.
+    ///
+    /// This operator returns whether a network is contained by another
+    /// network. `foo.is_contained_by_net(bar)` is the same as
+    /// `bar.contains_net(foo)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # table! {
+    /// #     hosts {
+    /// #         id -> Integer,
+    /// #         address -> Inet,
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use self::hosts::dsl::*;
+    /// #     let conn = establish_connection();
+    /// #     conn.execute("DROP TABLE IF EXISTS hosts").unwrap();
+    /// #     conn.execute("CREATE TABLE hosts (id SERIAL PRIMARY KEY, address INET NOT NULL)").unwrap();
+    /// #
+    /// diesel::insert_into(hosts)
+    ///     .values(address.eq("192.168.1.5"))
+    ///     .execute(&conn)?;
+    ///
+    /// let data = hosts.select(id)
+    ///     .filter(address.is_contained_by_net("192.168.1.0/24"))
+    ///     .load::<i32>(&conn)?;
+    /// assert_eq!(vec![1], data);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn is_contained_by_net<T>(self, other: T) -> IsContainedByNet<Self, T::Expression>
+    where
+        T: AsExpression<Inet>,
+    {
+        IsContainedByNet::new(self, other.as_expression())
+    }
+
+    /// Creates a PostgreSQL `>>` expression.
+    ///
+    /// This operator returns whether a network contains another network.
+    /// `foo.contains_net(bar)` is the same as `bar.is_contained_by_net(foo)`.
+    fn contains_net<T>(self, other: T) -> ContainsNet<Self, T::Expression>
+    where
+        T: AsExpression<Inet>,
+    {
+        ContainsNet::new(self, other.as_expression())
+    }
+}
+
+impl<T: Expression<SqlType = Inet>> PgNetExpressionMethods for T {}
@@ -0,0 +1,74 @@
+use backend::Backend;
+use expression::{AppearsOnTable, Expression, NonAggregate, SelectableExpression};
+use pg::Pg;
+use query_builder::{AstPass, QueryFragment};
+use result::QueryResult;
+use sql_types::Bool;
+
+/// Represents `expr FILTER (WHERE predicate)`, restricting the rows an
+/// aggregate function such as `count` or `sum` operates on without an
+/// additional `GROUP BY`.
+///
+/// See [`FilterAggregateExpressionMethods`](trait.FilterAggregateExpressionMethods.html) for usage.
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct AggregateFilter<Expr, Predicate> {
+    expr: Expr,
+    predicate: Predicate,
+}
+
+impl<Expr, Predicate> AggregateFilter<Expr, Predicate> {
+    pub fn new(expr: Expr, predicate: Predicate) -> Self {
+        AggregateFilter { expr, predicate }
+    }
+}
+
+impl<Expr, Predicate> Expression for AggregateFilter<Expr, Predicate>
+where
+    Expr: Expression,
+{
+    type SqlType = Expr::SqlType;
+}
+
+impl<Expr, Predicate, QS> SelectableExpression<QS> for AggregateFilter<Expr, Predicate> where
+    AggregateFilter<Expr, Predicate>: AppearsOnTable<QS>
+{
+}
+
+impl<Expr, Predicate, QS> AppearsOnTable<QS> for AggregateFilter<Expr, Predicate> where
+    AggregateFilter<Expr, Predicate>: Expression
+{
+}
+
+impl<Expr, Predicate> NonAggregate for AggregateFilter<Expr, Predicate> {}
+
+impl<Expr, Predicate> QueryFragment<Pg> for AggregateFilter<Expr, Predicate>
+where
+    Expr: QueryFragment<Pg>,
+    Predicate: QueryFragment<Pg>,
+{
+    fn walk_ast(&self, mut out: AstPass<Pg>) -> QueryResult<()> {
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(" FILTER (WHERE ");
+        self.predicate.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+/// Adds the `.filter()` method to aggregate expressions, for constructing a
+/// PostgreSQL `FILTER (WHERE ...)` clause.
+///
+/// Unlike [`QueryDsl::filter`](../../query_dsl/trait.QueryDsl.html#method.filter),
+/// this narrows the rows seen by a single aggregate expression rather than
+/// the whole query.
+pub trait FilterAggregateExpressionMethods: Expression + Sized {
+    /// See the trait documentation.
+    fn filter<Predicate>(self, predicate: Predicate) -> AggregateFilter<Self, Predicate>
+    where
+        Predicate: Expression<SqlType = Bool>,
+    {
+        AggregateFilter::new(self, predicate)
+    }
+}
+
+impl<T: Expression> FilterAggregateExpressionMethods for T {}
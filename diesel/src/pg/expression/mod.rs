@@ -14,7 +14,9 @@ pub mod helper_types;
 #[doc(hidden)]
 pub mod operators;
 
+mod cast;
 mod date_and_time;
+pub(crate) mod filter;
 
 /// PostgreSQL specific expression DSL methods.
 ///
@@ -28,5 +30,8 @@ pub mod dsl {
     #[doc(inline)]
     pub use super::array::array;
 
+    #[doc(inline)]
+    pub use super::date_and_time::date_trunc;
+
     pub use super::extensions::*;
 }
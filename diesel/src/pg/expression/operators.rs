@@ -7,5 +7,11 @@ diesel_infix_operator!(Contains, " @> ", backend: Pg);
 diesel_infix_operator!(IsContainedBy, " <@ ", backend: Pg);
 diesel_infix_operator!(ILike, " ILIKE ", backend: Pg);
 diesel_infix_operator!(NotILike, " NOT ILIKE ", backend: Pg);
+diesel_infix_operator!(Matches, " ~ ", backend: Pg);
+diesel_infix_operator!(NotMatches, " !~ ", backend: Pg);
+diesel_infix_operator!(IMatches, " ~* ", backend: Pg);
+diesel_infix_operator!(NotIMatches, " !~* ", backend: Pg);
+diesel_infix_operator!(IsContainedByNet, " << ", backend: Pg);
+diesel_infix_operator!(ContainsNet, " >> ", backend: Pg);
 diesel_postfix_operator!(NullsFirst, " NULLS FIRST", (), backend: Pg);
 diesel_postfix_operator!(NullsLast, " NULLS LAST", (), backend: Pg);
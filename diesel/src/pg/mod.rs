@@ -11,13 +11,15 @@ pub mod upsert;
 mod backend;
 mod connection;
 mod metadata_lookup;
+mod multi_host;
 mod query_builder;
 pub(crate) mod serialize;
 mod transaction;
 
 pub use self::backend::{Pg, PgTypeMetadata};
-pub use self::connection::PgConnection;
+pub use self::connection::{CopyIn, CopyRowWriter, PgConnection};
 pub use self::metadata_lookup::PgMetadataLookup;
+pub use self::multi_host::PgMultiHostConnection;
 pub use self::query_builder::DistinctOnClause;
 pub use self::query_builder::PgQueryBuilder;
 pub use self::transaction::TransactionBuilder;
@@ -0,0 +1,95 @@
+extern crate time_03;
+
+use self::time_03::{Date, OffsetDateTime, PrimitiveDateTime, Time as ClockTime};
+use std::io::Write;
+
+use super::{PgDate, PgTime, PgTimestamp};
+use deserialize::{self, FromSql};
+use pg::Pg;
+use serialize::{self, Output, ToSql};
+use sql_types;
+
+/// Number of seconds from the Unix epoch (1970-01-01) to the Postgres epoch
+/// (2000-01-01), matching `TIME_SEC_CONV` in `deprecated_time.rs`.
+const PG_EPOCH_IN_UNIX_SECONDS: i64 = 946_684_800;
+
+/// Julian day number of the Postgres epoch (2000-01-01).
+const PG_EPOCH_JULIAN_DAY: i32 = 2_451_545;
+
+impl ToSql<sql_types::Timestamp, Pg> for PrimitiveDateTime {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        let unix_timestamp = self.assume_utc().unix_timestamp();
+        let micros =
+            (unix_timestamp - PG_EPOCH_IN_UNIX_SECONDS) * 1_000_000 + i64::from(self.microsecond());
+        ToSql::<sql_types::Timestamp, Pg>::to_sql(&PgTimestamp(micros), out)
+    }
+}
+
+impl FromSql<sql_types::Timestamp, Pg> for PrimitiveDateTime {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let PgTimestamp(offset_microseconds) =
+            FromSql::<sql_types::Timestamp, Pg>::from_sql(bytes)?;
+        let nanos =
+            (i128::from(PG_EPOCH_IN_UNIX_SECONDS) * 1_000_000 + i128::from(offset_microseconds))
+                * 1000;
+        let dt = OffsetDateTime::from_unix_timestamp_nanos(nanos)?;
+        Ok(PrimitiveDateTime::new(dt.date(), dt.time()))
+    }
+}
+
+impl ToSql<sql_types::Timestamptz, Pg> for OffsetDateTime {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        let micros = self.unix_timestamp_nanos() / 1000
+            - i128::from(PG_EPOCH_IN_UNIX_SECONDS) * 1_000_000;
+        ToSql::<sql_types::Timestamptz, Pg>::to_sql(&PgTimestamp(micros as i64), out)
+    }
+}
+
+impl FromSql<sql_types::Timestamptz, Pg> for OffsetDateTime {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let PgTimestamp(offset_microseconds) =
+            FromSql::<sql_types::Timestamptz, Pg>::from_sql(bytes)?;
+        let nanos =
+            (i128::from(PG_EPOCH_IN_UNIX_SECONDS) * 1_000_000 + i128::from(offset_microseconds))
+                * 1000;
+        Ok(OffsetDateTime::from_unix_timestamp_nanos(nanos)?)
+    }
+}
+
+impl ToSql<sql_types::Date, Pg> for Date {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        ToSql::<sql_types::Date, Pg>::to_sql(
+            &PgDate(self.to_julian_day() - PG_EPOCH_JULIAN_DAY),
+            out,
+        )
+    }
+}
+
+impl FromSql<sql_types::Date, Pg> for Date {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let PgDate(offset_days) = FromSql::<sql_types::Date, Pg>::from_sql(bytes)?;
+        Ok(Date::from_julian_day(offset_days + PG_EPOCH_JULIAN_DAY)?)
+    }
+}
+
+impl ToSql<sql_types::Time, Pg> for ClockTime {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        let (hour, minute, second, micro) = self.as_hms_micro();
+        let micros = (i64::from(hour) * 3600 + i64::from(minute) * 60 + i64::from(second))
+            * 1_000_000
+            + i64::from(micro);
+        ToSql::<sql_types::Time, Pg>::to_sql(&PgTime(micros), out)
+    }
+}
+
+impl FromSql<sql_types::Time, Pg> for ClockTime {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let PgTime(micros) = FromSql::<sql_types::Time, Pg>::from_sql(bytes)?;
+        let seconds = micros / 1_000_000;
+        let micro = (micros % 1_000_000) as u32;
+        let hour = (seconds / 3600) as u8;
+        let minute = ((seconds % 3600) / 60) as u8;
+        let second = (seconds % 60) as u8;
+        Ok(ClockTime::from_hms_micro(hour, minute, second, micro)?)
+    }
+}
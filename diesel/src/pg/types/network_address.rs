@@ -3,7 +3,7 @@ extern crate libc;
 
 use self::ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
 use std::io::prelude::*;
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 use deserialize::{self, FromSql};
 use pg::Pg;
@@ -35,6 +35,11 @@ mod foreign_derives {
     #[sql_type = "Inet"]
     #[sql_type = "Cidr"]
     struct IpNetworkProxy(IpNetwork);
+
+    #[derive(FromSqlRow, AsExpression)]
+    #[diesel(foreign_derive)]
+    #[sql_type = "Inet"]
+    struct IpAddrProxy(::std::net::IpAddr);
 }
 
 macro_rules! err {
@@ -150,6 +155,27 @@ macro_rules! impl_Sql {
 impl_Sql!(Inet, 0);
 impl_Sql!(Cidr, 1);
 
+/// `std::net::IpAddr` maps to `Inet` as a full-width host address (a `/32`
+/// network for IPv4, `/128` for IPv6). `Cidr` intentionally isn't supported
+/// here, since a bare `IpAddr` can't represent an arbitrary prefix length;
+/// use `ipnetwork::IpNetwork` for that.
+impl FromSql<Inet, Pg> for IpAddr {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let network = <IpNetwork as FromSql<Inet, Pg>>::from_sql(bytes)?;
+        Ok(network.ip())
+    }
+}
+
+impl ToSql<Inet, Pg> for IpAddr {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        let network = match *self {
+            IpAddr::V4(addr) => IpNetwork::V4(Ipv4Network::new(addr, 32)?),
+            IpAddr::V6(addr) => IpNetwork::V6(Ipv6Network::new(addr, 128)?),
+        };
+        ToSql::<Inet, Pg>::to_sql(&network, out)
+    }
+}
+
 #[test]
 fn macaddr_roundtrip() {
     let mut bytes = Output::test();
@@ -301,3 +301,204 @@ mod bigdecimal {
         }
     }
 }
+
+#[cfg(feature = "decimal")]
+mod decimal {
+    extern crate rust_decimal;
+
+    use self::rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    use deserialize::{self, FromSql};
+    use pg::data_types::PgNumeric;
+    use pg::Pg;
+    use serialize::{self, Output, ToSql};
+    use sql_types::Numeric;
+    use std::io::prelude::*;
+
+    fn pg_numeric_to_decimal(numeric: &PgNumeric) -> deserialize::Result<Decimal> {
+        let (negative, weight, scale, digits) = match *numeric {
+            PgNumeric::Positive {
+                weight,
+                scale,
+                ref digits,
+            } => (false, weight, scale, digits),
+            PgNumeric::Negative {
+                weight,
+                scale,
+                ref digits,
+            } => (true, weight, scale, digits),
+            PgNumeric::NaN => return Err(Box::from("NaN is not (yet) supported in rust_decimal")),
+        };
+
+        let mut digit_str = String::new();
+        for digit in digits {
+            digit_str.push_str(&format!("{:04}", digit));
+        }
+        let mut chars: Vec<char> = digit_str.chars().collect();
+
+        // The decimal point sits `(weight + 1) * 4` digits in from the start
+        // of the concatenated digit groups.
+        let point = (i64::from(weight) + 1) * 4;
+        if point <= 0 {
+            let mut padding = vec!['0'; (-point) as usize];
+            padding.append(&mut chars);
+            chars = padding;
+        }
+        let point = point.max(0) as usize;
+        while chars.len() < point {
+            chars.push('0');
+        }
+
+        let (int_part, frac_part) = chars.split_at(point);
+        let mut frac_part = frac_part.to_vec();
+        frac_part.resize(scale as usize, '0');
+
+        let mut result = String::new();
+        if negative {
+            result.push('-');
+        }
+        if int_part.is_empty() {
+            result.push('0');
+        } else {
+            result.extend(int_part);
+        }
+        if scale > 0 {
+            result.push('.');
+            result.extend(frac_part);
+        }
+
+        Decimal::from_str(&result)
+            .map_err(|e| Box::from(format!("{} is not a valid decimal: {}", result, e)))
+    }
+
+    impl<'a> From<&'a Decimal> for PgNumeric {
+        fn from(decimal: &'a Decimal) -> Self {
+            let scale = decimal.scale() as u16;
+            let unsigned = decimal.abs().to_string();
+            let (int_part, frac_part) = match unsigned.find('.') {
+                Some(idx) => (&unsigned[..idx], &unsigned[idx + 1..]),
+                None => (&unsigned[..], ""),
+            };
+
+            let mut int_digits: Vec<char> = int_part.chars().collect();
+            while int_digits.len() % 4 != 0 {
+                int_digits.insert(0, '0');
+            }
+            let mut frac_digits: Vec<char> = frac_part.chars().collect();
+            while frac_digits.len() % 4 != 0 {
+                frac_digits.push('0');
+            }
+            let mut weight = int_digits.len() as i16 / 4 - 1;
+
+            let mut all_digits = int_digits;
+            all_digits.append(&mut frac_digits);
+
+            let mut digits: Vec<i16> = all_digits
+                .chunks(4)
+                .map(|chunk| {
+                    chunk
+                        .iter()
+                        .collect::<String>()
+                        .parse()
+                        .expect("four decimal digits always parse as i16")
+                }).collect();
+
+            while digits.len() > 1 && digits[0] == 0 {
+                digits.remove(0);
+                weight -= 1;
+            }
+            while digits.len() > 1 && *digits.last().unwrap() == 0 {
+                digits.pop();
+            }
+            if digits == [0] {
+                weight = 0;
+            }
+
+            if !decimal.is_sign_negative() || digits == [0] {
+                PgNumeric::Positive {
+                    digits,
+                    scale,
+                    weight,
+                }
+            } else {
+                PgNumeric::Negative {
+                    digits,
+                    scale,
+                    weight,
+                }
+            }
+        }
+    }
+
+    impl From<Decimal> for PgNumeric {
+        fn from(decimal: Decimal) -> Self {
+            (&decimal).into()
+        }
+    }
+
+    impl ToSql<Numeric, Pg> for Decimal {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+            let numeric = PgNumeric::from(self);
+            ToSql::<Numeric, Pg>::to_sql(&numeric, out)
+        }
+    }
+
+    impl FromSql<Numeric, Pg> for Decimal {
+        fn from_sql(numeric: Option<&[u8]>) -> deserialize::Result<Self> {
+            let numeric = PgNumeric::from_sql(numeric)?;
+            pg_numeric_to_decimal(&numeric)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn decimal_to_pgnumeric_converts_digits_to_base_10000() {
+            let decimal = Decimal::from_str("1").unwrap();
+            let expected = PgNumeric::Positive {
+                weight: 0,
+                scale: 0,
+                digits: vec![1],
+            };
+            assert_eq!(expected, decimal.into());
+
+            let decimal = Decimal::from_str("10000").unwrap();
+            let expected = PgNumeric::Positive {
+                weight: 1,
+                scale: 0,
+                digits: vec![1],
+            };
+            assert_eq!(expected, decimal.into());
+        }
+
+        #[test]
+        fn decimal_to_pg_numeric_properly_adjusts_scale() {
+            let decimal = Decimal::from_str("1.1").unwrap();
+            let expected = PgNumeric::Positive {
+                weight: 0,
+                scale: 1,
+                digits: vec![1, 1000],
+            };
+            assert_eq!(expected, decimal.into());
+
+            let decimal = Decimal::from_str("0.1").unwrap();
+            let expected = PgNumeric::Positive {
+                weight: -1,
+                scale: 1,
+                digits: vec![1000],
+            };
+            assert_eq!(expected, decimal.into());
+        }
+
+        #[test]
+        fn decimal_round_trips_through_pg_numeric() {
+            let decimal = Decimal::from_str("-123.456").unwrap();
+            let pg_numeric: PgNumeric = decimal.into();
+            let res: Decimal = pg_numeric_to_decimal(&pg_numeric).unwrap();
+            assert_eq!(res, decimal);
+        }
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! See [the methods on `InsertStatement`](../../query_builder/struct.InsertStatement.html#impl-1)
 //! for usage examples.
+//!
+//! This module, including `IncompleteOnConflict::filter_target`, is
+//! PostgreSQL-only. SQLite's `ON CONFLICT` support is not yet implemented by
+//! Diesel at all, so there is nothing here for that backend to extend.
 
 mod on_conflict_actions;
 mod on_conflict_clause;
@@ -1,6 +1,7 @@
 use super::on_conflict_actions::*;
 use super::on_conflict_clause::*;
 use super::on_conflict_target::*;
+use expression::AppearsOnTable;
 use query_builder::{AsChangeset, InsertStatement, UndecoratedInsertRecord};
 use query_source::QuerySource;
 
@@ -202,6 +203,57 @@ impl<T, U, Op, Ret, Target> IncompleteOnConflict<InsertStatement<T, U, Op, Ret>,
     }
 }
 
+impl<T, U, Op, Ret, Target> IncompleteOnConflict<InsertStatement<T, U, Op, Ret>, Target> {
+    /// Restricts the conflict target to a partial unique index matching
+    /// `predicate`, generating `ON CONFLICT (...) WHERE predicate`.
+    ///
+    /// This is required when the unique index the conflict is meant to
+    /// target is a partial index (`CREATE UNIQUE INDEX ... WHERE ...`), since
+    /// Postgres requires the inference specification to repeat the index
+    /// predicate verbatim to be able to pick the index.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("on_conflict_docs_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     use users::dsl::*;
+    /// #     let conn = establish_connection();
+    /// #     conn.execute("TRUNCATE TABLE users").unwrap();
+    /// conn.execute(
+    ///     "CREATE UNIQUE INDEX users_name_no_pascal ON users (name) WHERE name <> 'Pascal'",
+    /// ).unwrap();
+    /// let user = User { id: 1, name: "Sean", };
+    /// let same_name_different_id = User { id: 2, name: "Sean" };
+    ///
+    /// assert_eq!(Ok(1), diesel::insert_into(users).values(&user).execute(&conn));
+    ///
+    /// let inserted_row_count = diesel::insert_into(users)
+    ///     .values(&same_name_different_id)
+    ///     .on_conflict(name)
+    ///     .filter_target(name.ne("Pascal"))
+    ///     .do_nothing()
+    ///     .execute(&conn);
+    /// assert_eq!(Ok(0), inserted_row_count);
+    /// # }
+    /// ```
+    pub fn filter_target<Predicate>(
+        self,
+        predicate: Predicate,
+    ) -> IncompleteOnConflict<InsertStatement<T, U, Op, Ret>, ConflictTargetWithPredicate<Target, Predicate>>
+    where
+        Target: ColumnOrExpressionTarget,
+        Predicate: AppearsOnTable<T>,
+    {
+        IncompleteOnConflict {
+            stmt: self.stmt,
+            target: ConflictTargetWithPredicate::new(self.target, predicate),
+        }
+    }
+}
+
 impl<Stmt, Target> IncompleteOnConflict<Stmt, Target> {
     /// Used to create a query in the form `ON CONFLICT (...) DO UPDATE ...`
     ///
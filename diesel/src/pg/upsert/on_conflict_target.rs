@@ -1,4 +1,4 @@
-use expression::SqlLiteral;
+use expression::{AppearsOnTable, SqlLiteral};
 use pg::Pg;
 use query_builder::*;
 use query_source::Column;
@@ -56,6 +56,15 @@ pub struct OnConstraint<'a> {
 
 pub trait OnConflictTarget<Table>: QueryFragment<Pg> {}
 
+/// Marker for `ConflictTarget`s that name a column or expression list, as opposed to
+/// `on_constraint(...)`.
+///
+/// Used to restrict [`IncompleteOnConflict::filter_target`](../../pg/upsert/struct.IncompleteOnConflict.html#method.filter_target)
+/// to targets it can actually apply to: Postgres infers a target for `ON CONFLICT (...) WHERE
+/// <predicate>` from the column/expression list, but rejects a predicate on `ON CONFLICT ON
+/// CONSTRAINT ...` outright, since a named constraint is looked up directly rather than inferred.
+pub trait ColumnOrExpressionTarget {}
+
 #[doc(hidden)]
 #[derive(Debug, Clone, Copy)]
 pub struct NoConflictTarget;
@@ -83,6 +92,8 @@ impl<T: Column> QueryFragment<Pg> for ConflictTarget<T> {
 
 impl<T: Column> OnConflictTarget<T::Table> for ConflictTarget<T> {}
 
+impl<T: Column> ColumnOrExpressionTarget for ConflictTarget<T> {}
+
 impl<ST> QueryFragment<Pg> for ConflictTarget<SqlLiteral<ST>>
 where
     SqlLiteral<ST>: QueryFragment<Pg>,
@@ -100,6 +111,8 @@ where
 {
 }
 
+impl<ST> ColumnOrExpressionTarget for ConflictTarget<SqlLiteral<ST>> {}
+
 impl<'a> QueryFragment<Pg> for ConflictTarget<OnConstraint<'a>> {
     fn walk_ast(&self, mut out: AstPass<Pg>) -> QueryResult<()> {
         out.push_sql(" ON CONSTRAINT ");
@@ -133,6 +146,12 @@ macro_rules! on_conflict_tuples {
             $($col: Column<Table=T::Table>,)+
         {
         }
+
+        impl<T, $($col),+> ColumnOrExpressionTarget for ConflictTarget<(T, $($col),+)> where
+            T: Column,
+            $($col: Column<Table=T::Table>,)+
+        {
+        }
     }
 }
 
@@ -142,3 +161,36 @@ on_conflict_tuples!(U, V, W);
 on_conflict_tuples!(U, V, W, X);
 on_conflict_tuples!(U, V, W, X, Y);
 on_conflict_tuples!(U, V, W, X, Y, Z);
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct ConflictTargetWithPredicate<Target, Predicate> {
+    target: Target,
+    predicate: Predicate,
+}
+
+impl<Target, Predicate> ConflictTargetWithPredicate<Target, Predicate> {
+    pub(crate) fn new(target: Target, predicate: Predicate) -> Self {
+        ConflictTargetWithPredicate { target, predicate }
+    }
+}
+
+impl<Target, Predicate> QueryFragment<Pg> for ConflictTargetWithPredicate<Target, Predicate>
+where
+    Target: QueryFragment<Pg>,
+    Predicate: QueryFragment<Pg>,
+{
+    fn walk_ast(&self, mut out: AstPass<Pg>) -> QueryResult<()> {
+        self.target.walk_ast(out.reborrow())?;
+        out.push_sql(" WHERE ");
+        self.predicate.walk_ast(out.reborrow())?;
+        Ok(())
+    }
+}
+
+impl<Table, Target, Predicate> OnConflictTarget<Table> for ConflictTargetWithPredicate<Target, Predicate>
+where
+    Target: OnConflictTarget<Table>,
+    Predicate: AppearsOnTable<Table> + QueryFragment<Pg>,
+{
+}
@@ -0,0 +1,155 @@
+use backend::Backend;
+use dsl::AsExprOf;
+use expression::IntoSql;
+use query_builder::limit_clause::{LimitClause, NoLimitClause};
+use query_builder::offset_clause::{NoOffsetClause, OffsetClause};
+use query_builder::order_clause::{NoOrderClause, OrderClause};
+use query_builder::{AstPass, Query, QueryFragment, QueryId};
+use query_dsl::RunQueryDsl;
+use result::QueryResult;
+use sql_types::BigInt;
+
+/// The kind of set operation used to combine two queries.
+///
+/// See [`CombineDsl`](../query_dsl/trait.CombineDsl.html) for the methods
+/// that construct a `CombinationClause` of each kind.
+#[derive(Debug, Clone, Copy, QueryId)]
+pub enum CombinationKind {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+impl CombinationKind {
+    fn literal(&self) -> &'static str {
+        match *self {
+            CombinationKind::Union => "UNION",
+            CombinationKind::UnionAll => "UNION ALL",
+            CombinationKind::Intersect => "INTERSECT",
+            CombinationKind::Except => "EXCEPT",
+        }
+    }
+}
+
+/// Represents a `UNION`, `UNION ALL`, `INTERSECT`, or `EXCEPT` of two
+/// queries, produced by the methods on
+/// [`CombineDsl`](../query_dsl/trait.CombineDsl.html).
+///
+/// The combined result can still be ordered, limited, and offset before it
+/// is loaded, in the same way as a plain select statement.
+#[derive(Debug, Clone, Copy)]
+pub struct CombinationClause<Kind, Left, Right, Order = NoOrderClause, Limit = NoLimitClause, Offset = NoOffsetClause> {
+    kind: Kind,
+    left: Left,
+    right: Right,
+    order: Order,
+    limit: Limit,
+    offset: Offset,
+}
+
+impl<Left, Right> CombinationClause<CombinationKind, Left, Right> {
+    pub(crate) fn new(kind: CombinationKind, left: Left, right: Right) -> Self {
+        CombinationClause {
+            kind: kind,
+            left: left,
+            right: right,
+            order: NoOrderClause,
+            limit: NoLimitClause,
+            offset: NoOffsetClause,
+        }
+    }
+}
+
+impl<Kind, Left, Right, Order, Limit, Offset> CombinationClause<Kind, Left, Right, Order, Limit, Offset> {
+    /// Adds an `ORDER BY` clause applying to the combined result.
+    pub fn order_by<Expr>(
+        self,
+        expr: Expr,
+    ) -> CombinationClause<Kind, Left, Right, OrderClause<Expr>, Limit, Offset> {
+        CombinationClause {
+            kind: self.kind,
+            left: self.left,
+            right: self.right,
+            order: OrderClause(expr),
+            limit: self.limit,
+            offset: self.offset,
+        }
+    }
+
+    /// Limits the number of rows returned by the combined result.
+    pub fn limit(
+        self,
+        limit: i64,
+    ) -> CombinationClause<Kind, Left, Right, Order, LimitClause<AsExprOf<i64, BigInt>>, Offset> {
+        CombinationClause {
+            kind: self.kind,
+            left: self.left,
+            right: self.right,
+            order: self.order,
+            limit: LimitClause(limit.into_sql::<BigInt>()),
+            offset: self.offset,
+        }
+    }
+
+    /// Skips the given number of rows of the combined result.
+    pub fn offset(
+        self,
+        offset: i64,
+    ) -> CombinationClause<Kind, Left, Right, Order, Limit, OffsetClause<AsExprOf<i64, BigInt>>> {
+        CombinationClause {
+            kind: self.kind,
+            left: self.left,
+            right: self.right,
+            order: self.order,
+            limit: self.limit,
+            offset: OffsetClause(offset.into_sql::<BigInt>()),
+        }
+    }
+}
+
+// The kind of combination is a runtime value, so two clauses of the same type can render
+// different SQL; they must not share a prepared statement.
+impl<Kind, Left, Right, Order, Limit, Offset> QueryId
+    for CombinationClause<Kind, Left, Right, Order, Limit, Offset>
+{
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<Left, Right, Order, Limit, Offset> Query
+    for CombinationClause<CombinationKind, Left, Right, Order, Limit, Offset>
+where
+    Left: Query,
+    Right: Query<SqlType = Left::SqlType>,
+{
+    type SqlType = Left::SqlType;
+}
+
+impl<DB, Left, Right, Order, Limit, Offset> QueryFragment<DB>
+    for CombinationClause<CombinationKind, Left, Right, Order, Limit, Offset>
+where
+    DB: Backend,
+    Left: QueryFragment<DB>,
+    Right: QueryFragment<DB>,
+    Order: QueryFragment<DB>,
+    Limit: QueryFragment<DB>,
+    Offset: QueryFragment<DB>,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        self.left.walk_ast(out.reborrow())?;
+        out.push_sql(" ");
+        out.push_sql(self.kind.literal());
+        out.push_sql(" ");
+        self.right.walk_ast(out.reborrow())?;
+        self.order.walk_ast(out.reborrow())?;
+        self.limit.walk_ast(out.reborrow())?;
+        self.offset.walk_ast(out.reborrow())
+    }
+}
+
+impl<Left, Right, Order, Limit, Offset, Conn> RunQueryDsl<Conn>
+    for CombinationClause<CombinationKind, Left, Right, Order, Limit, Offset>
+{
+}
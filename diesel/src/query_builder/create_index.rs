@@ -0,0 +1,157 @@
+use backend::Backend;
+use expression::{AppearsOnTable, Expression};
+use query_builder::{AstPass, ColumnList, QueryFragment, QueryId};
+use query_dsl::RunQueryDsl;
+use query_source::Table;
+use result::QueryResult;
+use sql_types::Bool;
+
+/// A `CREATE INDEX` statement, as returned by [`create_index`](../fn.create_index.html).
+#[must_use = "Queries are only executed when calling `load`, `get_result` or similar."]
+pub struct CreateIndex<'a, Tab, Cols, Predicate> {
+    index_name: &'a str,
+    unique: bool,
+    table: Tab,
+    columns: Cols,
+    predicate: Option<Predicate>,
+}
+
+impl<'a> CreateIndex<'a, (), (), ()> {
+    pub(crate) fn new(index_name: &'a str) -> Self {
+        CreateIndex {
+            index_name,
+            unique: false,
+            table: (),
+            columns: (),
+            predicate: None,
+        }
+    }
+
+    /// The table this index is being created on.
+    pub fn on<Tab: Table>(self, table: Tab) -> CreateIndex<'a, Tab, (), ()> {
+        CreateIndex {
+            index_name: self.index_name,
+            unique: self.unique,
+            table,
+            columns: (),
+            predicate: None,
+        }
+    }
+}
+
+impl<'a, Tab> CreateIndex<'a, Tab, (), ()> {
+    /// The column, or tuple of columns, to index.
+    pub fn columns<Cols>(self, columns: Cols) -> CreateIndex<'a, Tab, Cols, ()>
+    where
+        Cols: ColumnList<Table = Tab>,
+    {
+        CreateIndex {
+            index_name: self.index_name,
+            unique: self.unique,
+            table: self.table,
+            columns,
+            predicate: None,
+        }
+    }
+}
+
+impl<'a, Tab, Cols, Predicate> CreateIndex<'a, Tab, Cols, Predicate> {
+    /// Creates a `UNIQUE` index.
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+}
+
+impl<'a, Tab, Cols> CreateIndex<'a, Tab, Cols, ()> {
+    /// Restricts the index to rows matching `predicate`, creating a partial index. Supported by
+    /// Pg and SQLite; MySQL has no equivalent.
+    pub fn where_<Predicate>(self, predicate: Predicate) -> CreateIndex<'a, Tab, Cols, Predicate>
+    where
+        Predicate: Expression<SqlType = Bool> + AppearsOnTable<Tab>,
+    {
+        CreateIndex {
+            index_name: self.index_name,
+            unique: self.unique,
+            table: self.table,
+            columns: self.columns,
+            predicate: Some(predicate),
+        }
+    }
+}
+
+impl<'a, Tab, Cols, Predicate, DB> QueryFragment<DB> for CreateIndex<'a, Tab, Cols, Predicate>
+where
+    DB: Backend,
+    Tab: Table,
+    Tab::FromClause: QueryFragment<DB>,
+    Cols: ColumnList<Table = Tab>,
+    Predicate: QueryFragment<DB>,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.push_sql("CREATE ");
+        if self.unique {
+            out.push_sql("UNIQUE ");
+        }
+        out.push_sql("INDEX ");
+        out.push_identifier(self.index_name)?;
+        out.push_sql(" ON ");
+        self.table.from_clause().walk_ast(out.reborrow())?;
+        out.push_sql(" (");
+        self.columns.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        if let Some(ref predicate) = self.predicate {
+            out.push_sql(" WHERE ");
+            predicate.walk_ast(out.reborrow())?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, Tab, Cols, Predicate> QueryId for CreateIndex<'a, Tab, Cols, Predicate> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<'a, Tab, Cols, Predicate, Conn> RunQueryDsl<Conn> for CreateIndex<'a, Tab, Cols, Predicate> {}
+
+/// A `DROP INDEX` statement, as returned by [`drop_index`](fn.drop_index.html).
+#[must_use = "Queries are only executed when calling `load`, `get_result` or similar."]
+pub struct DropIndex<'a> {
+    index_name: &'a str,
+    if_exists: bool,
+}
+
+impl<'a> DropIndex<'a> {
+    pub(crate) fn new(index_name: &'a str) -> Self {
+        DropIndex {
+            index_name,
+            if_exists: false,
+        }
+    }
+
+    /// Adds `IF EXISTS` to the statement, so dropping a nonexistent index isn't an error.
+    pub fn if_exists(mut self) -> Self {
+        self.if_exists = true;
+        self
+    }
+}
+
+impl<'a, DB: Backend> QueryFragment<DB> for DropIndex<'a> {
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.push_sql("DROP INDEX ");
+        if self.if_exists {
+            out.push_sql("IF EXISTS ");
+        }
+        out.push_identifier(self.index_name)
+    }
+}
+
+impl<'a> QueryId for DropIndex<'a> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<'a, Conn> RunQueryDsl<Conn> for DropIndex<'a> {}
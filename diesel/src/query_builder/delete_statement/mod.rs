@@ -1,3 +1,7 @@
+mod using_clause;
+
+pub use self::using_clause::{NoUsingClause, UsingClause};
+
 use backend::Backend;
 use dsl::{Filter, IntoBoxed};
 use expression::{AppearsOnTable, SelectableExpression};
@@ -20,15 +24,19 @@ use result::QueryResult;
 /// - `Ret`: The `RETURNING` clause of this query. The exact types used to
 ///   represent this are private. You can safely rely on the default type
 ///   representing the lack of a `RETURNING` clause.
-pub struct DeleteStatement<T, U, Ret = NoReturningClause> {
+/// - `Using`: The `USING` clause of this query. The exact types used to
+///   represent this are private. You can safely rely on the default type
+///   representing the lack of a `USING` clause.
+pub struct DeleteStatement<T, U, Ret = NoReturningClause, Using = NoUsingClause> {
     table: T,
     where_clause: U,
     returning: Ret,
+    using_clause: Using,
 }
 
 /// A `DELETE` statement with a boxed `WHERE` clause
-pub type BoxedDeleteStatement<'a, DB, T, Ret = NoReturningClause> =
-    DeleteStatement<T, BoxedWhereClause<'a, DB>, Ret>;
+pub type BoxedDeleteStatement<'a, DB, T, Ret = NoReturningClause, Using = NoUsingClause> =
+    DeleteStatement<T, BoxedWhereClause<'a, DB>, Ret, Using>;
 
 impl<T, U> DeleteStatement<T, U, NoReturningClause> {
     pub(crate) fn new(table: T, where_clause: U) -> Self {
@@ -36,9 +44,57 @@ impl<T, U> DeleteStatement<T, U, NoReturningClause> {
             table: table,
             where_clause: where_clause,
             returning: NoReturningClause,
+            using_clause: NoUsingClause,
         }
     }
 
+    /// Adds an additional table to delete rows based on, generating
+    /// `DELETE ... USING`.
+    ///
+    /// This lets a bulk delete be keyed on data in another table without
+    /// resorting to `WHERE id IN (SELECT ...)`, which on Pg. can be
+    /// significantly slower than an equivalent join. `other` may be
+    /// referenced from `.filter()` by pairing it with a raw SQL predicate
+    /// (see [`sql()`](../dsl/fn.sql.html)), since `USING` is not yet wired
+    /// into the `SelectableExpression`/`AppearsOnTable` checks used by
+    /// generated columns.
+    ///
+    /// This is a PostgreSQL-specific extension. MySQL and SQLite have no
+    /// equivalent `DELETE ... USING` syntax; statements built with this
+    /// method are not usable on those backends.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # #[cfg(feature = "postgres")]
+    /// # fn main() {
+    /// #     use diesel::dsl::sql;
+    /// #     use diesel::sql_types::Bool;
+    /// #     use schema::{posts, users};
+    /// #     let connection = establish_connection();
+    /// let deleted_rows = diesel::delete(posts::table)
+    ///     .using(users::table)
+    ///     .filter(sql::<Bool>("posts.user_id = users.id"))
+    ///     .execute(&connection);
+    /// # let _ = deleted_rows;
+    /// # }
+    /// # #[cfg(not(feature = "postgres"))]
+    /// # fn main() {}
+    /// ```
+    pub fn using<F>(self, other: F) -> DeleteStatement<T, U, NoReturningClause, UsingClause<F>> {
+        DeleteStatement {
+            table: self.table,
+            where_clause: self.where_clause,
+            returning: self.returning,
+            using_clause: UsingClause(other),
+        }
+    }
+}
+
+impl<T, U, Using> DeleteStatement<T, U, NoReturningClause, Using> {
     /// Adds the given predicate to the `WHERE` clause of the statement being
     /// constructed.
     ///
@@ -126,69 +182,73 @@ impl<T, U> DeleteStatement<T, U, NoReturningClause> {
     }
 }
 
-impl<T, U, Ret, Predicate> FilterDsl<Predicate> for DeleteStatement<T, U, Ret>
+impl<T, U, Ret, Using, Predicate> FilterDsl<Predicate> for DeleteStatement<T, U, Ret, Using>
 where
     U: WhereAnd<Predicate>,
     Predicate: AppearsOnTable<T>,
 {
-    type Output = DeleteStatement<T, U::Output, Ret>;
+    type Output = DeleteStatement<T, U::Output, Ret, Using>;
 
     fn filter(self, predicate: Predicate) -> Self::Output {
         DeleteStatement {
             table: self.table,
             where_clause: self.where_clause.and(predicate),
             returning: self.returning,
+            using_clause: self.using_clause,
         }
     }
 }
 
-impl<'a, T, U, Ret, DB> BoxedDsl<'a, DB> for DeleteStatement<T, U, Ret>
+impl<'a, T, U, Ret, Using, DB> BoxedDsl<'a, DB> for DeleteStatement<T, U, Ret, Using>
 where
     U: Into<BoxedWhereClause<'a, DB>>,
 {
-    type Output = BoxedDeleteStatement<'a, DB, T, Ret>;
+    type Output = BoxedDeleteStatement<'a, DB, T, Ret, Using>;
 
     fn internal_into_boxed(self) -> Self::Output {
         DeleteStatement {
             table: self.table,
             where_clause: self.where_clause.into(),
             returning: self.returning,
+            using_clause: self.using_clause,
         }
     }
 }
 
-impl<T, U, Ret, DB> QueryFragment<DB> for DeleteStatement<T, U, Ret>
+impl<T, U, Ret, Using, DB> QueryFragment<DB> for DeleteStatement<T, U, Ret, Using>
 where
     DB: Backend,
     T: Table,
     T::FromClause: QueryFragment<DB>,
     U: QueryFragment<DB>,
     Ret: QueryFragment<DB>,
+    Using: QueryFragment<DB>,
 {
     fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
         out.push_sql("DELETE FROM ");
         self.table.from_clause().walk_ast(out.reborrow())?;
+        self.using_clause.walk_ast(out.reborrow())?;
         self.where_clause.walk_ast(out.reborrow())?;
         self.returning.walk_ast(out.reborrow())?;
         Ok(())
     }
 }
 
-impl<T, U> AsQuery for DeleteStatement<T, U, NoReturningClause>
+impl<T, U, Using> AsQuery for DeleteStatement<T, U, NoReturningClause, Using>
 where
     T: Table,
     T::AllColumns: SelectableExpression<T>,
-    DeleteStatement<T, U, ReturningClause<T::AllColumns>>: Query,
+    DeleteStatement<T, U, ReturningClause<T::AllColumns>, Using>: Query,
 {
     type SqlType = <Self::Query as Query>::SqlType;
-    type Query = DeleteStatement<T, U, ReturningClause<T::AllColumns>>;
+    type Query = DeleteStatement<T, U, ReturningClause<T::AllColumns>, Using>;
 
     fn as_query(self) -> Self::Query {
         self.returning(T::all_columns())
     }
 }
 
-impl<T, U, Ret> Query for DeleteStatement<T, U, ReturningClause<Ret>>
+impl<T, U, Ret, Using> Query for DeleteStatement<T, U, ReturningClause<Ret>, Using>
 where
     T: Table,
     Ret: SelectableExpression<T>,
@@ -196,9 +256,9 @@ where
     type SqlType = Ret::SqlType;
 }
 
-impl<T, U, Ret, Conn> RunQueryDsl<Conn> for DeleteStatement<T, U, Ret> {}
+impl<T, U, Ret, Using, Conn> RunQueryDsl<Conn> for DeleteStatement<T, U, Ret, Using> {}
 
-impl<T, U> DeleteStatement<T, U, NoReturningClause> {
+impl<T, U, Using> DeleteStatement<T, U, NoReturningClause, Using> {
     /// Specify what expression is returned after execution of the `delete`.
     ///
     /// # Examples
@@ -221,15 +281,16 @@ impl<T, U> DeleteStatement<T, U, NoReturningClause> {
     /// # #[cfg(not(feature = "postgres"))]
     /// # fn main() {}
     /// ```
-    pub fn returning<E>(self, returns: E) -> DeleteStatement<T, U, ReturningClause<E>>
+    pub fn returning<E>(self, returns: E) -> DeleteStatement<T, U, ReturningClause<E>, Using>
     where
         E: SelectableExpression<T>,
-        DeleteStatement<T, U, ReturningClause<E>>: Query,
+        DeleteStatement<T, U, ReturningClause<E>, Using>: Query,
     {
         DeleteStatement {
             table: self.table,
             where_clause: self.where_clause,
             returning: ReturningClause(returns),
+            using_clause: self.using_clause,
         }
     }
 }
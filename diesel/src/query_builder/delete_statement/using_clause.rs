@@ -0,0 +1 @@
+simple_clause!(NoUsingClause, UsingClause, " USING ");
@@ -0,0 +1,228 @@
+//! Support for runtime-selected ("dynamic") column projection.
+//!
+//! See [`DynamicSelectClause`] for details.
+
+use backend::Backend;
+use deserialize::{self, FromSql, FromSqlRow, Queryable};
+use expression::{AppearsOnTable, BoxableExpression, Expression, NonAggregate,
+                  SelectableExpression};
+use query_builder::{AstPass, QueryFragment, QueryId};
+use result::QueryResult;
+use row::Row;
+use sql_types::{HasSqlType, Integer, Nullable, Text};
+
+/// The maximum number of columns that can be selected through a single
+/// [`DynamicSelectClause`](struct.DynamicSelectClause.html). Diesel's row
+/// decoding is arity-checked at compile time, so a dynamically-sized
+/// selection is padded up to this many columns under the hood; selecting
+/// more than this many columns at once will panic.
+pub const MAX_DYNAMIC_COLUMNS: usize = 32;
+
+/// The `SqlType` of a [`DynamicSelectClause`](struct.DynamicSelectClause.html).
+///
+/// The actual number and type of the selected columns is only known at
+/// runtime, so this marker carries no information of its own -- it exists
+/// purely so `DynamicSelectClause` can plug into the rest of the query
+/// builder like any other selectable expression.
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct Untyped;
+
+// `Untyped` never appears as a bind parameter -- only as the top-level
+// `SqlType` of a query using `DynamicSelectClause` -- so any metadata works
+// here. We reuse `Integer`'s, since every backend already has to provide it.
+impl<DB> HasSqlType<Untyped> for DB
+where
+    DB: Backend + HasSqlType<Integer>,
+{
+    fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        <DB as HasSqlType<Integer>>::metadata(lookup)
+    }
+}
+
+/// A `SELECT` clause whose columns are chosen at runtime (for example, based
+/// on user input), rather than being fixed at compile time.
+///
+/// Every field added with [`add_field`](#method.add_field) must be castable
+/// to `Text` (see
+/// [`CastExpressionMethods::cast`](../expression/cast/trait.CastExpressionMethods.html#method.cast)),
+/// since the SQL type of a dynamically-chosen column can't be known until
+/// runtime. Running the query returns a `Vec<`[`DynamicRow`](struct.DynamicRow.html)`>`,
+/// where each row holds one `Option<String>` per selected field, in the
+/// order they were added.
+///
+/// At most [`MAX_DYNAMIC_COLUMNS`](constant.MAX_DYNAMIC_COLUMNS.html) fields
+/// can be selected through a single `DynamicSelectClause`.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut select = DynamicSelectClause::new();
+/// for column in fields_requested_by_the_user {
+///     match column {
+///         "id" => select.add_field(users::id.cast::<Text>()),
+///         "name" => select.add_field(users::name.cast::<Text>()),
+///         _ => return Err("unknown column"),
+///     }
+/// }
+/// let rows = users::table.select(select).load::<DynamicRow>(&conn)?;
+/// for row in &rows {
+///     println!("{:?}", row.get(0));
+/// }
+/// ```
+pub struct DynamicSelectClause<'a, QS, DB>
+where
+    DB: Backend,
+{
+    fields: Vec<Box<BoxableExpression<QS, DB, SqlType = Text> + 'a>>,
+}
+
+impl<'a, QS, DB> DynamicSelectClause<'a, QS, DB>
+where
+    DB: Backend,
+{
+    /// Creates a new, empty dynamic select clause.
+    pub fn new() -> Self {
+        DynamicSelectClause { fields: Vec::new() }
+    }
+
+    /// Adds a field to this select clause.
+    ///
+    /// Panics if this would select more than
+    /// [`MAX_DYNAMIC_COLUMNS`](constant.MAX_DYNAMIC_COLUMNS.html) fields.
+    pub fn add_field<T>(&mut self, field: T)
+    where
+        T: BoxableExpression<QS, DB, SqlType = Text> + 'a,
+    {
+        assert!(
+            self.fields.len() < MAX_DYNAMIC_COLUMNS,
+            "cannot select more than {} dynamic columns",
+            MAX_DYNAMIC_COLUMNS,
+        );
+        self.fields.push(Box::new(field));
+    }
+}
+
+impl<'a, QS, DB> Default for DynamicSelectClause<'a, QS, DB>
+where
+    DB: Backend,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, QS, DB> Expression for DynamicSelectClause<'a, QS, DB>
+where
+    DB: Backend,
+{
+    type SqlType = Untyped;
+}
+
+impl<'a, QS, DB> SelectableExpression<QS> for DynamicSelectClause<'a, QS, DB>
+where
+    DB: Backend,
+    DynamicSelectClause<'a, QS, DB>: AppearsOnTable<QS>,
+{
+}
+
+impl<'a, QS, DB> AppearsOnTable<QS> for DynamicSelectClause<'a, QS, DB>
+where
+    DB: Backend,
+    DynamicSelectClause<'a, QS, DB>: Expression,
+{
+}
+
+impl<'a, QS, DB> NonAggregate for DynamicSelectClause<'a, QS, DB> where DB: Backend {}
+
+impl<'a, QS, DB> QueryId for DynamicSelectClause<'a, QS, DB>
+where
+    DB: Backend,
+{
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<'a, QS, DB> QueryFragment<DB> for DynamicSelectClause<'a, QS, DB>
+where
+    DB: Backend,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.push_sql(&self.fields.len().to_string());
+        for field in &self.fields {
+            out.push_sql(", ");
+            field.walk_ast(out.reborrow())?;
+        }
+        for _ in self.fields.len()..MAX_DYNAMIC_COLUMNS {
+            out.push_sql(", NULL");
+        }
+        Ok(())
+    }
+}
+
+/// A single row returned by a query using a
+/// [`DynamicSelectClause`](struct.DynamicSelectClause.html).
+///
+/// Holds one `Option<String>` per field that was added to the select clause,
+/// in the order they were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicRow {
+    values: Vec<Option<String>>,
+}
+
+impl DynamicRow {
+    /// Returns the value of the field at `index`, or `None` if that field is
+    /// `NULL`.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.values[index].as_ref().map(|s| s.as_str())
+    }
+
+    /// The number of fields selected for this row.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if this row has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Consumes this row, returning its values.
+    pub fn into_values(self) -> Vec<Option<String>> {
+        self.values
+    }
+}
+
+impl<DB> Queryable<Untyped, DB> for DynamicRow
+where
+    DB: Backend,
+    DynamicRow: FromSqlRow<Untyped, DB>,
+{
+    type Row = Self;
+
+    fn build(row: Self::Row) -> Self {
+        row
+    }
+}
+
+impl<DB> FromSqlRow<Untyped, DB> for DynamicRow
+where
+    DB: Backend,
+    i32: FromSql<Integer, DB>,
+    Option<String>: FromSql<Nullable<Text>, DB>,
+{
+    const FIELDS_NEEDED: usize = 1 + MAX_DYNAMIC_COLUMNS;
+
+    fn build_from_row<T: Row<DB>>(row: &mut T) -> deserialize::Result<Self> {
+        let count = <i32 as FromSql<Integer, DB>>::from_sql(row.take())? as usize;
+        let mut values = Vec::with_capacity(count);
+        for _ in 0..count {
+            let value = <Option<String> as FromSql<Nullable<Text>, DB>>::from_sql(row.take())?;
+            values.push(value);
+        }
+        row.advance(MAX_DYNAMIC_COLUMNS - count);
+        Ok(DynamicRow { values })
+    }
+}
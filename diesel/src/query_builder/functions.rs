@@ -1,7 +1,11 @@
+use super::create_index::{CreateIndex, DropIndex};
 use super::delete_statement::DeleteStatement;
 use super::insert_statement::{Insert, InsertOrIgnore, Replace};
+use super::truncate_statement::TruncateStatement;
+use super::with_query::WithQuery;
 use super::{IncompleteInsertStatement, IntoUpdateTarget, SelectStatement, SqlQuery,
             UpdateStatement};
+use query_source::Table;
 use dsl::Select;
 use expression::Expression;
 use query_dsl::methods::SelectDsl;
@@ -463,3 +467,98 @@ pub fn replace_into<T>(target: T) -> IncompleteInsertStatement<T, Replace> {
 pub fn sql_query<T: Into<String>>(query: T) -> SqlQuery {
     SqlQuery::new(query.into())
 }
+
+/// Begins building a `CREATE INDEX` statement.
+///
+/// Call [`.on`] to specify the table, then [`.columns`] to specify the column (or tuple of
+/// columns) to index. [`.unique`] and [`.where_`] (for a partial index, supported by Pg and
+/// SQLite) can be chained in afterwards.
+///
+/// This is intended for use in migrations written as Rust code, so they don't need to
+/// hand-assemble DDL strings.
+///
+/// [`.on`]: query_builder/struct.CreateIndex.html#method.on
+/// [`.columns`]: query_builder/struct.CreateIndex.html#method.columns
+/// [`.unique`]: query_builder/struct.CreateIndex.html#method.unique
+/// [`.where_`]: query_builder/struct.CreateIndex.html#method.where_
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../doctest_setup.rs");
+/// #
+/// # fn main() {
+/// #     use schema::users::dsl::*;
+/// #     let connection = establish_connection();
+/// diesel::create_index("users_name_index")
+///     .on(users)
+///     .columns(name)
+///     .unique()
+///     .execute(&connection)
+///     .unwrap();
+/// # }
+/// ```
+pub fn create_index(index_name: &str) -> CreateIndex<(), (), ()> {
+    CreateIndex::new(index_name)
+}
+
+/// Begins building a `DROP INDEX` statement. See [`create_index`](fn.create_index.html).
+pub fn drop_index(index_name: &str) -> DropIndex {
+    DropIndex::new(index_name)
+}
+
+/// Attaches a `WITH` common table expression to `query`, binding the result
+/// of `cte` to `alias`.
+///
+/// Each call attaches one CTE and emits its own `WITH` keyword. Passing a
+/// `with(...)` as the `query` of another call renders
+/// `WITH a AS (...) WITH b AS (...) ...`, which is not valid SQL, so a
+/// statement built this way carries a single CTE.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../doctest_setup.rs");
+/// #
+/// # fn main() {
+/// #     use schema::users::dsl::*;
+/// let young_users_cte = users.filter(id.lt(2));
+/// let query = diesel::with("young_users", young_users_cte, users.select(name));
+/// # let _ = query;
+/// # }
+/// ```
+pub fn with<Cte, Query>(alias: &'static str, cte: Cte, query: Query) -> WithQuery<Cte, Query> {
+    WithQuery::new(alias, cte, query)
+}
+
+/// Creates a `TRUNCATE TABLE` statement, or its closest portable equivalent.
+///
+/// On Pg and MySQL, `.execute` runs a real `TRUNCATE TABLE` (call [`.cascade`] beforehand for
+/// `TRUNCATE TABLE ... CASCADE`, which is only meaningful on Pg). SQLite has no `TRUNCATE`, so
+/// there `.execute` instead runs `DELETE FROM` and resets the table's `AUTOINCREMENT` counter in
+/// `sqlite_sequence`, so a freshly truncated table's ids start over from `1`. On Pg the emitted
+/// `TRUNCATE TABLE` has no `RESTART IDENTITY`, so sequences keep their current values there.
+///
+/// This is intended for test teardown and batch reset code that needs to run against more than
+/// one backend without hand-picking the right statement itself.
+///
+/// [`.cascade`]: query_builder/struct.TruncateStatement.html#method.cascade
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../doctest_setup.rs");
+/// #
+/// # fn main() {
+/// #     use schema::users::dsl::*;
+/// #     let connection = establish_connection();
+/// diesel::truncate(users).execute(&connection).unwrap();
+/// assert_eq!(Ok(0), users.count().first(&connection));
+/// # }
+/// ```
+pub fn truncate<T: Table>(table: T) -> TruncateStatement<T> {
+    TruncateStatement::new(table)
+}
@@ -0,0 +1,265 @@
+//! Build an `INSERT` statement for a table from a `serde_json::Value` object
+//! at runtime, validating its keys against a fixed set of known columns.
+//!
+//! See [`JsonInsert`] for details.
+
+extern crate serde_json;
+
+use std::fmt;
+
+use backend::Backend;
+use query_builder::{AstPass, QueryFragment, QueryId};
+use result::{Error, QueryResult};
+use serialize::ToSql;
+use sql_types::{BigInt, Bool, Double, HasSqlType, Nullable, Text};
+
+/// The SQL type of a single column accepted by [`JsonInsert`].
+///
+/// Only the handful of scalar types a JSON value can unambiguously represent
+/// are supported here -- for anything else (dates, UUIDs, enums, and so on),
+/// write the `INSERT` with a `#[derive(Insertable)]` struct instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonColumnType {
+    /// A JSON string, bound as `Text`.
+    Text,
+    /// A JSON integer, widened to `i64` and bound as `BigInt`.
+    BigInt,
+    /// A JSON number, bound as `Double`.
+    Double,
+    /// A JSON boolean, bound as `Bool`.
+    Bool,
+}
+
+/// One column that [`JsonInsert`] is allowed to write to.
+#[derive(Debug, Clone, Copy)]
+pub struct JsonColumn {
+    name: &'static str,
+    ty: JsonColumnType,
+    nullable: bool,
+}
+
+impl JsonColumn {
+    /// Declares a `NOT NULL` column.
+    pub fn new(name: &'static str, ty: JsonColumnType) -> Self {
+        JsonColumn {
+            name,
+            ty,
+            nullable: false,
+        }
+    }
+
+    /// Declares a nullable column. A JSON `null` binds SQL `NULL`.
+    pub fn nullable(name: &'static str, ty: JsonColumnType) -> Self {
+        JsonColumn {
+            name,
+            ty,
+            nullable: true,
+        }
+    }
+}
+
+/// An `INSERT` statement for a single table, built at runtime from a
+/// `serde_json::Value` object rather than from a
+/// [`#[derive(Insertable)]`](../../derive.Insertable.html) struct.
+///
+/// Each key in the JSON object is validated against `columns`: an unknown
+/// key, or a value that can't be coerced to its column's declared
+/// [`JsonColumnType`], is rejected with a
+/// [`QueryBuilderError`](../../result/enum.Error.html#variant.QueryBuilderError)
+/// before any SQL is sent to the database. Keys not present in the JSON
+/// object are simply omitted from the `INSERT`, leaving them to the column's
+/// default.
+///
+/// This is meant for generic import endpoints and fixtures, where the set of
+/// columns being written isn't known until runtime; application code that
+/// knows its columns at compile time should prefer a
+/// `#[derive(Insertable)]` struct, which diesel can check against the schema.
+///
+/// # Example
+///
+/// ```ignore
+/// let columns = [
+///     JsonColumn::new("name", JsonColumnType::Text),
+///     JsonColumn::nullable("hair_color", JsonColumnType::Text),
+/// ];
+/// let insert = JsonInsert::new("users", &columns, &json_value)?;
+/// insert.execute(&connection)?;
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct JsonInsert<DB> {
+    table_name: String,
+    column_names: Vec<&'static str>,
+    values: Vec<Box<QueryFragment<DB>>>,
+}
+
+impl<DB> JsonInsert<DB>
+where
+    DB: Backend + HasSqlType<Text> + HasSqlType<BigInt> + HasSqlType<Double> + HasSqlType<Bool>,
+{
+    /// Builds an insert for `table_name`, writing whichever of `columns` are
+    /// present as keys of the JSON object `value`.
+    ///
+    /// Returns an error if `value` isn't a JSON object, if it has a key that
+    /// isn't in `columns`, or if a present value can't be coerced to its
+    /// column's declared type.
+    pub fn new(
+        table_name: &str,
+        columns: &[JsonColumn],
+        value: &self::serde_json::Value,
+    ) -> QueryResult<Self> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| Error::QueryBuilderError("expected a JSON object".into()))?;
+
+        for key in object.keys() {
+            if !columns.iter().any(|c| c.name == key.as_str()) {
+                return Err(Error::QueryBuilderError(
+                    format!("`{}` is not a known column", key).into(),
+                ));
+            }
+        }
+
+        let mut column_names = Vec::new();
+        let mut values: Vec<Box<QueryFragment<DB>>> = Vec::new();
+        for column in columns {
+            if let Some(json_value) = object.get(column.name) {
+                column_names.push(column.name);
+                values.push(bind_for_column(column, json_value)?);
+            }
+        }
+
+        Ok(JsonInsert {
+            table_name: table_name.into(),
+            column_names,
+            values,
+        })
+    }
+}
+
+fn bind_for_column<DB>(
+    column: &JsonColumn,
+    value: &self::serde_json::Value,
+) -> QueryResult<Box<QueryFragment<DB>>>
+where
+    DB: Backend + HasSqlType<Text> + HasSqlType<BigInt> + HasSqlType<Double> + HasSqlType<Bool>,
+{
+    if value.is_null() {
+        if !column.nullable {
+            return Err(Error::QueryBuilderError(
+                format!("`{}` is not nullable", column.name).into(),
+            ));
+        }
+        return Ok(match column.ty {
+            JsonColumnType::Text => Box::new(JsonBind::<Option<String>, Nullable<Text>>::new(None)),
+            JsonColumnType::BigInt => Box::new(JsonBind::<Option<i64>, Nullable<BigInt>>::new(None)),
+            JsonColumnType::Double => Box::new(JsonBind::<Option<f64>, Nullable<Double>>::new(None)),
+            JsonColumnType::Bool => Box::new(JsonBind::<Option<bool>, Nullable<Bool>>::new(None)),
+        });
+    }
+
+    let type_error = || {
+        Error::QueryBuilderError(
+            format!("`{}` could not be coerced to {:?}", column.name, column.ty).into(),
+        )
+    };
+
+    Ok(match column.ty {
+        JsonColumnType::Text => {
+            let s = value.as_str().ok_or_else(type_error)?.to_owned();
+            if column.nullable {
+                Box::new(JsonBind::<Option<String>, Nullable<Text>>::new(Some(s)))
+            } else {
+                Box::new(JsonBind::<String, Text>::new(s))
+            }
+        }
+        JsonColumnType::BigInt => {
+            let n = value.as_i64().ok_or_else(type_error)?;
+            if column.nullable {
+                Box::new(JsonBind::<Option<i64>, Nullable<BigInt>>::new(Some(n)))
+            } else {
+                Box::new(JsonBind::<i64, BigInt>::new(n))
+            }
+        }
+        JsonColumnType::Double => {
+            let n = value.as_f64().ok_or_else(type_error)?;
+            if column.nullable {
+                Box::new(JsonBind::<Option<f64>, Nullable<Double>>::new(Some(n)))
+            } else {
+                Box::new(JsonBind::<f64, Double>::new(n))
+            }
+        }
+        JsonColumnType::Bool => {
+            let b = value.as_bool().ok_or_else(type_error)?;
+            if column.nullable {
+                Box::new(JsonBind::<Option<bool>, Nullable<Bool>>::new(Some(b)))
+            } else {
+                Box::new(JsonBind::<bool, Bool>::new(b))
+            }
+        }
+    })
+}
+
+/// A single bound value of statically known SQL type, boxed as a
+/// `QueryFragment` so that columns of different SQL types can be collected
+/// into one `Vec` (see [`DynamicSelectClause`](../dynamic_select/struct.DynamicSelectClause.html)
+/// for the same trick applied to selected columns).
+struct JsonBind<T, ST> {
+    value: T,
+    _marker: ::std::marker::PhantomData<ST>,
+}
+
+impl<T, ST> JsonBind<T, ST> {
+    fn new(value: T) -> Self {
+        JsonBind {
+            value,
+            _marker: ::std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, ST, DB> QueryFragment<DB> for JsonBind<T, ST>
+where
+    DB: Backend + HasSqlType<ST>,
+    T: ToSql<ST, DB> + fmt::Debug,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.push_bind_param::<ST, T>(&self.value)
+    }
+}
+
+impl<DB> QueryId for JsonInsert<DB> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<DB> QueryFragment<DB> for JsonInsert<DB>
+where
+    DB: Backend,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.push_sql("INSERT INTO ");
+        out.push_identifier(&self.table_name)?;
+        out.push_sql(" (");
+        for (i, name) in self.column_names.iter().enumerate() {
+            if i != 0 {
+                out.push_sql(", ");
+            }
+            out.push_identifier(name)?;
+        }
+        out.push_sql(") VALUES (");
+        for (i, value) in self.values.iter().enumerate() {
+            if i != 0 {
+                out.push_sql(", ");
+            }
+            value.walk_ast(out.reborrow())?;
+        }
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Conn> ::query_dsl::RunQueryDsl<Conn> for JsonInsert<Conn::Backend> where
+    Conn: ::connection::Connection
+{
+}
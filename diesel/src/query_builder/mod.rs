@@ -11,13 +11,19 @@ mod clause_macro;
 
 mod ast_pass;
 pub mod bind_collector;
+#[doc(hidden)]
+pub mod combination_clause;
+mod create_index;
 mod debug_query;
 mod delete_statement;
 mod distinct_clause;
+pub mod dynamic_select;
 #[doc(hidden)]
 pub mod functions;
 mod group_by_clause;
 mod insert_statement;
+#[cfg(feature = "serde_json")]
+pub mod json_insert;
 mod limit_clause;
 pub(crate) mod locking_clause;
 #[doc(hidden)]
@@ -28,11 +34,14 @@ mod returning_clause;
 mod select_clause;
 mod select_statement;
 mod sql_query;
+mod truncate_statement;
 mod update_statement;
 mod where_clause;
+mod with_query;
 
 pub use self::ast_pass::AstPass;
 pub use self::bind_collector::BindCollector;
+pub use self::create_index::{CreateIndex, DropIndex};
 pub use self::debug_query::DebugQuery;
 pub use self::delete_statement::{BoxedDeleteStatement, DeleteStatement};
 #[doc(inline)]
@@ -42,12 +51,19 @@ pub use self::query_id::QueryId;
 #[doc(hidden)]
 pub use self::select_statement::{BoxedSelectStatement, SelectStatement};
 pub use self::sql_query::SqlQuery;
+pub use self::truncate_statement::TruncateStatement;
 #[cfg(feature = "with-deprecated")]
 #[allow(deprecated)]
 pub use self::update_statement::IncompleteUpdateStatement;
 #[doc(inline)]
 pub use self::update_statement::{AsChangeset, BoxedUpdateStatement, IntoUpdateTarget,
                                  UpdateStatement, UpdateTarget};
+#[doc(inline)]
+pub use self::with_query::WithQuery;
+#[doc(inline)]
+pub use self::combination_clause::CombinationClause;
+#[doc(inline)]
+pub use self::dynamic_select::{DynamicRow, DynamicSelectClause};
 
 pub(crate) use self::insert_statement::ColumnList;
 
@@ -307,6 +323,17 @@ impl<T: Query> AsQuery for T {
 /// # }
 /// # }
 /// ```
+///
+/// Bind values are always shown separately (as in the examples above),
+/// rather than inlined into the SQL text. `ToSql` only knows how to write a
+/// value in the wire format the backend expects for a bind parameter, not as
+/// a quoted SQL literal, so there's no generic, injection-safe way to splice
+/// them into the query string. If you're on SQLite and want a single string
+/// with bind values substituted in place (for example, to paste into a SQL
+/// prompt), use [`SqliteConnection::expanded_sql`], which asks SQLite itself
+/// to do the substitution.
+///
+/// [`SqliteConnection::expanded_sql`]: ../sqlite/struct.SqliteConnection.html#method.expanded_sql
 pub fn debug_query<DB, T>(query: &T) -> DebugQuery<T, DB> {
     DebugQuery::new(query)
 }
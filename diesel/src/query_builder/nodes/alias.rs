@@ -0,0 +1,94 @@
+use backend::Backend;
+use expression::{AppearsOnTable, Expression, NonAggregate, SelectableExpression};
+use query_builder::{AstPass, QueryFragment, QueryId};
+use result::QueryResult;
+use std::marker::PhantomData;
+
+/// A derived table, produced by calling
+/// [`.as_table(alias)`](../../query_dsl/trait.AsTableDsl.html#method.as_table)
+/// on a select statement.
+///
+/// The subquery is wrapped in parentheses and given a name, so it can be
+/// joined against with raw SQL, or embedded in another query's `FROM`
+/// clause via [`sql_query`](../../fn.sql_query.html). Columns of the
+/// derived table are referenced with
+/// [`.column()`](#method.column), which behaves like
+/// [`sql`](../../dsl/fn.sql.html) in that Diesel cannot verify the SQL type
+/// you provide is correct.
+#[derive(Debug, Clone, Copy)]
+pub struct Alias<Sub> {
+    subquery: Sub,
+    name: &'static str,
+}
+
+// `name` changes the rendered SQL without changing the type.
+impl<Sub> QueryId for Alias<Sub> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<Sub> Alias<Sub> {
+    pub(crate) fn new(subquery: Sub, name: &'static str) -> Self {
+        Alias { subquery, name }
+    }
+
+    /// References a column of this derived table by name.
+    pub fn column<ST>(&self, column_name: &'static str) -> AliasedColumn<ST> {
+        AliasedColumn {
+            alias_name: self.name,
+            column_name,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<DB, Sub> QueryFragment<DB> for Alias<Sub>
+where
+    DB: Backend,
+    Sub: QueryFragment<DB>,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.push_sql("(");
+        self.subquery.walk_ast(out.reborrow())?;
+        out.push_sql(") AS ");
+        out.push_identifier(self.name)
+    }
+}
+
+/// A typed reference to a column of an [`Alias`](struct.Alias.html)ed
+/// derived table, e.g. `t.some_column`.
+#[derive(Debug, Clone, Copy)]
+pub struct AliasedColumn<ST> {
+    alias_name: &'static str,
+    column_name: &'static str,
+    _marker: PhantomData<ST>,
+}
+
+// The names change the rendered SQL without changing the type.
+impl<ST> QueryId for AliasedColumn<ST> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<ST> Expression for AliasedColumn<ST> {
+    type SqlType = ST;
+}
+
+impl<ST, QS> SelectableExpression<QS> for AliasedColumn<ST> {}
+
+impl<ST, QS> AppearsOnTable<QS> for AliasedColumn<ST> {}
+
+impl<ST> NonAggregate for AliasedColumn<ST> {}
+
+impl<ST, DB> QueryFragment<DB> for AliasedColumn<ST>
+where
+    DB: Backend,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.push_identifier(self.alias_name)?;
+        out.push_sql(".");
+        out.push_identifier(self.column_name)
+    }
+}
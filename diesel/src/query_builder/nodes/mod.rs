@@ -2,6 +2,10 @@ use backend::Backend;
 use query_builder::*;
 use result::QueryResult;
 
+mod alias;
+
+pub use self::alias::{Alias, AliasedColumn};
+
 #[derive(Debug, Copy, Clone)]
 pub struct Identifier<'a>(pub &'a str);
 
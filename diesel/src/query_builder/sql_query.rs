@@ -5,7 +5,7 @@ use connection::Connection;
 use deserialize::QueryableByName;
 use query_builder::{AstPass, QueryFragment, QueryId};
 use query_dsl::{LoadQuery, RunQueryDsl};
-use result::QueryResult;
+use result::{Error, QueryResult};
 use serialize::ToSql;
 use sql_types::HasSqlType;
 
@@ -76,6 +76,60 @@ impl SqlQuery {
     pub fn bind<ST, Value>(self, value: Value) -> UncheckedBind<Self, Value, ST> {
         UncheckedBind::new(self, value)
     }
+
+    /// Bind a value for use with a named (`:name`) placeholder in this SQL query.
+    ///
+    /// Unlike [`bind`](#method.bind), which binds values positionally, this
+    /// resolves each `:name` occurring in the query text to the value bound
+    /// under that name, regardless of the order in which they were bound.
+    /// Executing the query will return a
+    /// [`QueryBuilderError`](../result/enum.Error.html#variant.QueryBuilderError)
+    /// if the query text contains a `:name` placeholder that was never
+    /// bound.
+    ///
+    /// As with `bind`, Diesel cannot validate that `value` is of the correct
+    /// type for `:name`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../doctest_setup.rs");
+    /// #
+    /// # use schema::users;
+    /// #
+    /// # #[derive(QueryableByName, Debug, PartialEq)]
+    /// # #[table_name="users"]
+    /// # struct User {
+    /// #     id: i32,
+    /// #     name: String,
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     use diesel::sql_query;
+    /// #     use diesel::sql_types::{Integer, Text};
+    /// #
+    /// #     let connection = establish_connection();
+    /// #     diesel::insert_into(users::table)
+    /// #         .values(users::name.eq("Jim"))
+    /// #         .execute(&connection).unwrap();
+    /// let users = sql_query("SELECT * FROM users WHERE id > :min_id AND name <> :excluded_name")
+    ///     .bind_named::<Text, _>("excluded_name", "Tess")
+    ///     .bind_named::<Integer, _>("min_id", 1)
+    ///     .get_results(&connection);
+    /// let expected_users = vec![
+    ///     User { id: 3, name: "Jim".into() },
+    /// ];
+    /// assert_eq!(Ok(expected_users), users);
+    /// # }
+    /// ```
+    pub fn bind_named<ST, Value>(
+        self,
+        name: &'static str,
+        value: Value,
+    ) -> SqlQueryWithNamedBinds<(NamedBind<Value, ST>, ())> {
+        SqlQueryWithNamedBinds::new(self.query, (NamedBind::new(name, value), ()))
+    }
 }
 
 impl<DB> QueryFragment<DB> for SqlQuery
@@ -164,3 +218,136 @@ where
 }
 
 impl<Conn, Query, Value, ST> RunQueryDsl<Conn> for UncheckedBind<Query, Value, ST> {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NamedBind<Value, ST> {
+    name: &'static str,
+    value: Value,
+    _marker: PhantomData<ST>,
+}
+
+impl<Value, ST> NamedBind<Value, ST> {
+    fn new(name: &'static str, value: Value) -> Self {
+        NamedBind {
+            name,
+            value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Looks up the value bound under a given `:name`, and pushes it as a bind
+/// parameter if found. Implemented for the heterogeneous cons-list of
+/// [`NamedBind`]s built up by chained calls to
+/// [`bind_named`](struct.SqlQuery.html#method.bind_named).
+pub trait FindNamedBind<DB: Backend> {
+    fn push_named_bind(&self, name: &str, out: AstPass<DB>) -> QueryResult<bool>;
+}
+
+impl<DB: Backend> FindNamedBind<DB> for () {
+    fn push_named_bind(&self, _name: &str, _out: AstPass<DB>) -> QueryResult<bool> {
+        Ok(false)
+    }
+}
+
+impl<DB, Value, ST, Rest> FindNamedBind<DB> for (NamedBind<Value, ST>, Rest)
+where
+    DB: Backend + HasSqlType<ST>,
+    Value: ToSql<ST, DB>,
+    Rest: FindNamedBind<DB>,
+{
+    fn push_named_bind(&self, name: &str, mut out: AstPass<DB>) -> QueryResult<bool> {
+        if self.0.name == name {
+            out.push_bind_param::<ST, Value>(&self.0.value)?;
+            Ok(true)
+        } else {
+            self.1.push_named_bind(name, out.reborrow())
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[must_use = "Queries are only executed when calling `load`, `get_result` or similar."]
+/// The return value of [`SqlQuery::bind_named`](struct.SqlQuery.html#method.bind_named).
+pub struct SqlQueryWithNamedBinds<Binds> {
+    query: String,
+    binds: Binds,
+}
+
+impl<Binds> SqlQueryWithNamedBinds<Binds> {
+    fn new(query: String, binds: Binds) -> Self {
+        SqlQueryWithNamedBinds { query, binds }
+    }
+
+    /// See [`SqlQuery::bind_named`](struct.SqlQuery.html#method.bind_named).
+    pub fn bind_named<ST, Value>(
+        self,
+        name: &'static str,
+        value: Value,
+    ) -> SqlQueryWithNamedBinds<(NamedBind<Value, ST>, Binds)> {
+        SqlQueryWithNamedBinds::new(self.query, (NamedBind::new(name, value), self.binds))
+    }
+}
+
+impl<DB, Binds> QueryFragment<DB> for SqlQueryWithNamedBinds<Binds>
+where
+    DB: Backend,
+    Binds: FindNamedBind<DB>,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.unsafe_to_cache_prepared();
+
+        let mut rest = &self.query[..];
+        while let Some(colon_pos) = rest.find(':') {
+            let (before, from_colon) = rest.split_at(colon_pos);
+            out.push_sql(before);
+            let after_colon = &from_colon[1..];
+
+            // `::` is the Postgres cast operator, not a named placeholder.
+            if after_colon.starts_with(':') {
+                out.push_sql("::");
+                rest = &after_colon[1..];
+                continue;
+            }
+
+            let name_len = after_colon
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or_else(|| after_colon.len());
+            if name_len == 0 {
+                out.push_sql(":");
+                rest = after_colon;
+                continue;
+            }
+
+            let name = &after_colon[..name_len];
+            if !self.binds.push_named_bind(name, out.reborrow())? {
+                return Err(Error::QueryBuilderError(
+                    format!("no value was bound for named placeholder `:{}`", name).into(),
+                ));
+            }
+            rest = &after_colon[name_len..];
+        }
+        out.push_sql(rest);
+
+        Ok(())
+    }
+}
+
+impl<Binds> QueryId for SqlQueryWithNamedBinds<Binds> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<Conn, Binds, T> LoadQuery<Conn, T> for SqlQueryWithNamedBinds<Binds>
+where
+    Conn: Connection,
+    T: QueryableByName<Conn::Backend>,
+    Self: QueryFragment<Conn::Backend> + QueryId,
+{
+    fn internal_load(self, conn: &Conn) -> QueryResult<Vec<T>> {
+        conn.query_by_name(&self)
+    }
+}
+
+impl<Conn, Binds> RunQueryDsl<Conn> for SqlQueryWithNamedBinds<Binds> {}
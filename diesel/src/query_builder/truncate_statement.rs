@@ -0,0 +1,115 @@
+use backend::Backend;
+use connection::Connection;
+#[cfg(feature = "mysql")]
+use mysql::Mysql;
+#[cfg(feature = "postgres")]
+use pg::Pg;
+use query_builder::{AstPass, QueryFragment, QueryId};
+#[cfg(feature = "sqlite")]
+use query_builder::QueryBuilder;
+#[cfg(feature = "sqlite")]
+use query_dsl::methods::ExecuteDsl;
+use query_dsl::RunQueryDsl;
+use query_source::Table;
+use result::QueryResult;
+#[cfg(feature = "sqlite")]
+use sqlite::{Sqlite, SqliteConnection, SqliteQueryBuilder};
+
+/// A `TRUNCATE TABLE` statement, or its closest portable equivalent, as returned by
+/// [`truncate`](../fn.truncate.html).
+///
+/// On Pg and MySQL, `.execute` runs a real `TRUNCATE TABLE`. SQLite has no `TRUNCATE`, so there
+/// `.execute` instead runs `DELETE FROM` followed by resetting the table's `AUTOINCREMENT`
+/// counter in `sqlite_sequence`.
+#[derive(Debug, Clone, Copy)]
+#[must_use = "Queries are only executed when calling `load`, `get_result` or similar."]
+pub struct TruncateStatement<T> {
+    table: T,
+    cascade: bool,
+}
+
+// `cascade` changes the rendered SQL without changing the type.
+impl<T> QueryId for TruncateStatement<T> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<T, Conn> RunQueryDsl<Conn> for TruncateStatement<T> {}
+
+impl<T> TruncateStatement<T> {
+    pub(crate) fn new(table: T) -> Self {
+        TruncateStatement {
+            table,
+            cascade: false,
+        }
+    }
+
+    /// Adds `CASCADE` to the statement, so that rows referencing this table through a foreign
+    /// key are truncated as well.
+    ///
+    /// Only meaningful on Pg. MySQL has no equivalent, and always disallows truncating a table
+    /// referenced by a foreign key instead. SQLite's `DELETE FROM` has no `CASCADE` either, so
+    /// this flag is ignored there; rows in tables with `ON DELETE CASCADE` foreign keys are
+    /// removed by SQLite itself as a side effect of the `DELETE FROM`.
+    pub fn cascade(mut self) -> Self {
+        self.cascade = true;
+        self
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> QueryFragment<Pg> for TruncateStatement<T>
+where
+    T: Table,
+    T::FromClause: QueryFragment<Pg>,
+{
+    fn walk_ast(&self, mut out: AstPass<Pg>) -> QueryResult<()> {
+        out.push_sql("TRUNCATE TABLE ");
+        self.table.from_clause().walk_ast(out.reborrow())?;
+        if self.cascade {
+            out.push_sql(" CASCADE");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<T> QueryFragment<Mysql> for TruncateStatement<T>
+where
+    T: Table,
+    T::FromClause: QueryFragment<Mysql>,
+{
+    fn walk_ast(&self, mut out: AstPass<Mysql>) -> QueryResult<()> {
+        out.push_sql("TRUNCATE TABLE ");
+        self.table.from_clause().walk_ast(out.reborrow())?;
+        Ok(())
+    }
+}
+
+// SQLite has no `TRUNCATE`, and the closest equivalent takes two separate statements (the
+// `DELETE FROM` itself, plus resetting `sqlite_sequence`), so this backend can't be expressed as
+// a single `QueryFragment`. Instead of going through the `QueryFragment`-based blanket
+// `ExecuteDsl` impl, `execute` is implemented directly for this backend.
+#[cfg(feature = "sqlite")]
+impl<T> ExecuteDsl<SqliteConnection, Sqlite> for TruncateStatement<T>
+where
+    T: Table,
+    T::FromClause: QueryFragment<Sqlite>,
+{
+    fn execute(query: Self, conn: &SqliteConnection) -> QueryResult<usize> {
+        let mut query_builder = SqliteQueryBuilder::new();
+        query.table.from_clause().to_sql(&mut query_builder)?;
+        let quoted_table = query_builder.finish();
+        let raw_table_name = quoted_table[1..quoted_table.len() - 1].replace("``", "`");
+
+        let deleted = conn.execute(&format!("DELETE FROM {}", quoted_table))?;
+        // `sqlite_sequence` only has a row for tables that actually use `AUTOINCREMENT`, so
+        // this is a no-op for tables that don't.
+        conn.execute(&format!(
+            "DELETE FROM sqlite_sequence WHERE name = '{}'",
+            raw_table_name.replace('\'', "''")
+        ))?;
+        Ok(deleted)
+    }
+}
@@ -19,7 +19,11 @@ use result::QueryResult;
 /// By default, any `Option` fields on the struct are skipped if their value is
 /// `None`. If you would like to assign `NULL` to the field instead, you can
 /// annotate your struct with `#[changeset_options(treat_none_as_null =
-/// "true")]`.
+/// "true")]`, or annotate just the field you want this behavior for with
+/// `#[diesel(treat_none_as_null)]`. If a struct needs both behaviors for the
+/// same field depending on the update, use `Option<Option<T>>` instead: the
+/// outer `Option` decides whether the column is touched at all, and `None` on
+/// the inner `Option` assigns `NULL`.
 pub trait AsChangeset {
     /// The table which `Self::Changeset` will be updating
     type Target: QuerySource;
@@ -0,0 +1 @@
+simple_clause!(NoFromClause, FromClause, " FROM ");
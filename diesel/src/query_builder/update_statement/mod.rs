@@ -1,7 +1,9 @@
 pub mod changeset;
+mod from_clause;
 pub mod target;
 
 pub use self::changeset::AsChangeset;
+pub use self::from_clause::{FromClause, NoFromClause};
 pub use self::target::{IntoUpdateTarget, UpdateTarget};
 
 use backend::Backend;
@@ -29,6 +31,7 @@ impl<T, U> UpdateStatement<T, U, SetNotCalled> {
             where_clause: target.where_clause,
             values: SetNotCalled,
             returning: NoReturningClause,
+            from_clause: NoFromClause,
         }
     }
 
@@ -48,6 +51,7 @@ impl<T, U> UpdateStatement<T, U, SetNotCalled> {
             where_clause: self.where_clause,
             values: values.as_changeset(),
             returning: self.returning,
+            from_clause: self.from_clause,
         }
     }
 }
@@ -58,18 +62,19 @@ impl<T, U> UpdateStatement<T, U, SetNotCalled> {
 /// See [`update`](../fn.update.html) for usage examples, or [the update
 /// guide](https://diesel.rs/guides/all-about-updates/) for a more exhaustive
 /// set of examples.
-pub struct UpdateStatement<T, U, V = SetNotCalled, Ret = NoReturningClause> {
+pub struct UpdateStatement<T, U, V = SetNotCalled, Ret = NoReturningClause, From = NoFromClause> {
     table: T,
     where_clause: U,
     values: V,
     returning: Ret,
+    from_clause: From,
 }
 
 /// An `UPDATE` statement with a boxed `WHERE` clause.
-pub type BoxedUpdateStatement<'a, DB, T, V = SetNotCalled, Ret = NoReturningClause> =
-    UpdateStatement<T, BoxedWhereClause<'a, DB>, V, Ret>;
+pub type BoxedUpdateStatement<'a, DB, T, V = SetNotCalled, Ret = NoReturningClause, From = NoFromClause> =
+    UpdateStatement<T, BoxedWhereClause<'a, DB>, V, Ret, From>;
 
-impl<T, U, V, Ret> UpdateStatement<T, U, V, Ret> {
+impl<T, U, V, Ret, From> UpdateStatement<T, U, V, Ret, From> {
     /// Adds the given predicate to the `WHERE` clause of the statement being
     /// constructed.
     ///
@@ -159,12 +164,12 @@ impl<T, U, V, Ret> UpdateStatement<T, U, V, Ret> {
     }
 }
 
-impl<T, U, V, Ret, Predicate> FilterDsl<Predicate> for UpdateStatement<T, U, V, Ret>
+impl<T, U, V, Ret, From, Predicate> FilterDsl<Predicate> for UpdateStatement<T, U, V, Ret, From>
 where
     U: WhereAnd<Predicate>,
     Predicate: AppearsOnTable<T>,
 {
-    type Output = UpdateStatement<T, U::Output, V, Ret>;
+    type Output = UpdateStatement<T, U::Output, V, Ret, From>;
 
     fn filter(self, predicate: Predicate) -> Self::Output {
         UpdateStatement {
@@ -172,15 +177,16 @@ where
             where_clause: self.where_clause.and(predicate),
             values: self.values,
             returning: self.returning,
+            from_clause: self.from_clause,
         }
     }
 }
 
-impl<'a, T, U, V, Ret, DB> BoxedDsl<'a, DB> for UpdateStatement<T, U, V, Ret>
+impl<'a, T, U, V, Ret, From, DB> BoxedDsl<'a, DB> for UpdateStatement<T, U, V, Ret, From>
 where
     U: Into<BoxedWhereClause<'a, DB>>,
 {
-    type Output = BoxedUpdateStatement<'a, DB, T, V, Ret>;
+    type Output = BoxedUpdateStatement<'a, DB, T, V, Ret, From>;
 
     fn internal_into_boxed(self) -> Self::Output {
         UpdateStatement {
@@ -188,11 +194,12 @@ where
             where_clause: self.where_clause.into(),
             values: self.values,
             returning: self.returning,
+            from_clause: self.from_clause,
         }
     }
 }
 
-impl<T, U, V, Ret, DB> QueryFragment<DB> for UpdateStatement<T, U, V, Ret>
+impl<T, U, V, Ret, From, DB> QueryFragment<DB> for UpdateStatement<T, U, V, Ret, From>
 where
     DB: Backend,
     T: Table,
@@ -200,6 +207,7 @@ where
     U: QueryFragment<DB>,
     V: QueryFragment<DB>,
     Ret: QueryFragment<DB>,
+    From: QueryFragment<DB>,
 {
     fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
         if self.values.is_noop()? {
@@ -213,32 +221,33 @@ where
         self.table.from_clause().walk_ast(out.reborrow())?;
         out.push_sql(" SET ");
         self.values.walk_ast(out.reborrow())?;
+        self.from_clause.walk_ast(out.reborrow())?;
         self.where_clause.walk_ast(out.reborrow())?;
         self.returning.walk_ast(out.reborrow())?;
         Ok(())
     }
 }
 
-impl<T, U, V, Ret> QueryId for UpdateStatement<T, U, V, Ret> {
+impl<T, U, V, Ret, From> QueryId for UpdateStatement<T, U, V, Ret, From> {
     type QueryId = ();
 
     const HAS_STATIC_QUERY_ID: bool = false;
 }
 
-impl<T, U, V> AsQuery for UpdateStatement<T, U, V, NoReturningClause>
+impl<T, U, V, From> AsQuery for UpdateStatement<T, U, V, NoReturningClause, From>
 where
     T: Table,
-    UpdateStatement<T, U, V, ReturningClause<T::AllColumns>>: Query,
+    UpdateStatement<T, U, V, ReturningClause<T::AllColumns>, From>: Query,
 {
     type SqlType = <Self::Query as Query>::SqlType;
-    type Query = UpdateStatement<T, U, V, ReturningClause<T::AllColumns>>;
+    type Query = UpdateStatement<T, U, V, ReturningClause<T::AllColumns>, From>;
 
     fn as_query(self) -> Self::Query {
         self.returning(T::all_columns())
     }
 }
 
-impl<T, U, V, Ret> Query for UpdateStatement<T, U, V, ReturningClause<Ret>>
+impl<T, U, V, Ret, From> Query for UpdateStatement<T, U, V, ReturningClause<Ret>, From>
 where
     T: Table,
     Ret: Expression + SelectableExpression<T> + NonAggregate,
@@ -246,9 +255,56 @@ where
     type SqlType = Ret::SqlType;
 }
 
-impl<T, U, V, Ret, Conn> RunQueryDsl<Conn> for UpdateStatement<T, U, V, Ret> {}
+impl<T, U, V, Ret, From, Conn> RunQueryDsl<Conn> for UpdateStatement<T, U, V, Ret, From> {}
+
+impl<T, U, V, From> UpdateStatement<T, U, V, NoReturningClause, From> {
+    /// Adds an additional `FROM` source to the `UPDATE` statement.
+    ///
+    /// This corresponds to `UPDATE ... FROM` as supported by PostgreSQL and
+    /// SQLite (3.33.0 and later), and lets you avoid a correlated subquery
+    /// when updating a table based on data in another one. It is not
+    /// supported on MySQL, which instead uses a multi-table `UPDATE` syntax
+    /// with no `FROM` keyword; statements built with this method are not
+    /// usable on that backend.
+    ///
+    /// Note that this only adds `from` to the generated SQL. Diesel's
+    /// column-level `SelectableExpression`/`AppearsOnTable` checks are not
+    /// yet aware of it, so referencing columns of `from` from `.set()` or
+    /// `.filter()` currently requires a raw SQL fragment (see
+    /// [`sql()`](../dsl/fn.sql.html)) rather than the generated column path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # #[cfg(feature = "postgres")]
+    /// # fn main() {
+    /// #     use diesel::dsl::sql;
+    /// #     use diesel::sql_types::Bool;
+    /// #     use schema::{posts, users};
+    /// #     let connection = establish_connection();
+    /// let updated_rows = diesel::update(posts::table)
+    ///     .set(posts::title.eq("Untitled"))
+    ///     .from(users::table)
+    ///     .filter(sql::<Bool>("posts.user_id = users.id"))
+    ///     .execute(&connection);
+    /// # let _ = updated_rows;
+    /// # }
+    /// # #[cfg(not(feature = "postgres"))]
+    /// # fn main() {}
+    /// ```
+    pub fn from<F>(self, from: F) -> UpdateStatement<T, U, V, NoReturningClause, FromClause<F>> {
+        UpdateStatement {
+            table: self.table,
+            where_clause: self.where_clause,
+            values: self.values,
+            returning: self.returning,
+            from_clause: FromClause(from),
+        }
+    }
 
-impl<T, U, V> UpdateStatement<T, U, V, NoReturningClause> {
     /// Specify what expression is returned after execution of the `update`.
     /// # Examples
     ///
@@ -271,16 +327,17 @@ impl<T, U, V> UpdateStatement<T, U, V, NoReturningClause> {
     /// # #[cfg(not(feature = "postgres"))]
     /// # fn main() {}
     /// ```
-    pub fn returning<E>(self, returns: E) -> UpdateStatement<T, U, V, ReturningClause<E>>
+    pub fn returning<E>(self, returns: E) -> UpdateStatement<T, U, V, ReturningClause<E>, From>
     where
         T: Table,
-        UpdateStatement<T, U, V, ReturningClause<E>>: Query,
+        UpdateStatement<T, U, V, ReturningClause<E>, From>: Query,
     {
         UpdateStatement {
             table: self.table,
             where_clause: self.where_clause,
             values: self.values,
             returning: ReturningClause(returns),
+            from_clause: self.from_clause,
         }
     }
 }
@@ -0,0 +1,56 @@
+use backend::Backend;
+use query_builder::{AstPass, Query, QueryFragment, QueryId};
+use query_dsl::RunQueryDsl;
+use result::QueryResult;
+
+/// Prepends a `WITH` common table expression to `query`, binding the result
+/// of `cte` to `alias`.
+///
+/// This is constructed by [`with`](../fn.with.html). See that function for
+/// usage examples. Each `WithQuery` renders exactly one CTE behind its own
+/// `WITH` keyword; a statement with several CTEs in one `WITH` list cannot be
+/// built with it.
+#[derive(Debug, Clone, Copy)]
+pub struct WithQuery<Cte, Query> {
+    alias: &'static str,
+    cte: Cte,
+    query: Query,
+}
+
+// `alias` changes the rendered SQL without changing the type.
+impl<Cte, Q> QueryId for WithQuery<Cte, Q> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<Cte, Query> WithQuery<Cte, Query> {
+    pub(crate) fn new(alias: &'static str, cte: Cte, query: Query) -> Self {
+        WithQuery { alias, cte, query }
+    }
+}
+
+impl<Cte, Q> Query for WithQuery<Cte, Q>
+where
+    Q: Query,
+{
+    type SqlType = Q::SqlType;
+}
+
+impl<Cte, Q, DB> QueryFragment<DB> for WithQuery<Cte, Q>
+where
+    DB: Backend,
+    Cte: QueryFragment<DB>,
+    Q: QueryFragment<DB>,
+{
+    fn walk_ast(&self, mut out: AstPass<DB>) -> QueryResult<()> {
+        out.push_sql("WITH ");
+        out.push_identifier(self.alias)?;
+        out.push_sql(" AS (");
+        self.cte.walk_ast(out.reborrow())?;
+        out.push_sql(") ");
+        self.query.walk_ast(out.reborrow())
+    }
+}
+
+impl<Cte, Q, Conn> RunQueryDsl<Conn> for WithQuery<Cte, Q> {}
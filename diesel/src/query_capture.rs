@@ -0,0 +1,216 @@
+//! A [`Connection`] wrapper that records every statement it executes, for testing repository
+//! code by asserting on the queries it issued instead of inspecting log output.
+//!
+//! [`Connection`]: ../connection/trait.Connection.html
+
+use std::cell::RefCell;
+
+use backend::{Backend, UsesAnsiSavepointSyntax};
+use connection::{AnsiTransactionManager, Connection, SimpleConnection};
+use deserialize::{Queryable, QueryableByName};
+use query_builder::{debug_query, AsQuery, QueryFragment, QueryId};
+use result::{ConnectionResult, QueryResult};
+use sql_types::HasSqlType;
+
+/// One statement captured by [`QueryCapture`](struct.QueryCapture.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedQuery {
+    /// The rendered SQL, without bind values.
+    pub sql: String,
+    /// The bind values, in the order they appear in `sql`, each rendered with `{:?}`.
+    ///
+    /// Splitting these back out of the query's `Debug` output is best-effort text splitting, not
+    /// a real parser, so treat this as good enough for a `contains`/count-style assertion, not as
+    /// something to compare a bind value against for exact equality.
+    pub binds: Vec<String>,
+    /// Whether this statement went through `execute`/`execute_returning_count`/`batch_execute` (a
+    /// write, from this wrapper's point of view) rather than `query_by_index`/`query_by_name` (a
+    /// read).
+    pub is_write: bool,
+}
+
+/// Wraps `C`, recording every statement it executes.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../doctest_setup.rs");
+/// use diesel::query_capture::QueryCapture;
+///
+/// # fn main() {
+/// #     run_test().unwrap();
+/// # }
+/// #
+/// # fn run_test() -> QueryResult<()> {
+/// #     use schema::users::dsl::*;
+/// let conn = QueryCapture::new(establish_connection());
+/// let _ = users.load::<(i32, String)>(&conn)?;
+///
+/// assert_queries_executed!(conn, 1);
+/// assert!(!conn.queries()[0].is_write);
+/// #     Ok(())
+/// # }
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct QueryCapture<C> {
+    conn: C,
+    queries: RefCell<Vec<CapturedQuery>>,
+}
+
+impl<C: Connection> QueryCapture<C> {
+    /// Wraps `conn`, with nothing captured yet.
+    pub fn new(conn: C) -> Self {
+        QueryCapture {
+            conn,
+            queries: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Every statement captured so far, oldest first.
+    pub fn queries(&self) -> Vec<CapturedQuery> {
+        self.queries.borrow().clone()
+    }
+
+    /// Discards every statement captured so far.
+    pub fn clear(&self) {
+        self.queries.borrow_mut().clear();
+    }
+
+    /// The number of captured statements whose SQL mentions `table_name`.
+    ///
+    /// This is a plain substring match against the rendered SQL (this wrapper doesn't parse it),
+    /// so pick a `table_name` that isn't a substring of another table you're also querying.
+    pub fn count_for_table(&self, table_name: &str) -> usize {
+        self.queries
+            .borrow()
+            .iter()
+            .filter(|q| q.sql.contains(table_name))
+            .count()
+    }
+
+    /// The number of captured statements with `is_write` set.
+    pub fn writes_executed(&self) -> usize {
+        self.queries.borrow().iter().filter(|q| q.is_write).count()
+    }
+
+    fn record(&self, captured: CapturedQuery) {
+        self.queries.borrow_mut().push(captured);
+    }
+
+    fn render<T, DB>(source: &T) -> CapturedQuery
+    where
+        DB: Backend,
+        DB::QueryBuilder: Default,
+        T: QueryFragment<DB>,
+    {
+        let rendered = debug_query::<DB, _>(source).to_string();
+        let mut parts = rendered.splitn(2, " -- binds: ");
+        let sql = parts.next().unwrap_or_default().to_string();
+        let binds = parts
+            .next()
+            .map(|binds| {
+                binds
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(", ")
+                    .filter(|bind| !bind.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        CapturedQuery {
+            sql,
+            binds,
+            is_write: false,
+        }
+    }
+}
+
+impl<C: Connection> SimpleConnection for QueryCapture<C> {
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        self.record(CapturedQuery {
+            sql: query.to_string(),
+            binds: Vec::new(),
+            is_write: true,
+        });
+        self.conn.batch_execute(query)
+    }
+}
+
+impl<C> Connection for QueryCapture<C>
+where
+    C: Connection<TransactionManager = AnsiTransactionManager>,
+    C::Backend: UsesAnsiSavepointSyntax,
+    <C::Backend as Backend>::QueryBuilder: Default,
+{
+    type Backend = C::Backend;
+    type TransactionManager = AnsiTransactionManager;
+
+    fn establish(database_url: &str) -> ConnectionResult<Self> {
+        C::establish(database_url).map(QueryCapture::new)
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.record(CapturedQuery {
+            sql: query.to_string(),
+            binds: Vec::new(),
+            is_write: true,
+        });
+        self.conn.execute(query)
+    }
+
+    fn query_by_index<T, U>(&self, source: T) -> QueryResult<Vec<U>>
+    where
+        T: AsQuery,
+        T::Query: QueryFragment<Self::Backend> + QueryId,
+        Self::Backend: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Self::Backend>,
+    {
+        let query = source.as_query();
+        self.record(Self::render(&query));
+        self.conn.query_by_index(query)
+    }
+
+    fn query_by_name<T, U>(&self, source: &T) -> QueryResult<Vec<U>>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+        U: QueryableByName<Self::Backend>,
+    {
+        self.record(Self::render(source));
+        self.conn.query_by_name(source)
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+    {
+        let mut captured = Self::render(source);
+        captured.is_write = true;
+        self.record(captured);
+        self.conn.execute_returning_count(source)
+    }
+
+    fn transaction_manager(&self) -> &Self::TransactionManager {
+        self.conn.transaction_manager()
+    }
+}
+
+/// Asserts that exactly `$count` statements have been captured on `$capture` (a
+/// [`QueryCapture`](query_capture/struct.QueryCapture.html)) since it was created or last
+/// [`clear`](query_capture/struct.QueryCapture.html#method.clear)ed.
+#[macro_export]
+macro_rules! assert_queries_executed {
+    ($capture:expr, $count:expr) => {
+        let __diesel_captured = $capture.queries();
+        assert_eq!(
+            $count,
+            __diesel_captured.len(),
+            "expected {} quer{} to have been executed, but {} were:\n{:#?}",
+            $count,
+            if $count == 1 { "y" } else { "ies" },
+            __diesel_captured.len(),
+            __diesel_captured,
+        );
+    };
+}
@@ -0,0 +1,15 @@
+use query_builder::nodes::Alias;
+
+/// The `as_table` method
+///
+/// Implemented for every type, and exported from the prelude, so `as_table`
+/// can be called on any query.
+pub trait AsTableDsl: Sized {
+    /// Wraps `self` in parentheses and gives it the name `alias`, so it can
+    /// be used as a derived table (a subquery in `FROM`).
+    fn as_table(self, alias: &'static str) -> Alias<Self> {
+        Alias::new(self, alias)
+    }
+}
+
+impl<T> AsTableDsl for T {}
@@ -0,0 +1,45 @@
+use query_builder::combination_clause::{CombinationClause, CombinationKind};
+use query_builder::Query;
+
+/// Extension trait to combine two queries using a `UNION`, `UNION ALL`,
+/// `INTERSECT`, or `EXCEPT` statement.
+///
+/// Diesel requires that both sides of the combination select the same SQL
+/// type, which is checked at compile time.
+pub trait CombineDsl: Query + Sized {
+    /// Combine two queries using a SQL `UNION`.
+    ///
+    /// Duplicate rows are removed from the result set.
+    fn union<Rhs>(self, rhs: Rhs) -> CombinationClause<CombinationKind, Self, Rhs>
+    where
+        Rhs: Query<SqlType = Self::SqlType>,
+    {
+        CombinationClause::new(CombinationKind::Union, self, rhs)
+    }
+
+    /// Combine two queries using a SQL `UNION ALL`.
+    fn union_all<Rhs>(self, rhs: Rhs) -> CombinationClause<CombinationKind, Self, Rhs>
+    where
+        Rhs: Query<SqlType = Self::SqlType>,
+    {
+        CombinationClause::new(CombinationKind::UnionAll, self, rhs)
+    }
+
+    /// Combine two queries using a SQL `INTERSECT`.
+    fn intersect<Rhs>(self, rhs: Rhs) -> CombinationClause<CombinationKind, Self, Rhs>
+    where
+        Rhs: Query<SqlType = Self::SqlType>,
+    {
+        CombinationClause::new(CombinationKind::Intersect, self, rhs)
+    }
+
+    /// Combine two queries using a SQL `EXCEPT`.
+    fn except<Rhs>(self, rhs: Rhs) -> CombinationClause<CombinationKind, Self, Rhs>
+    where
+        Rhs: Query<SqlType = Self::SqlType>,
+    {
+        CombinationClause::new(CombinationKind::Except, self, rhs)
+    }
+}
+
+impl<T: Query> CombineDsl for T {}
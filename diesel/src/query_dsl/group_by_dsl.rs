@@ -13,6 +13,11 @@ use query_source::Table;
 /// Since Diesel otherwise assumes that you have no `GROUP BY` clause (which
 /// would mean that mixing an aggregate and non aggregate expression in the same
 /// query is an error), you may need to use `sql` for your select clause.
+///
+/// The [`is_aggregate`](../expression/is_aggregate/index.html) module
+/// contains the marker types that a future, fully type-checked version of
+/// this trait will use to reject a `SELECT` clause that mixes grouped and
+/// ungrouped columns at compile time.
 pub trait GroupByDsl<Expr: Expression> {
     /// The type returned by `.group_by`
     type Output: Query;
@@ -22,7 +22,9 @@ use query_builder::locking_clause as lock;
 use query_source::{joins, Table};
 use result::{first_or_not_found, QueryResult};
 
+mod as_table_dsl;
 mod belonging_to_dsl;
+mod combine_dsl;
 #[doc(hidden)]
 pub mod boxed_dsl;
 mod distinct_dsl;
@@ -42,7 +44,9 @@ mod save_changes_dsl;
 pub mod select_dsl;
 mod single_value_dsl;
 
+pub use self::as_table_dsl::AsTableDsl;
 pub use self::belonging_to_dsl::BelongingToDsl;
+pub use self::combine_dsl::CombineDsl;
 #[doc(hidden)]
 pub use self::group_by_dsl::GroupByDsl;
 pub use self::join_dsl::{InternalJoinDsl, JoinOnDsl, JoinWithImplicitOnClause};
@@ -993,6 +997,31 @@ pub trait QueryDsl: Sized {
     /// assert_eq!(Ok(2), users_by_name("Tess").select(users::id).first(&connection));
     /// # }
     /// ```
+    ///
+    /// ### Building a query for more than one backend
+    ///
+    /// A single boxed query is always tied to one concrete backend, since the
+    /// `DB` type parameter is part of `BoxedQuery`/[`BoxedSelectStatement`]
+    /// itself. If a library needs to build the *same* query for whichever
+    /// backend the caller happens to be using, write the query-building
+    /// function generic over `DB: Backend` (plus whatever backend-specific
+    /// traits the expressions it uses require), and let it be monomorphized
+    /// once per backend that's actually compiled in:
+    ///
+    /// ```ignore
+    /// fn users_by_name<'a, DB>(name: &'a str) -> users::BoxedQuery<'a, DB>
+    /// where
+    ///     DB: Backend,
+    /// {
+    ///     users::table.filter(users::name.eq(name)).into_boxed()
+    /// }
+    ///
+    /// // Called with `DB = diesel::pg::Pg` against a `PgConnection`, or with
+    /// // `DB = diesel::sqlite::Sqlite` against a `SqliteConnection`, chosen
+    /// // at the call site (or behind a `cfg` on the connection type in use).
+    /// ```
+    ///
+    /// [`BoxedSelectStatement`]: ../query_builder/struct.BoxedSelectStatement.html
     fn into_boxed<'a, DB>(self) -> IntoBoxed<'a, Self, DB>
     where
         DB: Backend,
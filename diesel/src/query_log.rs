@@ -0,0 +1,125 @@
+//! Pluggable formatters for query instrumentation events.
+//!
+//! Nothing in this crate emits [`QueryLogEvent`]s on its own — timing a query means wrapping
+//! whichever [`Connection`] sends it (for example, from inside
+//! [`HookedConnection`]'s `execute`/`query_by_index` calls) and building one by hand from the
+//! timing, row count, [`StatementCache`] hit/miss, and
+//! [`transaction_depth`](../connection/struct.AnsiTransactionManager.html) available at the call
+//! site. This module just standardizes the shape of that event and how it turns into a single log
+//! line, so every backend and connection wrapper renders the same schema instead of each rolling
+//! its own.
+//!
+//! [`Connection`]: ../connection/trait.Connection.html
+//! [`HookedConnection`]: ../connection_hooks/struct.HookedConnection.html
+//! [`StatementCache`]: ../connection/struct.StatementCache.html
+
+/// One query having been run.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryLogEvent<'a> {
+    /// The SQL text that was sent to the database.
+    pub sql: &'a str,
+    /// How long the database took to run it.
+    pub duration_ms: u64,
+    /// The number of rows returned or affected.
+    pub rows: usize,
+    /// Whether this query reused an already-prepared statement rather than preparing a new one.
+    pub cache_hit: bool,
+    /// The transaction depth the connection was at when the query ran (0 outside of any
+    /// transaction).
+    pub transaction_depth: u32,
+}
+
+/// Renders a [`QueryLogEvent`](struct.QueryLogEvent.html) as a single log line.
+pub trait QueryLogFormatter {
+    /// Renders `event`, with no trailing newline.
+    fn format(&self, event: &QueryLogEvent) -> String;
+}
+
+/// Renders events as single-line JSON objects, suitable for log aggregation.
+///
+/// ```rust
+/// # extern crate diesel;
+/// use diesel::query_log::{JsonFormatter, QueryLogEvent, QueryLogFormatter};
+///
+/// # fn main() {
+/// let event = QueryLogEvent {
+///     sql: "SELECT 1",
+///     duration_ms: 2,
+///     rows: 1,
+///     cache_hit: true,
+///     transaction_depth: 0,
+/// };
+/// assert_eq!(
+///     r#"{"sql":"SELECT 1","duration_ms":2,"rows":1,"cache_hit":true,"transaction_depth":0}"#,
+///     JsonFormatter.format(&event),
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormatter;
+
+impl QueryLogFormatter for JsonFormatter {
+    fn format(&self, event: &QueryLogEvent) -> String {
+        format!(
+            r#"{{"sql":"{}","duration_ms":{},"rows":{},"cache_hit":{},"transaction_depth":{}}}"#,
+            escape_json_string(event.sql),
+            event.duration_ms,
+            event.rows,
+            event.cache_hit,
+            event.transaction_depth,
+        )
+    }
+}
+
+/// Renders events for a human to read during development.
+///
+/// ```rust
+/// # extern crate diesel;
+/// use diesel::query_log::{HumanReadableFormatter, QueryLogEvent, QueryLogFormatter};
+///
+/// # fn main() {
+/// let event = QueryLogEvent {
+///     sql: "SELECT 1",
+///     duration_ms: 2,
+///     rows: 1,
+///     cache_hit: true,
+///     transaction_depth: 0,
+/// };
+/// assert_eq!(
+///     "SELECT 1 -- 2ms, 1 row, cached, tx depth 0",
+///     HumanReadableFormatter.format(&event),
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HumanReadableFormatter;
+
+impl QueryLogFormatter for HumanReadableFormatter {
+    fn format(&self, event: &QueryLogEvent) -> String {
+        format!(
+            "{} -- {}ms, {} row{}, {}, tx depth {}",
+            event.sql,
+            event.duration_ms,
+            event.rows,
+            if event.rows == 1 { "" } else { "s" },
+            if event.cache_hit { "cached" } else { "uncached" },
+            event.transaction_depth,
+        )
+    }
+}
+
+fn escape_json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
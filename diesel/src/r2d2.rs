@@ -13,6 +13,8 @@ pub type PoolError = self::r2d2::Error;
 use std::convert::Into;
 use std::fmt;
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use backend::UsesAnsiSavepointSyntax;
 use connection::{AnsiTransactionManager, SimpleConnection};
@@ -25,10 +27,22 @@ use sql_types::HasSqlType;
 ///
 /// See the [r2d2 documentation] for usage examples.
 ///
+/// `Pool::builder().max_lifetime(...)` already evicts connections past a maximum age, and
+/// `Pool::builder().test_on_check_out(true)` already runs [`is_valid`] on every checkout; both
+/// are r2d2 features, not something this manager needs to reimplement. What this manager adds on
+/// top is [`metrics`] (so broken/created connection counts can be reported) and
+/// [`ConnectionCustomizer`], an adapter for running plain closures as r2d2's
+/// `CustomizeConnection::on_acquire` hook, e.g. to set pragmas or register SQL functions on every
+/// new [`SqliteConnection`].
+///
 /// [r2d2 documentation]: ../../r2d2
+/// [`is_valid`]: #method.is_valid
+/// [`metrics`]: #method.metrics
+/// [`SqliteConnection`]: ../sqlite/struct.SqliteConnection.html
 #[derive(Debug, Clone)]
 pub struct ConnectionManager<T> {
     database_url: String,
+    metrics: ConnectionManagerMetrics,
     _marker: PhantomData<T>,
 }
 
@@ -40,9 +54,92 @@ impl<T> ConnectionManager<T> {
     pub fn new<S: Into<String>>(database_url: S) -> Self {
         ConnectionManager {
             database_url: database_url.into(),
+            metrics: ConnectionManagerMetrics::default(),
             _marker: PhantomData,
         }
     }
+
+    /// Counters tracking how many connections this manager has created and how many were
+    /// evicted for being broken.
+    ///
+    /// The returned handle is cheap to clone, and stays linked to the same counters as the
+    /// manager (and any of its clones) it was obtained from.
+    pub fn metrics(&self) -> ConnectionManagerMetrics {
+        self.metrics.clone()
+    }
+}
+
+/// Counters tracking the lifecycle of connections created by a [`ConnectionManager`].
+///
+/// [`ConnectionManager`]: struct.ConnectionManager.html
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionManagerMetrics {
+    connections_created: Arc<AtomicUsize>,
+    connections_broken: Arc<AtomicUsize>,
+}
+
+impl ConnectionManagerMetrics {
+    /// The number of connections successfully established by the manager these metrics came
+    /// from.
+    pub fn connections_created(&self) -> usize {
+        self.connections_created.load(Ordering::Relaxed)
+    }
+
+    /// The number of connections that failed an `is_valid` check, which makes the pool evict
+    /// them. `has_broken` always returns `false` and is not counted.
+    pub fn connections_broken(&self) -> usize {
+        self.connections_broken.load(Ordering::Relaxed)
+    }
+}
+
+/// Adapts a plain closure to r2d2's `CustomizeConnection::on_acquire`, for simple one-shot
+/// initialization of every new connection (e.g. setting SQLite pragmas, or registering custom SQL
+/// functions on every new [`SqliteConnection`]).
+///
+/// [`SqliteConnection`]: ../sqlite/struct.SqliteConnection.html
+///
+/// ```rust,no_run
+/// # extern crate diesel;
+/// use diesel::r2d2::{ConnectionCustomizer, ConnectionManager, Pool};
+/// use diesel::sqlite::SqliteConnection;
+///
+/// # fn main() {
+/// let manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+/// let pool = Pool::builder()
+///     .connection_customizer(Box::new(ConnectionCustomizer::new(|conn: &mut SqliteConnection| {
+///         conn.execute("PRAGMA foreign_keys = ON").map(|_| ())
+///     })))
+///     .build(manager)
+///     .unwrap();
+/// # let _ = pool;
+/// # }
+/// ```
+pub struct ConnectionCustomizer<F> {
+    on_acquire: F,
+}
+
+impl<F> ConnectionCustomizer<F> {
+    /// Wraps `on_acquire`, which is run once against every connection this manager creates,
+    /// right after it's established.
+    pub fn new(on_acquire: F) -> Self {
+        ConnectionCustomizer { on_acquire }
+    }
+}
+
+impl<F> fmt::Debug for ConnectionCustomizer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConnectionCustomizer").finish()
+    }
+}
+
+impl<F, C> self::r2d2::CustomizeConnection<C, Error> for ConnectionCustomizer<F>
+where
+    C: Connection,
+    F: Fn(&mut C) -> QueryResult<()> + Send + Sync + 'static,
+{
+    fn on_acquire(&self, conn: &mut C) -> Result<(), Error> {
+        (self.on_acquire)(conn).map_err(Error::QueryError)
+    }
 }
 
 /// The error used when managing connections with `r2d2`.
@@ -81,13 +178,21 @@ where
     type Error = Error;
 
     fn connect(&self) -> Result<T, Error> {
-        T::establish(&self.database_url).map_err(Error::ConnectionError)
+        let conn = T::establish(&self.database_url).map_err(Error::ConnectionError)?;
+        self.metrics
+            .connections_created
+            .fetch_add(1, Ordering::Relaxed);
+        Ok(conn)
     }
 
     fn is_valid(&self, conn: &mut T) -> Result<(), Error> {
-        conn.execute("SELECT 1")
-            .map(|_| ())
-            .map_err(Error::QueryError)
+        let result = conn.execute("SELECT 1").map(|_| ()).map_err(Error::QueryError);
+        if result.is_err() {
+            self.metrics
+                .connections_broken
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        result
     }
 
     fn has_broken(&self, _conn: &mut T) -> bool {
@@ -219,4 +324,49 @@ mod tests {
         let query = select("foo".into_sql::<Text>());
         assert_eq!("foo", query.get_result::<String>(&conn).unwrap());
     }
+
+    #[test]
+    fn metrics_tracks_connections_created() {
+        let manager = ConnectionManager::<TestConnection>::new(database_url());
+        let metrics = manager.metrics();
+        assert_eq!(0, metrics.connections_created());
+
+        let pool = Pool::builder().max_size(2).build(manager).unwrap();
+        let conn1 = pool.get().unwrap();
+        let conn2 = pool.get().unwrap();
+
+        assert_eq!(2, metrics.connections_created());
+        assert_eq!(0, metrics.connections_broken());
+
+        drop(conn1);
+        drop(conn2);
+    }
+
+    #[test]
+    fn connection_customizer_runs_on_acquire_for_every_new_connection() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let acquisitions = Arc::new(AtomicUsize::new(0));
+        let acquisitions_in_closure = Arc::clone(&acquisitions);
+
+        let manager = ConnectionManager::<TestConnection>::new(database_url());
+        let pool = Pool::builder()
+            .max_size(2)
+            .connection_customizer(Box::new(ConnectionCustomizer::new(
+                move |_: &mut TestConnection| {
+                    acquisitions_in_closure.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                },
+            )))
+            .build(manager)
+            .unwrap();
+
+        let conn1 = pool.get().unwrap();
+        let conn2 = pool.get().unwrap();
+
+        assert_eq!(2, acquisitions.load(Ordering::SeqCst));
+
+        drop(conn1);
+        drop(conn2);
+    }
 }
@@ -0,0 +1,221 @@
+//! A [`Connection`] wrapper that routes reads to replicas and writes to a primary.
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use backend::UsesAnsiSavepointSyntax;
+use connection::{AnsiTransactionManager, Connection, SimpleConnection, TransactionManager};
+use deserialize::{Queryable, QueryableByName};
+use query_builder::{AsQuery, QueryFragment, QueryId};
+use result::{ConnectionError, ConnectionResult, QueryResult};
+use sql_types::HasSqlType;
+
+/// Wraps a primary connection `W` and a set of replica connections `R`, implementing
+/// [`Connection`] by routing `load`-style calls ([`query_by_index`]/[`query_by_name`]) to a
+/// replica, and everything else (`execute`, `batch_execute`, and anything run while a
+/// transaction is open) to the primary.
+///
+/// Replicas are picked round-robin. Immediately after a write, reads are routed to the primary
+/// instead for `sticky_window` (a "sticky session"), since a replica may not have caught up with
+/// a write that was just made from the same connection yet. This is a heuristic, not a guarantee;
+/// it does not help other connections observe a write promptly, and it does not help this
+/// connection if replication lag exceeds `sticky_window`. The window is also armed by any
+/// `batch_execute` (including the `BEGIN`/`COMMIT`/`ROLLBACK` issued around transactions), not
+/// only by statements that actually changed data.
+///
+/// [`Connection`]: ../connection/trait.Connection.html
+/// [`query_by_index`]: ../connection/trait.Connection.html#tymethod.query_by_index
+/// [`query_by_name`]: ../connection/trait.Connection.html#tymethod.query_by_name
+#[allow(missing_debug_implementations)]
+pub struct ReadWriteSplit<W, R> {
+    primary: W,
+    replicas: Vec<R>,
+    next_replica: Cell<usize>,
+    sticky_window: Duration,
+    last_write: Cell<Option<Instant>>,
+    transaction_manager: AnsiTransactionManager,
+}
+
+impl<W, R> ReadWriteSplit<W, R>
+where
+    W: Connection,
+    R: Connection<Backend = W::Backend>,
+    W::Backend: UsesAnsiSavepointSyntax,
+{
+    /// Wraps `primary` and `replicas`, sticking reads to `primary` for `sticky_window` after
+    /// each write made through this wrapper.
+    ///
+    /// `replicas` may be empty, in which case every call is routed to `primary`.
+    pub fn new(primary: W, replicas: Vec<R>, sticky_window: Duration) -> Self {
+        ReadWriteSplit {
+            primary,
+            replicas,
+            next_replica: Cell::new(0),
+            sticky_window,
+            last_write: Cell::new(None),
+            transaction_manager: AnsiTransactionManager::new(),
+        }
+    }
+
+    fn record_write(&self) {
+        self.last_write.set(Some(Instant::now()));
+    }
+
+    fn is_within_sticky_window(&self) -> bool {
+        match self.last_write.get() {
+            Some(last_write) => last_write.elapsed() < self.sticky_window,
+            None => false,
+        }
+    }
+
+    fn should_read_from_primary(&self) -> bool {
+        self.replicas.is_empty()
+            || <AnsiTransactionManager as TransactionManager<W>>::get_transaction_depth(
+                &self.transaction_manager,
+            ) > 0
+            || self.is_within_sticky_window()
+    }
+
+    fn next_replica(&self) -> &R {
+        let index = self.next_replica.get();
+        self.next_replica.set((index + 1) % self.replicas.len());
+        &self.replicas[index]
+    }
+}
+
+impl<W, R> SimpleConnection for ReadWriteSplit<W, R>
+where
+    W: Connection,
+    R: Connection<Backend = W::Backend>,
+    W::Backend: UsesAnsiSavepointSyntax,
+{
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        let result = self.primary.batch_execute(query);
+        self.record_write();
+        result
+    }
+}
+
+impl<W, R> Connection for ReadWriteSplit<W, R>
+where
+    W: Connection<TransactionManager = AnsiTransactionManager>,
+    R: Connection<Backend = W::Backend>,
+    W::Backend: UsesAnsiSavepointSyntax,
+{
+    type Backend = W::Backend;
+    type TransactionManager = AnsiTransactionManager;
+
+    fn establish(_: &str) -> ConnectionResult<Self> {
+        Err(ConnectionError::BadConnection(String::from(
+            "ReadWriteSplit cannot be established from a single database URL, use ReadWriteSplit::new",
+        )))
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        let result = self.primary.execute(query);
+        self.record_write();
+        result
+    }
+
+    fn query_by_index<T, U>(&self, source: T) -> QueryResult<Vec<U>>
+    where
+        T: AsQuery,
+        T::Query: QueryFragment<Self::Backend> + QueryId,
+        Self::Backend: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Self::Backend>,
+    {
+        if self.should_read_from_primary() {
+            self.primary.query_by_index(source)
+        } else {
+            self.next_replica().query_by_index(source)
+        }
+    }
+
+    fn query_by_name<T, U>(&self, source: &T) -> QueryResult<Vec<U>>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+        U: QueryableByName<Self::Backend>,
+    {
+        if self.should_read_from_primary() {
+            self.primary.query_by_name(source)
+        } else {
+            self.next_replica().query_by_name(source)
+        }
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+    {
+        let result = self.primary.execute_returning_count(source);
+        self.record_write();
+        result
+    }
+
+    fn transaction_manager(&self) -> &Self::TransactionManager {
+        &self.transaction_manager
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use prelude::*;
+    use query_capture::QueryCapture;
+    use sql_types::Integer;
+
+    fn sqlite() -> QueryCapture<::sqlite::SqliteConnection> {
+        QueryCapture::new(::sqlite::SqliteConnection::establish(":memory:").unwrap())
+    }
+
+    fn do_select(conn: &ReadWriteSplit<QueryCapture<::sqlite::SqliteConnection>, QueryCapture<::sqlite::SqliteConnection>>) {
+        ::select(1.into_sql::<Integer>())
+            .get_result::<i32>(conn)
+            .unwrap();
+    }
+
+    #[test]
+    fn reads_round_robin_across_replicas_when_no_write_has_happened() {
+        let conn = ReadWriteSplit::new(sqlite(), vec![sqlite(), sqlite()], Duration::from_secs(0));
+
+        for _ in 0..4 {
+            do_select(&conn);
+        }
+
+        assert_eq!(0, conn.primary.queries().len());
+        assert_eq!(2, conn.replicas[0].queries().len());
+        assert_eq!(2, conn.replicas[1].queries().len());
+    }
+
+    #[test]
+    fn reads_go_to_the_primary_within_the_sticky_window_after_a_write() {
+        let conn = ReadWriteSplit::new(sqlite(), vec![sqlite()], Duration::from_secs(60));
+
+        conn.execute("SELECT 1").unwrap();
+        do_select(&conn);
+
+        assert_eq!(0, conn.replicas[0].queries().len());
+        assert_eq!(2, conn.primary.queries().len());
+    }
+
+    #[test]
+    fn reads_go_to_the_primary_when_there_are_no_replicas() {
+        let conn = ReadWriteSplit::new(sqlite(), Vec::new(), Duration::from_secs(0));
+
+        do_select(&conn);
+
+        assert_eq!(1, conn.primary.queries().len());
+    }
+
+    #[test]
+    fn writes_always_go_to_the_primary() {
+        let conn = ReadWriteSplit::new(sqlite(), vec![sqlite()], Duration::from_secs(0));
+
+        conn.execute("SELECT 1").unwrap();
+
+        assert_eq!(1, conn.primary.writes_executed());
+        assert_eq!(0, conn.replicas[0].writes_executed());
+    }
+}
@@ -1,5 +1,6 @@
 //! Errors, type aliases, and functions related to working with `Result`.
 
+use std::any::Any;
 use std::convert::From;
 use std::error::Error as StdError;
 use std::ffi::NulError;
@@ -73,6 +74,16 @@ pub enum Error {
     /// when a transaction was already open.
     AlreadyInTransaction,
 
+    /// An update guarded by a `#[diesel(version_column)]` field matched zero rows.
+    ///
+    /// This means the row was changed (or deleted) by someone else between when it was loaded
+    /// and when this update was sent, so the update was discarded rather than silently
+    /// overwriting the other change.
+    StaleObject {
+        /// The name of the table the update was sent to.
+        table_name: &'static str,
+    },
+
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -89,6 +100,17 @@ pub enum DatabaseErrorKind {
     UniqueViolation,
     /// A foreign key constraint was violated.
     ForeignKeyViolation,
+    /// A `CHECK` constraint was violated.
+    CheckViolation,
+    /// A `NOT NULL` constraint was violated.
+    NotNullViolation,
+    /// A serializable or repeatable-read transaction was aborted because it could not be
+    /// guaranteed to be equivalent to running the concurrent transactions one at a time.
+    /// Retrying the transaction from the start is the normal recovery.
+    SerializationFailure,
+    /// The database detected a deadlock between this transaction and another one, and aborted
+    /// this one to break it. Retrying the transaction from the start is the normal recovery.
+    DeadlockDetected,
     /// The query could not be sent to the database due to a protocol violation.
     ///
     /// An example of a case where this would occur is if you attempted to send
@@ -133,6 +155,13 @@ pub trait DatabaseErrorInformation {
     /// Currently this method will return `None` for all backends other than
     /// PostgreSQL.
     fn constraint_name(&self) -> Option<&str>;
+
+    /// Returns `self` as an `Any`, so that backend-specific error information
+    /// (e.g. [`SqliteErrorInformation`]) can be recovered from a
+    /// `Box<DatabaseErrorInformation + Send + Sync>` via `downcast_ref`.
+    ///
+    /// [`SqliteErrorInformation`]: ../sqlite/struct.SqliteErrorInformation.html
+    fn as_any(&self) -> &Any;
 }
 
 impl fmt::Debug for DatabaseErrorInformation + Send + Sync {
@@ -161,6 +190,9 @@ impl DatabaseErrorInformation for String {
     fn constraint_name(&self) -> Option<&str> {
         None
     }
+    fn as_any(&self) -> &Any {
+        self
+    }
 }
 
 /// Errors which can occur during [`Connection::establish`]
@@ -245,6 +277,69 @@ impl From<NulError> for Error {
     }
 }
 
+impl Error {
+    /// Whether retrying the same operation stands a chance of succeeding, because the failure
+    /// looks transient rather than caused by the query or data itself: a serialization or
+    /// deadlock failure from a concurrent transaction, or the database being busy/locked by
+    /// another connection.
+    ///
+    /// This lets generic retry middleware decide whether to retry without matching on
+    /// backend-specific error message text itself.
+    pub fn is_retriable(&self) -> bool {
+        match *self {
+            Error::DatabaseError(DatabaseErrorKind::SerializationFailure, _)
+            | Error::DatabaseError(DatabaseErrorKind::DeadlockDetected, _) => true,
+            Error::DatabaseError(_, ref info) => is_retriable_sqlite_error(info.as_any()),
+            _ => false,
+        }
+    }
+
+    /// Whether this error indicates the underlying connection is no longer usable, and should be
+    /// dropped (or replaced, e.g. by a connection pool) rather than reused for another query.
+    pub fn is_connection_broken(&self) -> bool {
+        match *self {
+            Error::DatabaseError(_, ref info) => is_connection_broken_sqlite_error(info.as_any()),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn is_retriable_sqlite_error(info: &Any) -> bool {
+    use sqlite::{SqliteErrorCode, SqliteErrorInformation};
+
+    match info.downcast_ref::<SqliteErrorInformation>() {
+        Some(info) => match info.extended_code() {
+            SqliteErrorCode::Busy | SqliteErrorCode::BusySnapshot | SqliteErrorCode::Locked => true,
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn is_retriable_sqlite_error(_info: &Any) -> bool {
+    false
+}
+
+#[cfg(feature = "sqlite")]
+fn is_connection_broken_sqlite_error(info: &Any) -> bool {
+    use sqlite::{SqliteErrorCode, SqliteErrorInformation};
+
+    match info.downcast_ref::<SqliteErrorInformation>() {
+        Some(info) => match info.extended_code() {
+            SqliteErrorCode::IoErr | SqliteErrorCode::Corrupt => true,
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn is_connection_broken_sqlite_error(_info: &Any) -> bool {
+    false
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -256,6 +351,9 @@ impl Display for Error {
             Error::SerializationError(ref e) => e.fmt(f),
             Error::RollbackTransaction => write!(f, "{}", self.description()),
             Error::AlreadyInTransaction => write!(f, "{}", self.description()),
+            Error::StaleObject { table_name } => {
+                write!(f, "The row in `{}` was modified by another connection", table_name)
+            }
             Error::__Nonexhaustive => unreachable!(),
         }
     }
@@ -274,6 +372,9 @@ impl StdError for Error {
             Error::AlreadyInTransaction => {
                 "Cannot perform this operation while a transaction is open"
             }
+            Error::StaleObject { .. } => {
+                "The row was modified by another connection since it was loaded"
+            }
             Error::__Nonexhaustive => unreachable!(),
         }
     }
@@ -331,6 +432,10 @@ impl PartialEq for Error {
             (&Error::NotFound, &Error::NotFound) => true,
             (&Error::RollbackTransaction, &Error::RollbackTransaction) => true,
             (&Error::AlreadyInTransaction, &Error::AlreadyInTransaction) => true,
+            (
+                &Error::StaleObject { table_name: a },
+                &Error::StaleObject { table_name: b },
+            ) => a == b,
             _ => false,
         }
     }
@@ -362,3 +467,50 @@ impl StdError for UnexpectedNullError {
         "Unexpected null for non-null column"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_retriable_is_true_for_serialization_and_deadlock_failures() {
+        let serialization_failure = Error::DatabaseError(
+            DatabaseErrorKind::SerializationFailure,
+            Box::new(String::from("could not serialize access")),
+        );
+        let deadlock = Error::DatabaseError(
+            DatabaseErrorKind::DeadlockDetected,
+            Box::new(String::from("deadlock detected")),
+        );
+
+        assert!(serialization_failure.is_retriable());
+        assert!(deadlock.is_retriable());
+    }
+
+    #[test]
+    fn is_retriable_is_false_for_errors_that_wont_succeed_on_retry() {
+        let unique_violation = Error::DatabaseError(
+            DatabaseErrorKind::UniqueViolation,
+            Box::new(String::from("duplicate key")),
+        );
+
+        assert!(!unique_violation.is_retriable());
+        assert!(!Error::NotFound.is_retriable());
+    }
+
+    #[test]
+    fn is_connection_broken_is_false_for_non_database_errors() {
+        assert!(!Error::NotFound.is_connection_broken());
+        assert!(!Error::RollbackTransaction.is_connection_broken());
+    }
+
+    #[test]
+    fn is_connection_broken_is_false_for_a_plain_database_error() {
+        let unique_violation = Error::DatabaseError(
+            DatabaseErrorKind::UniqueViolation,
+            Box::new(String::from("duplicate key")),
+        );
+
+        assert!(!unique_violation.is_connection_broken());
+    }
+}
@@ -0,0 +1,206 @@
+//! A [`Connection`] wrapper that transparently retries individual statement executions that fail
+//! with a transient error (see [`Error::is_retriable`]), instead of surfacing the failure to the
+//! caller on the first attempt.
+//!
+//! Only `execute`, `batch_execute`, `query_by_name`, and `execute_returning_count` are retried.
+//! `query_by_index` takes its `source` by value and consumes it building the query to send, with
+//! no `Clone` bound available to rebuild it for a second attempt, so it passes straight through
+//! unretried -- reach it through `.as_query()` and `query_by_name`/`sql_query` instead if you need
+//! a `SELECT` retried.
+//!
+//! Retries are per-statement, not per-transaction: a statement that fails partway through a
+//! multi-statement transaction is retried on its own, not the whole transaction, since this
+//! wrapper has no way to replay statements that already succeeded earlier in the same one.
+//!
+//! [`Connection`]: ../connection/trait.Connection.html
+//! [`Error::is_retriable`]: ../result/enum.Error.html#method.is_retriable
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::thread;
+use std::time::Duration;
+
+use backend::UsesAnsiSavepointSyntax;
+use connection::{AnsiTransactionManager, Connection, SimpleConnection};
+use deserialize::{Queryable, QueryableByName};
+use query_builder::{AsQuery, QueryFragment, QueryId};
+use result::{ConnectionResult, Error, QueryResult};
+use sql_types::HasSqlType;
+
+/// Configures [`RetryingConnection`](struct.RetryingConnection.html)'s retry behavior.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// The number of retries allowed after the first attempt, so `max_retries: 3` means up to 4
+    /// attempts total before the error is returned to the caller. Defaults to `3`.
+    pub max_retries: u32,
+    /// The delay before the first retry. Each subsequent retry doubles the previous delay, plus
+    /// up to 50% random jitter, so connections that failed at the same moment don't all retry in
+    /// lockstep. Defaults to 10ms.
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let base_millis = self.base_delay.as_secs() * 1_000
+            + u64::from(self.base_delay.subsec_nanos()) / 1_000_000;
+        let backoff_millis =
+            base_millis.saturating_mul(1u64 << attempt.saturating_sub(1).min(16));
+        let jitter_millis = backoff_millis / 2;
+        let jitter = if jitter_millis == 0 {
+            0
+        } else {
+            random_u64() % jitter_millis
+        };
+        Duration::from_millis(backoff_millis.saturating_add(jitter))
+    }
+}
+
+/// A fresh, OS-seeded, but non-cryptographic random value, good enough to spread out jittered
+/// retries without pulling in a dedicated RNG dependency.
+fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// Called after a statement fails but before it's retried, for observability -- incrementing a
+/// metric, logging the attempt, and so on.
+pub trait RetryObserver: Send + Sync {
+    /// `attempt` is `1` for the first retry, `2` for the second, and so on. `error` is the
+    /// failure that's about to be retried.
+    fn on_retry(&self, attempt: u32, error: &Error);
+}
+
+impl<F: Fn(u32, &Error) + Send + Sync> RetryObserver for F {
+    fn on_retry(&self, attempt: u32, error: &Error) {
+        self(attempt, error)
+    }
+}
+
+/// Wraps `C`, transparently retrying statement executions that fail with an
+/// [`is_retriable`](../result/enum.Error.html#method.is_retriable) error, following `policy`. See
+/// the [module docs](index.html) for which methods are actually retried.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../doctest_setup.rs");
+/// use diesel::retry::RetryingConnection;
+///
+/// # fn main() {
+/// #     run_test().unwrap();
+/// # }
+/// #
+/// # fn run_test() -> QueryResult<()> {
+/// let conn = RetryingConnection::new(establish_connection());
+/// conn.execute("SELECT 1")?;
+/// #     Ok(())
+/// # }
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct RetryingConnection<C> {
+    conn: C,
+    policy: RetryPolicy,
+    observer: Option<Box<RetryObserver>>,
+}
+
+impl<C: Connection> RetryingConnection<C> {
+    /// Wraps `conn` with the default [`RetryPolicy`](struct.RetryPolicy.html) and no observer.
+    pub fn new(conn: C) -> Self {
+        Self::with_policy(conn, RetryPolicy::default())
+    }
+
+    /// Wraps `conn`, retrying according to `policy`.
+    pub fn with_policy(conn: C, policy: RetryPolicy) -> Self {
+        RetryingConnection {
+            conn,
+            policy,
+            observer: None,
+        }
+    }
+
+    /// Registers `observer` to be called before every retry.
+    pub fn set_observer<O: RetryObserver + 'static>(&mut self, observer: O) {
+        self.observer = Some(Box::new(observer));
+    }
+
+    fn retry<T, F>(&self, mut op: F) -> QueryResult<T>
+    where
+        F: FnMut() -> QueryResult<T>,
+    {
+        let mut attempt = 0;
+        loop {
+            match op() {
+                Ok(value) => return Ok(value),
+                Err(ref e) if attempt < self.policy.max_retries && e.is_retriable() => {
+                    attempt += 1;
+                    if let Some(ref observer) = self.observer {
+                        observer.on_retry(attempt, e);
+                    }
+                    thread::sleep(self.policy.delay_for(attempt));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl<C: Connection> SimpleConnection for RetryingConnection<C> {
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        self.retry(|| self.conn.batch_execute(query))
+    }
+}
+
+impl<C> Connection for RetryingConnection<C>
+where
+    C: Connection<TransactionManager = AnsiTransactionManager>,
+    C::Backend: UsesAnsiSavepointSyntax,
+{
+    type Backend = C::Backend;
+    type TransactionManager = AnsiTransactionManager;
+
+    fn establish(database_url: &str) -> ConnectionResult<Self> {
+        C::establish(database_url).map(RetryingConnection::new)
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.retry(|| self.conn.execute(query))
+    }
+
+    fn query_by_index<T, U>(&self, source: T) -> QueryResult<Vec<U>>
+    where
+        T: AsQuery,
+        T::Query: QueryFragment<Self::Backend> + QueryId,
+        Self::Backend: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Self::Backend>,
+    {
+        self.conn.query_by_index(source)
+    }
+
+    fn query_by_name<T, U>(&self, source: &T) -> QueryResult<Vec<U>>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+        U: QueryableByName<Self::Backend>,
+    {
+        self.retry(|| self.conn.query_by_name(source))
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize>
+    where
+        T: QueryFragment<Self::Backend> + QueryId,
+    {
+        self.retry(|| self.conn.execute_returning_count(source))
+    }
+
+    fn transaction_manager(&self) -> &Self::TransactionManager {
+        self.conn.transaction_manager()
+    }
+}
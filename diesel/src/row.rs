@@ -54,10 +54,43 @@ pub trait NamedRow<DB: Backend> {
             Ok(x) => x,
             Err(e) => return Err(e),
         };
-        let raw_value = self.get_raw_value(idx);
+        self.get_by_index(idx)
+    }
+
+    /// Retrieve and deserialize a single value from the query by column
+    /// index, rather than by name.
+    ///
+    /// As with [`get`](#method.get), `ST` *must* be the exact type of the
+    /// value at that index in the query, and there is no way for the
+    /// compiler to verify that you have provided the correct type.
+    fn get_by_index<ST, T>(&self, index: usize) -> deserialize::Result<T>
+    where
+        T: FromSql<ST, DB>,
+    {
+        let raw_value = self.get_raw_value(index);
         T::from_sql(raw_value)
     }
 
+    /// The number of columns present in this row.
+    fn column_count(&self) -> usize;
+
+    /// The name of the column at `index`, or `None` if this backend can't
+    /// report a name for that column (for example, a computed column with
+    /// no alias).
+    ///
+    /// Panics if `index` is out of bounds.
+    fn column_name(&self, index: usize) -> Option<&str>;
+
+    /// The name of every column present in this row, in order.
+    ///
+    /// Columns this backend can't name (see [`column_name`](#method.column_name))
+    /// are omitted, so this may be shorter than [`column_count`](#method.column_count).
+    fn column_names(&self) -> Vec<&str> {
+        (0..self.column_count())
+            .filter_map(|i| self.column_name(i))
+            .collect()
+    }
+
     #[doc(hidden)]
     fn index_of(&self, column_name: &str) -> Option<usize>;
     #[doc(hidden)]
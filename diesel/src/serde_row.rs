@@ -0,0 +1,103 @@
+//! Support for loading query results into an arbitrary `serde::Deserialize`
+//! type, with columns mapped to struct fields by name via `serde` instead of
+//! through a `#[derive(QueryableByName)]` struct.
+//!
+//! See [`SerdeRow`] for details.
+
+#[macro_use]
+extern crate serde;
+
+use self::serde::de::{self, DeserializeOwned};
+
+use deserialize::{self, QueryableByName};
+use dynamic_value::DynamicValue;
+use row::NamedRow;
+
+/// Wraps any `T: serde::de::DeserializeOwned`, so that it can be loaded from
+/// a [`sql_query`](../fn.sql_query.html) result via
+/// [`RunQueryDsl::load`](../query_dsl/trait.RunQueryDsl.html#method.load),
+/// with columns mapped onto `T`'s fields by name through `serde`.
+///
+/// ```rust,ignore
+/// // Requires the `serde` and `sqlite` features.
+/// use diesel::serde_row::SerdeRow;
+///
+/// #[derive(Deserialize, PartialEq, Debug)]
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// let users = sql_query("SELECT id, name FROM users ORDER BY id")
+///     .load::<SerdeRow<User>>(&connection)?
+///     .into_iter()
+///     .map(|row| row.0)
+///     .collect::<Vec<_>>();
+/// ```
+///
+/// This is currently only implemented for [`Sqlite`](../sqlite/struct.Sqlite.html).
+/// SQLite is the only backend where the storage class of a value is attached
+/// to the value itself, so it's the only backend where columns can be
+/// deserialized without a SQL type known at compile time. On Postgres and
+/// MySQL a raw column value is just untagged bytes (see
+/// [`DynamicValue`](../dynamic_value/enum.DynamicValue.html) for the same
+/// limitation).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerdeRow<T>(pub T);
+
+#[cfg(feature = "sqlite")]
+impl<T> QueryableByName<::sqlite::Sqlite> for SerdeRow<T>
+where
+    T: DeserializeOwned,
+{
+    fn build<R: NamedRow<::sqlite::Sqlite>>(row: &R) -> deserialize::Result<Self> {
+        let columns = (0..row.column_count())
+            .filter_map(|i| {
+                let name = row.column_name(i)?.to_owned();
+                let value = row
+                    .get_raw_value(i)
+                    .map(|v| v.dynamic_value())
+                    .unwrap_or(DynamicValue::Null);
+                Some((name, value))
+            })
+            .collect::<Vec<_>>();
+
+        T::deserialize(de::value::MapDeserializer::new(columns.into_iter()))
+            .map(SerdeRow)
+            .map_err(|e: de::value::Error| e.to_string().into())
+    }
+}
+
+impl<'de> de::Deserializer<'de> for DynamicValue {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            DynamicValue::Text(x) => visitor.visit_string(x),
+            DynamicValue::Integer(x) => visitor.visit_i64(x),
+            DynamicValue::Double(x) => visitor.visit_f64(x),
+            DynamicValue::Bool(x) => visitor.visit_bool(x),
+            DynamicValue::Binary(x) => visitor.visit_byte_buf(x),
+            DynamicValue::Null => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            DynamicValue::Null => visitor.visit_none(),
+            other => visitor.visit_some(other),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
@@ -0,0 +1,121 @@
+//! Support for the "soft delete" pattern, where rows are flagged via a nullable timestamp
+//! column instead of being removed with a real `DELETE`.
+//!
+//! Diesel's [`FilterDsl`](query_dsl/methods/trait.FilterDsl.html) has no hook that would let a
+//! table rewrite queries built against it, so implementing [`SoftDeletable`] does *not* make a
+//! plain `table.filter(...)` skip soft-deleted rows automatically. Start from
+//! [`SoftDeletable::not_deleted`] (or [`only_deleted`], [`with_deleted`]) instead of `table`
+//! directly wherever that matters.
+//!
+//! [`only_deleted`]: trait.SoftDeletable.html#method.only_deleted
+//! [`with_deleted`]: trait.SoftDeletable.html#method.with_deleted
+
+use dsl::{now, Eq, Filter, IsNotNull, IsNull, Update};
+use expression::nullable::Nullable as NullableExpr;
+use expression_methods::*;
+use query_builder::IntoUpdateTarget;
+use query_dsl::methods::FilterDsl;
+use query_source::{Column, Table};
+use sql_types::{Nullable, Timestamp};
+
+/// Opt-in marker for tables that use the "soft delete" pattern.
+///
+/// Implement this for a `table!`-declared table to get [`not_deleted`], [`with_deleted`], and
+/// [`only_deleted`] filters, and to make it usable with [`soft_delete`](fn.soft_delete.html).
+///
+/// [`not_deleted`]: #method.not_deleted
+/// [`with_deleted`]: #method.with_deleted
+/// [`only_deleted`]: #method.only_deleted
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # use diesel::soft_delete::SoftDeletable;
+/// #
+/// table! {
+///     posts {
+///         id -> Integer,
+///         deleted_at -> Nullable<Timestamp>,
+///     }
+/// }
+///
+/// impl SoftDeletable for posts::table {
+///     type DeletedAtColumn = posts::deleted_at;
+/// }
+/// #
+/// # fn main() {}
+/// ```
+pub trait SoftDeletable: Table + Sized {
+    /// The nullable timestamp column recording when a row was soft-deleted. `NULL` while the
+    /// row is still live.
+    type DeletedAtColumn: Column<Table = Self, SqlType = Nullable<Timestamp>> + Default;
+
+    /// Excludes rows that have been soft-deleted.
+    fn not_deleted(self) -> Filter<Self, IsNull<Self::DeletedAtColumn>>
+    where
+        Self: FilterDsl<IsNull<Self::DeletedAtColumn>>,
+    {
+        let deleted_at = Self::DeletedAtColumn::default();
+        FilterDsl::filter(self, deleted_at.is_null())
+    }
+
+    /// Returns every row, including ones that have been soft-deleted.
+    ///
+    /// `table` already returns every row on its own, so this simply returns `self` unchanged.
+    /// It exists as an explicit escape hatch, so that a call site built from
+    /// `table.with_deleted()` reads the same way as one built from `table.not_deleted()` or
+    /// `table.only_deleted()`.
+    fn with_deleted(self) -> Self {
+        self
+    }
+
+    /// Returns only rows that have been soft-deleted.
+    fn only_deleted(self) -> Filter<Self, IsNotNull<Self::DeletedAtColumn>>
+    where
+        Self: FilterDsl<IsNotNull<Self::DeletedAtColumn>>,
+    {
+        let deleted_at = Self::DeletedAtColumn::default();
+        FilterDsl::filter(self, deleted_at.is_not_null())
+    }
+}
+
+/// Soft-deletes every row targeted by `target`, by setting its
+/// [`SoftDeletable::DeletedAtColumn`](trait.SoftDeletable.html#associatedtype.DeletedAtColumn)
+/// to the current time instead of running a `DELETE`.
+///
+/// Equivalent to `update(target).set(DeletedAtColumn::default().eq(now.nullable()))`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # use diesel::soft_delete::SoftDeletable;
+/// #
+/// table! {
+///     posts {
+///         id -> Integer,
+///         deleted_at -> Nullable<Timestamp>,
+///     }
+/// }
+///
+/// impl SoftDeletable for posts::table {
+///     type DeletedAtColumn = posts::deleted_at;
+/// }
+///
+/// # fn main() {
+/// use posts::dsl::*;
+/// let query = diesel::soft_delete::soft_delete(posts.filter(id.eq(1)));
+/// # let _ = query;
+/// # }
+/// ```
+pub fn soft_delete<T>(
+    target: T,
+) -> Update<T, Eq<<T::Table as SoftDeletable>::DeletedAtColumn, NullableExpr<now>>>
+where
+    T: IntoUpdateTarget,
+    T::Table: SoftDeletable,
+{
+    let deleted_at = <T::Table as SoftDeletable>::DeletedAtColumn::default();
+    ::update(target).set(deleted_at.eq(now.nullable()))
+}
@@ -162,18 +162,23 @@ pub type Float8 = Double;
 
 /// The arbitrary precision numeric SQL type.
 ///
-/// This type is only supported on PostgreSQL and MySQL.
-/// On SQLite, [`Double`](struct.Double.html) should be used instead.
+/// On PostgreSQL and MySQL, this type is backed by their native arbitrary
+/// precision numeric types. SQLite has no such type, so it is stored as
+/// `TEXT`; [`Double`](struct.Double.html) can be used there instead if
+/// lossy `f64` storage is acceptable.
 ///
 /// ### [`ToSql`](../serialize/trait.ToSql.html) impls
 ///
-/// - [`bigdecimal::BigDecimal`] with `feature = ["numeric"]`
+/// - [`bigdecimal::BigDecimal`] with `feature = ["numeric"]` (as `TEXT` on SQLite)
+/// - [`rust_decimal::Decimal`] with `feature = ["decimal"]` (lossless on all three backends)
 ///
 /// ### [`FromSql`](../deserialize/trait.FromSql.html) impls
 ///
-/// - [`bigdecimal::BigDecimal`] with `feature = ["numeric"]`
+/// - [`bigdecimal::BigDecimal`] with `feature = ["numeric"]` (as `TEXT` on SQLite)
+/// - [`rust_decimal::Decimal`] with `feature = ["decimal"]` (lossless on all three backends)
 ///
 /// [`bigdecimal::BigDecimal`]: /bigdecimal/struct.BigDecimal.html
+/// [`rust_decimal::Decimal`]: /rust_decimal/struct.Decimal.html
 #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
 #[postgres(oid = "1700", array_oid = "1231")]
 #[mysql_type = "String"]
@@ -51,3 +51,10 @@ impl TypeMetadata for Sqlite {
 }
 
 impl UsesAnsiSavepointSyntax for Sqlite {}
+
+/// SQLite has supported `RETURNING` on `INSERT`, `UPDATE`, and `DELETE`
+/// statements since 3.35.0 (2021-03-12). Running against an older `libsqlite3`
+/// will surface as a runtime SQL syntax error rather than a compile-time one,
+/// the same tradeoff Diesel already makes for other SQLite version-gated
+/// syntax.
+impl SupportsReturningClause for Sqlite {}
@@ -0,0 +1,185 @@
+extern crate libsqlite3_sys as ffi;
+
+use std::os::raw as libc;
+
+use result::ConnectionResult;
+
+use super::SqliteConnection;
+
+/// A typed builder for the `file:` URI and open flags `SqliteConnection::establish` needs, so
+/// callers don't have to hand-assemble a URI query string themselves.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../../doctest_setup.rs");
+/// use diesel::sqlite::SqliteConnectOptions;
+///
+/// # fn main() {
+/// let conn = SqliteConnectOptions::new()
+///     .path(":memory:")
+///     .read_only(false)
+///     .establish();
+/// assert!(conn.is_ok());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SqliteConnectOptions {
+    path: String,
+    read_only: bool,
+    memory: bool,
+    immutable: bool,
+    cache_shared: bool,
+    vfs: Option<String>,
+}
+
+impl Default for SqliteConnectOptions {
+    fn default() -> Self {
+        SqliteConnectOptions {
+            path: String::from(":memory:"),
+            read_only: false,
+            memory: false,
+            immutable: false,
+            cache_shared: false,
+            vfs: None,
+        }
+    }
+}
+
+impl SqliteConnectOptions {
+    /// Starts from a connection to a private, in-memory database (the same default `":memory:"`
+    /// would give you), to be customized with the other builder methods.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The path to the database file. Defaults to `":memory:"`.
+    pub fn path<P: Into<String>>(mut self, path: P) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Open the database read-only, failing with `SQLITE_CANTOPEN` if it doesn't already exist,
+    /// instead of silently creating an empty one. Defaults to `false`.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Opens `path` as a pure in-memory database (`mode=memory`) instead of a file, the same as
+    /// naming it `":memory:"`, except that combined with `cache_shared(true)` a named one can be
+    /// reopened by other connections instead of always being private. Defaults to `false`.
+    pub fn memory(mut self, memory: bool) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Tells SQLite the underlying file will not be modified by any process for as long as the
+    /// connection is open, letting it skip locking and change-detection overhead. Only meaningful
+    /// together with `read_only(true)`; setting it on a writable connection is asking SQLite to
+    /// break a promise you're not keeping. Defaults to `false`.
+    pub fn immutable(mut self, immutable: bool) -> Self {
+        self.immutable = immutable;
+        self
+    }
+
+    /// Use SQLite's shared cache mode for this connection, so this process's connections to the
+    /// same database share a single page cache instead of each keeping their own. Defaults to
+    /// `false`. See <https://sqlite.org/sharedcache.html>.
+    pub fn cache_shared(mut self, cache_shared: bool) -> Self {
+        self.cache_shared = cache_shared;
+        self
+    }
+
+    /// The name of a registered SQLite VFS to open the database through, e.g. `"unix-dotfile"`.
+    /// Defaults to SQLite's own default VFS.
+    pub fn vfs<S: Into<String>>(mut self, vfs: S) -> Self {
+        self.vfs = Some(vfs.into());
+        self
+    }
+
+    /// The `file:` URI these options translate to, and the `sqlite3_open_v2` flags that must be
+    /// passed alongside it -- `mode=ro`/`mode=rw` in the URI only takes effect if the matching
+    /// `SQLITE_OPEN_READONLY`/`SQLITE_OPEN_READWRITE` flag is also given to `sqlite3_open_v2`.
+    pub(crate) fn to_uri_and_flags(&self) -> (String, libc::c_int) {
+        let mut uri = format!("file:{}?", self.path);
+        if self.memory {
+            uri.push_str("mode=memory&");
+        } else if self.read_only {
+            uri.push_str("mode=ro&");
+        } else {
+            uri.push_str("mode=rwc&");
+        }
+        if self.immutable {
+            uri.push_str("immutable=1&");
+        }
+        if self.cache_shared {
+            uri.push_str("cache=shared&");
+        }
+        if let Some(ref vfs) = self.vfs {
+            uri.push_str("vfs=");
+            uri.push_str(vfs);
+            uri.push('&');
+        }
+        uri.pop(); // trailing `&` or `?` if nothing was appended
+
+        let mode_flags = if self.read_only {
+            ffi::SQLITE_OPEN_READONLY
+        } else {
+            ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE
+        };
+        (uri, mode_flags | ffi::SQLITE_OPEN_URI)
+    }
+
+    /// Opens the connection these options describe.
+    pub fn establish(&self) -> ConnectionResult<SqliteConnection> {
+        SqliteConnection::establish_with_options(self)
+    }
+}
+
+#[test]
+fn memory_overrides_read_only_in_the_uri_mode() {
+    let (uri, _) = SqliteConnectOptions::new()
+        .path("test")
+        .read_only(true)
+        .memory(true)
+        .cache_shared(true)
+        .to_uri_and_flags();
+    assert_eq!("file:test?mode=memory&cache=shared", uri);
+}
+
+#[test]
+fn default_options_open_an_in_memory_database_read_write() {
+    let (uri, flags) = SqliteConnectOptions::new().to_uri_and_flags();
+    assert_eq!("file::memory:?mode=rwc", uri);
+    assert_eq!(
+        ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE | ffi::SQLITE_OPEN_URI,
+        flags
+    );
+}
+
+#[test]
+fn read_only_sets_mode_ro_and_the_matching_open_flag() {
+    let (uri, flags) = SqliteConnectOptions::new()
+        .path("test.db")
+        .read_only(true)
+        .to_uri_and_flags();
+    assert_eq!("file:test.db?mode=ro", uri);
+    assert_eq!(ffi::SQLITE_OPEN_READONLY | ffi::SQLITE_OPEN_URI, flags);
+}
+
+#[test]
+fn builder_methods_compose_into_a_single_query_string() {
+    let (uri, _) = SqliteConnectOptions::new()
+        .path("test.db")
+        .read_only(true)
+        .immutable(true)
+        .cache_shared(true)
+        .vfs("unix-dotfile")
+        .to_uri_and_flags();
+    assert_eq!(
+        "file:test.db?mode=ro&immutable=1&cache=shared&vfs=unix-dotfile",
+        uri
+    );
+}
@@ -0,0 +1,166 @@
+extern crate regex;
+
+use std::cell::Cell;
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{SqliteConnection, SqliteValue};
+use result::QueryResult;
+use sql_types::{Bool, Double, Text};
+
+thread_local! {
+    static UUID_COUNTER: Cell<u64> = Cell::new(0);
+}
+
+pub fn register_default_functions(conn: &SqliteConnection) -> QueryResult<()> {
+    conn.register_sql_function::<(Text, Text), Bool, _, _, _>(
+        "regexp",
+        true,
+        |(pattern, string): (String, String)| {
+            regex::Regex::new(&pattern)
+                .map(|re| re.is_match(&string))
+                .unwrap_or(false)
+        },
+    )?;
+    conn.register_sql_function::<(Double, Double), Double, _, _, _>(
+        "power",
+        true,
+        |(base, exponent): (f64, f64)| base.powf(exponent),
+    )?;
+    conn.register_sql_function::<Double, Double, _, _, _>("sqrt", true, |x: f64| x.sqrt())?;
+    conn.register_sql_function::<Double, Double, _, _, _>("log", true, |x: f64| x.ln())?;
+    conn.register_sql_function::<Text, Text, _, _, _>("md5", true, |s: String| md5_hex(s.as_bytes()))?;
+    // `register_sql_function` has no zero-argument form (it dispatches on `Args: Queryable<_,
+    // _>`, which isn't implemented for `()`), so `uuid` is registered as a variadic function
+    // instead -- SQLite allows calling one with zero arguments, and this one ignores whatever
+    // arguments (if any) it's called with.
+    conn.register_variadic_function::<Text, String, _>(
+        "uuid",
+        false,
+        |_args: &[Option<&SqliteValue>]| random_uuid_v4(),
+    )?;
+    Ok(())
+}
+
+/// A pure-Rust MD5 implementation (RFC 1321), returning the lowercase hex digest, matching the
+/// convention of SQLite's own (optional, not always compiled in) `md5` extension function.
+///
+/// MD5 is only used here for a convenience scalar function, never anywhere security-relevant, so
+/// its well-known cryptographic weaknesses don't matter for this use.
+fn md5_hex(input: &[u8]) -> String {
+    const S: [u32; 64] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5,
+        9, 14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6,
+        10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+    ];
+    const K: [u32; 64] = [
+        0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613,
+        0xfd469501, 0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193,
+        0xa679438e, 0x49b40821, 0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d,
+        0x02441453, 0xd8a1e681, 0xe7d3fbc8, 0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+        0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a, 0xfffa3942, 0x8771f681, 0x6d9d6122,
+        0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70, 0x289b7ec6, 0xeaa127fa,
+        0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665, 0xf4292244,
+        0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+        0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb,
+        0xeb86d391,
+    ];
+
+    let mut a0: u32 = 0x67452301;
+    let mut b0: u32 = 0xefcdab89;
+    let mut c0: u32 = 0x98badcfe;
+    let mut d0: u32 = 0x10325476;
+
+    let mut message = input.to_vec();
+    let original_len_bits = (input.len() as u64).wrapping_mul(8);
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&original_len_bits.to_le_bytes());
+
+    for chunk in message.chunks(64) {
+        let mut m = [0u32; 16];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            m[i] = u32::from(word[0])
+                | (u32::from(word[1]) << 8)
+                | (u32::from(word[2]) << 16)
+                | (u32::from(word[3]) << 24);
+        }
+
+        let (mut a, mut b, mut c, mut d) = (a0, b0, c0, d0);
+
+        for i in 0..64 {
+            let (f, g) = if i < 16 {
+                ((b & c) | (!b & d), i)
+            } else if i < 32 {
+                ((d & b) | (!d & c), (5 * i + 1) % 16)
+            } else if i < 48 {
+                (b ^ c ^ d, (3 * i + 5) % 16)
+            } else {
+                (c ^ (b | !d), (7 * i) % 16)
+            };
+
+            let f = f
+                .wrapping_add(a)
+                .wrapping_add(K[i])
+                .wrapping_add(m[g]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(f.rotate_left(S[i]));
+        }
+
+        a0 = a0.wrapping_add(a);
+        b0 = b0.wrapping_add(b);
+        c0 = c0.wrapping_add(c);
+        d0 = d0.wrapping_add(d);
+    }
+
+    let mut hex = String::with_capacity(32);
+    for word in [a0, b0, c0, d0].iter() {
+        for byte in &word.to_le_bytes() {
+            let _ = write!(hex, "{:02x}", byte);
+        }
+    }
+    hex
+}
+
+/// A best-effort RFC 4122 version-4 UUID, for a convenient default identifier generator.
+///
+/// This mixes wall-clock time with a per-thread call counter rather than drawing from an actual
+/// CSPRNG, so it's suitable for generating IDs, not for anything where unpredictability matters
+/// (session tokens, password reset codes, etc). Depend on the `uuid` crate directly for that.
+fn random_uuid_v4() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs().wrapping_mul(1_000_000_000).wrapping_add(u64::from(d.subsec_nanos())))
+        .unwrap_or(0);
+    let counter = UUID_COUNTER.with(|c| {
+        let next = c.get().wrapping_add(1);
+        c.set(next);
+        next
+    });
+
+    let mut bytes = [0u8; 16];
+    let seed = nanos ^ counter.wrapping_mul(0x9E3779B97F4A7C15);
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        // A cheap avalanche so consecutive counter values don't produce visibly similar bytes.
+        let mixed = seed
+            .wrapping_add(i as u64)
+            .wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+        *byte = (mixed >> 32) as u8;
+    }
+
+    bytes[6] = (bytes[6] & 0x0f) | 0x40; // version 4
+    bytes[8] = (bytes[8] & 0x3f) | 0x80; // RFC 4122 variant
+
+    format!(
+        "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+        bytes[0], bytes[1], bytes[2], bytes[3],
+        bytes[4], bytes[5],
+        bytes[6], bytes[7],
+        bytes[8], bytes[9],
+        bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15],
+    )
+}
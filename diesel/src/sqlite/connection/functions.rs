@@ -1,6 +1,9 @@
 extern crate libsqlite3_sys as ffi;
 
-use super::raw::RawConnection;
+use std::error::Error as StdError;
+use std::os::raw as libc;
+
+use super::raw::{FunctionCallContext, RawConnection};
 use super::serialized_value::SerializedValue;
 use super::{Sqlite, SqliteValue};
 use deserialize::{FromSqlRow, Queryable};
@@ -29,7 +32,7 @@ where
         ));
     }
 
-    conn.register_sql_function(fn_name, fields_needed, deterministic, move |args| {
+    conn.register_sql_function(fn_name, fields_needed as i32, deterministic, move |args| {
         let mut row = FunctionRow { args };
         let args_row = Args::Row::build_from_row(&mut row).map_err(Error::DeserializationError)?;
         let args = Args::build(args_row);
@@ -53,6 +56,239 @@ where
     Ok(())
 }
 
+/// Like [`register`](fn.register.html), but for closures that can fail. `f` returns
+/// `Result<Ret, Box<Error + Send + Sync>>` instead of a bare `Ret`; an `Err` is reported to SQLite
+/// via `sqlite3_result_error` and surfaces to the query as a `DatabaseError`, instead of forcing
+/// the closure to be infallible.
+pub fn register_fallible<ArgsSqlType, RetSqlType, Args, Ret, F>(
+    conn: &RawConnection,
+    fn_name: &str,
+    deterministic: bool,
+    mut f: F,
+) -> QueryResult<()>
+where
+    F: FnMut(Args) -> Result<Ret, Box<StdError + Send + Sync>> + Send + 'static,
+    Args: Queryable<ArgsSqlType, Sqlite>,
+    Ret: ToSql<RetSqlType, Sqlite>,
+    Sqlite: HasSqlType<RetSqlType>,
+{
+    let fields_needed = Args::Row::FIELDS_NEEDED;
+    if fields_needed > 127 {
+        return Err(Error::DatabaseError(
+            DatabaseErrorKind::UnableToSendCommand,
+            Box::new("SQLite functions cannot take more than 127 parameters".to_string()),
+        ));
+    }
+
+    conn.register_sql_function(fn_name, fields_needed as i32, deterministic, move |args| {
+        let mut row = FunctionRow { args };
+        let args_row = Args::Row::build_from_row(&mut row).map_err(Error::DeserializationError)?;
+        let args = Args::build(args_row);
+
+        let result = f(args).map_err(|e| {
+            Error::DatabaseError(DatabaseErrorKind::__Unknown, Box::new(e.to_string()))
+        })?;
+
+        let mut buf = Output::new(Vec::new(), &());
+        let is_null = result.to_sql(&mut buf).map_err(Error::SerializationError)?;
+
+        let bytes = if let IsNull::Yes = is_null {
+            None
+        } else {
+            Some(buf.into_inner())
+        };
+
+        Ok(SerializedValue {
+            ty: Sqlite::metadata(&()),
+            data: bytes,
+        })
+    })?;
+    Ok(())
+}
+
+/// Like [`register`](fn.register.html), but `f` also receives a
+/// [`FunctionCallContext`](../raw/struct.FunctionCallContext.html), so it can cache the result of
+/// preprocessing a constant argument (e.g. compiling a regex from a pattern that doesn't change
+/// between rows) via `sqlite3_get_auxdata`/`sqlite3_set_auxdata` instead of redoing that work on
+/// every call.
+pub fn register_with_context<ArgsSqlType, RetSqlType, Args, Ret, F>(
+    conn: &RawConnection,
+    fn_name: &str,
+    deterministic: bool,
+    mut f: F,
+) -> QueryResult<()>
+where
+    F: FnMut(&FunctionCallContext, Args) -> Ret + Send + 'static,
+    Args: Queryable<ArgsSqlType, Sqlite>,
+    Ret: ToSql<RetSqlType, Sqlite>,
+    Sqlite: HasSqlType<RetSqlType>,
+{
+    let fields_needed = Args::Row::FIELDS_NEEDED;
+    if fields_needed > 127 {
+        return Err(Error::DatabaseError(
+            DatabaseErrorKind::UnableToSendCommand,
+            Box::new("SQLite functions cannot take more than 127 parameters".to_string()),
+        ));
+    }
+
+    conn.register_sql_function_with_context(
+        fn_name,
+        fields_needed as i32,
+        deterministic,
+        move |call_context, args| {
+            let mut row = FunctionRow { args };
+            let args_row =
+                Args::Row::build_from_row(&mut row).map_err(Error::DeserializationError)?;
+            let args = Args::build(args_row);
+
+            let result = f(call_context, args);
+
+            let mut buf = Output::new(Vec::new(), &());
+            let is_null = result.to_sql(&mut buf).map_err(Error::SerializationError)?;
+
+            let bytes = if let IsNull::Yes = is_null {
+                None
+            } else {
+                Some(buf.into_inner())
+            };
+
+            Ok(SerializedValue {
+                ty: Sqlite::metadata(&()),
+                data: bytes,
+            })
+        },
+    )?;
+    Ok(())
+}
+
+/// A SQL scalar type with a direct bridge to SQLite's native `sqlite3_value_*`/
+/// `sqlite3_result_*` C API, bypassing the `Queryable`/`ToSql` byte-buffer round trip `register`
+/// goes through. Implemented for the handful of primitive types SQLite has a native storage
+/// class for. See [`register_direct`](fn.register_direct.html).
+pub trait DirectSqlValue: Send + 'static {
+    #[doc(hidden)]
+    fn read_direct(value: &SqliteValue) -> Self;
+    #[doc(hidden)]
+    fn write_direct(self, ctx: *mut ffi::sqlite3_context);
+}
+
+impl DirectSqlValue for i32 {
+    fn read_direct(value: &SqliteValue) -> Self {
+        value.read_integer()
+    }
+
+    fn write_direct(self, ctx: *mut ffi::sqlite3_context) {
+        unsafe { ffi::sqlite3_result_int(ctx, self) }
+    }
+}
+
+impl DirectSqlValue for i64 {
+    fn read_direct(value: &SqliteValue) -> Self {
+        value.read_long()
+    }
+
+    fn write_direct(self, ctx: *mut ffi::sqlite3_context) {
+        unsafe { ffi::sqlite3_result_int64(ctx, self) }
+    }
+}
+
+impl DirectSqlValue for f64 {
+    fn read_direct(value: &SqliteValue) -> Self {
+        value.read_double()
+    }
+
+    fn write_direct(self, ctx: *mut ffi::sqlite3_context) {
+        unsafe { ffi::sqlite3_result_double(ctx, self) }
+    }
+}
+
+impl DirectSqlValue for String {
+    fn read_direct(value: &SqliteValue) -> Self {
+        value.read_text().to_owned()
+    }
+
+    fn write_direct(self, ctx: *mut ffi::sqlite3_context) {
+        unsafe {
+            ffi::sqlite3_result_text(
+                ctx,
+                self.as_ptr() as *const libc::c_char,
+                self.len() as libc::c_int,
+                ffi::SQLITE_TRANSIENT(),
+            )
+        }
+    }
+}
+
+/// Registers a single-argument function using [`DirectSqlValue`] for both the argument and
+/// return type. Unlike `register`, `f`'s argument is read straight out of SQLite with
+/// `sqlite3_value_*` and its return value written straight back with `sqlite3_result_*`, with no
+/// intermediate `Queryable` row or `ToSql` byte buffer - worthwhile for simple primitive-to-
+/// primitive transforms applied over large scans, where that per-row allocation shows up. `f` is
+/// never called with a SQL `NULL` argument; one is reported as a `DatabaseError` instead, since
+/// `DirectSqlValue` has no `NULL` representation to hand back.
+pub fn register_direct<Arg, Ret, F>(
+    conn: &RawConnection,
+    fn_name: &str,
+    deterministic: bool,
+    mut f: F,
+) -> QueryResult<()>
+where
+    F: FnMut(Arg) -> Ret + Send + 'static,
+    Arg: DirectSqlValue,
+    Ret: DirectSqlValue,
+{
+    conn.register_sql_function_direct(fn_name, 1, deterministic, move |call_context, args| {
+        match unsafe { SqliteValue::new(args[0]) } {
+            Some(value) => {
+                let result = f(Arg::read_direct(value));
+                result.write_direct(call_context.raw_ctx());
+                Ok(())
+            }
+            None => Err(Error::DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new("register_direct functions do not support NULL arguments".to_string()),
+            )),
+        }
+    })
+}
+
+/// Registers a function that takes a variable number of arguments (SQLite's `nArg = -1`), passed
+/// to `f` as a slice with one `Option<&SqliteValue>` per call-site argument (`None` for a SQL
+/// `NULL`), rather than the fixed-arity, typed tuple `register` above uses.
+pub fn register_variadic<RetSqlType, Ret, F>(
+    conn: &RawConnection,
+    fn_name: &str,
+    deterministic: bool,
+    mut f: F,
+) -> QueryResult<()>
+where
+    F: FnMut(&[Option<&SqliteValue>]) -> Ret + Send + 'static,
+    Ret: ToSql<RetSqlType, Sqlite>,
+    Sqlite: HasSqlType<RetSqlType>,
+{
+    conn.register_sql_function(fn_name, -1, deterministic, move |args| {
+        let values = args
+            .iter()
+            .map(|&arg| unsafe { SqliteValue::new(arg) })
+            .collect::<Vec<_>>();
+        let result = f(&values);
+
+        let mut buf = Output::new(Vec::new(), &());
+        let is_null = result.to_sql(&mut buf).map_err(Error::SerializationError)?;
+
+        let bytes = if let IsNull::Yes = is_null {
+            None
+        } else {
+            Some(buf.into_inner())
+        };
+
+        Ok(SerializedValue {
+            ty: Sqlite::metadata(&()),
+            data: bytes,
+        })
+    })
+}
+
 struct FunctionRow<'a> {
     args: &'a [*mut ffi::sqlite3_value],
 }
@@ -1,15 +1,34 @@
 extern crate libsqlite3_sys as ffi;
 
+mod blob;
+mod connect_options;
+mod default_functions;
+mod diff;
 mod functions;
 #[doc(hidden)]
 pub mod raw;
+mod schema;
 mod serialized_value;
 mod sqlite_value;
 mod statement_iterator;
 mod stmt;
-
+mod table_ddl;
+
+pub use self::blob::SqliteBlob;
+pub use self::connect_options::SqliteConnectOptions;
+pub use self::diff::{diff_schemas, diff_to_ddl, SchemaDiff};
+pub use self::functions::DirectSqlValue;
+pub use self::schema::{
+    ExpectedSqliteColumn, SchemaMismatch, SqliteColumn, SqliteForeignKey, SqliteIndex, SqliteTable,
+};
 pub use self::sqlite_value::SqliteValue;
+pub use self::stmt::{SqliteErrorCode, SqliteErrorInformation, StatementStatus};
+pub use self::table_ddl::{create_table_ddl, DdlColumnList, SqliteDdlType};
 
+use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
+use std::error::Error as StdError;
+use std::hash::{Hash, Hasher};
 use std::os::raw as libc;
 
 use self::raw::RawConnection;
@@ -18,20 +37,42 @@ use self::stmt::{Statement, StatementUse};
 use connection::*;
 use deserialize::{Queryable, QueryableByName};
 use query_builder::bind_collector::RawBytesBindCollector;
+use query_builder::functions::sql_query;
 use query_builder::*;
+use query_dsl::RunQueryDsl;
 use result::*;
 use serialize::ToSql;
-use sql_types::HasSqlType;
+use sql_types::{HasSqlType, Text};
 use sqlite::Sqlite;
 
+#[derive(QueryableByName)]
+struct DatabaseListRow {
+    #[sql_type = "Text"]
+    name: String,
+}
+
+#[derive(QueryableByName)]
+struct JournalModeRow {
+    #[sql_type = "Text"]
+    #[column_name = "journal_mode"]
+    journal_mode: String,
+}
+
 /// Connections for the SQLite backend. Unlike other backends, "connection URLs"
 /// for SQLite are file paths, [URIs](https://sqlite.org/uri.html), or special
 /// identifiers like `:memory:`.
+///
+/// Connections are opened with `SQLITE_OPEN_URI` enabled, so a URI filename can pass
+/// `mode=rw` (or `mode=ro`) to fail with an error instead of silently creating an
+/// empty database when the file doesn't already exist, e.g.
+/// `establish("file:my.db?mode=rw")`.
 #[allow(missing_debug_implementations)]
 pub struct SqliteConnection {
     statement_cache: StatementCache<Sqlite, Statement>,
     raw_connection: RawConnection,
     transaction_manager: AnsiTransactionManager,
+    readonly_tx: Cell<bool>,
+    attached_schemas_salt: Cell<u64>,
 }
 
 // This relies on the invariant that RawConnection or Statement are never
@@ -54,6 +95,8 @@ impl Connection for SqliteConnection {
             statement_cache: StatementCache::new(),
             raw_connection: conn,
             transaction_manager: AnsiTransactionManager::new(),
+            readonly_tx: Cell::new(false),
+            attached_schemas_salt: Cell::new(0),
         })
     }
 
@@ -96,6 +139,12 @@ impl Connection for SqliteConnection {
     {
         let mut statement = try!(self.prepare_query(source));
         let mut statement_use = StatementUse::new(&mut statement);
+        if self.is_readonly_tx() && !statement_use.is_readonly() {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new("Cannot execute a write inside a read-only transaction".to_string()),
+            ));
+        }
         try!(statement_use.run());
         Ok(self.raw_connection.rows_affected_by_last_query())
     }
@@ -107,6 +156,128 @@ impl Connection for SqliteConnection {
 }
 
 impl SqliteConnection {
+    /// Establishes a connection using a [`SqliteConnectOptions`](struct.SqliteConnectOptions.html)
+    /// builder instead of a raw URI string.
+    pub fn establish_with_options(options: &SqliteConnectOptions) -> ConnectionResult<Self> {
+        let (uri, flags) = options.to_uri_and_flags();
+        RawConnection::establish_with_flags(&uri, flags).map(|conn| SqliteConnection {
+            statement_cache: StatementCache::new(),
+            raw_connection: conn,
+            transaction_manager: AnsiTransactionManager::new(),
+            readonly_tx: Cell::new(false),
+            attached_schemas_salt: Cell::new(0),
+        })
+    }
+
+    /// Opens a named, shared, in-memory database (`file:{name}?mode=memory&cache=shared`), so
+    /// other connections that establish the same `name` see the same database instead of each
+    /// getting their own private one -- useful for a connection pool backing an in-memory test
+    /// database.
+    ///
+    /// SQLite destroys a shared in-memory database's contents as soon as its last open connection
+    /// is closed, exactly like it would for a file if it were deleted -- so callers pooling
+    /// connections need to keep at least one connection to `name` open for as long as they want
+    /// the data to survive, and expect it gone (not merely empty) once that last connection drops.
+    pub fn establish_shared_memory(name: &str) -> ConnectionResult<Self> {
+        SqliteConnectOptions::new()
+            .path(name)
+            .memory(true)
+            .cache_shared(true)
+            .establish()
+    }
+
+    /// `ATTACH`es another database file to this connection under `schema_name`, so statements can
+    /// reference `schema_name.table_name` alongside this connection's main database.
+    ///
+    /// A single [`transaction`](../connection/trait.Connection.html#method.transaction) already
+    /// covers statements against every attached schema as well as the main one -- SQLite commits
+    /// or rolls back all of them together. See
+    /// [`check_atomic_commit_conditions`](#method.check_atomic_commit_conditions) for the one case
+    /// where that guarantee doesn't hold.
+    pub fn attach_database(&self, path: &str, schema_name: &str) -> QueryResult<()> {
+        self.execute(&format!(
+            "ATTACH DATABASE {} AS {}",
+            quote_string_literal(path),
+            quote_identifier(schema_name)
+        ))?;
+        self.refresh_attached_schemas_salt();
+        Ok(())
+    }
+
+    /// `DETACH`es a database previously attached with
+    /// [`attach_database`](#method.attach_database).
+    pub fn detach_database(&self, schema_name: &str) -> QueryResult<()> {
+        self.execute(&format!(
+            "DETACH DATABASE {}",
+            quote_identifier(schema_name)
+        ))?;
+        self.refresh_attached_schemas_salt();
+        Ok(())
+    }
+
+    /// Checks whether a transaction spanning every currently attached database is guaranteed to
+    /// commit atomically.
+    ///
+    /// SQLite can't make a multi-database commit atomic if two or more of the databases involved
+    /// are simultaneously in `journal_mode = WAL` -- in that case a `COMMIT` touching more than
+    /// one of them fails outright with `SQLITE_ERROR` rather than partially applying, but it's
+    /// better to catch the misconfiguration up front than to find out the first time a
+    /// transaction is committed. Returns `Ok(())` if at most one attached database (including
+    /// `main`) uses WAL, and a `DatabaseError` naming the offending schemas otherwise.
+    pub fn check_atomic_commit_conditions(&self) -> QueryResult<()> {
+        let schemas = self.attached_schema_names()?;
+        let mut wal_schemas = Vec::new();
+        for schema in schemas {
+            let journal_mode = sql_query(format!("PRAGMA {}.journal_mode", schema))
+                .load::<JournalModeRow>(self)?
+                .pop()
+                .map(|row| row.journal_mode)
+                .unwrap_or_default();
+            if journal_mode.eq_ignore_ascii_case("wal") {
+                wal_schemas.push(schema);
+            }
+        }
+
+        if wal_schemas.len() > 1 {
+            let message = format!(
+                "Cannot guarantee an atomic commit across attached databases {} because more \
+                 than one of them uses journal_mode = WAL",
+                wal_schemas.join(", ")
+            );
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(message),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Runs a transaction covering the main database and every attached one, after checking
+    /// [`check_atomic_commit_conditions`](#method.check_atomic_commit_conditions) so a
+    /// non-atomic configuration is caught before any statements run instead of at `COMMIT` time.
+    pub fn attached_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+        E: From<Error>,
+    {
+        self.check_atomic_commit_conditions()?;
+        self.transaction(f)
+    }
+
+    fn attached_schema_names(&self) -> QueryResult<Vec<String>> {
+        sql_query("PRAGMA database_list")
+            .load::<DatabaseListRow>(self)
+            .map(|rows| rows.into_iter().map(|row| row.name).collect())
+    }
+
+    fn refresh_attached_schemas_salt(&self) {
+        let schemas = self.attached_schema_names().unwrap_or_default();
+        let mut hasher = DefaultHasher::new();
+        schemas.hash(&mut hasher);
+        self.attached_schemas_salt.set(hasher.finish());
+    }
+
     /// Run a transaction with `BEGIN IMMEDIATE`
     ///
     /// This method will return an error if a transaction is already open.
@@ -167,6 +338,59 @@ impl SqliteConnection {
         self.transaction_sql(f, "BEGIN EXCLUSIVE")
     }
 
+    /// Whether this connection currently has a read-only transaction open via
+    /// [`read_only_transaction`](#method.read_only_transaction).
+    ///
+    /// While this is `true`, a statement run with `.execute()` (an `insert_into`, `update`,
+    /// `delete`, or `sql_query`) that isn't
+    /// [`sqlite3_stmt_readonly`](https://www.sqlite.org/c3ref/stmt_readonly.html) is rejected
+    /// with a `DatabaseError`. Statements run with `.load()` or `.get_result()` (including ones
+    /// with a `RETURNING` clause), and raw SQL passed to `execute` or `batch_execute`, are not
+    /// checked.
+    pub fn is_readonly_tx(&self) -> bool {
+        self.readonly_tx.get()
+    }
+
+    /// Runs a transaction in which writes made with `.execute()` are rejected, using
+    /// [`sqlite3_stmt_readonly`](https://www.sqlite.org/c3ref/stmt_readonly.html) to detect them
+    /// automatically. See [`is_readonly_tx`](#method.is_readonly_tx) for which statements are
+    /// checked.
+    ///
+    /// This method will return an error if a transaction is already open.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use schema::users::dsl::*;
+    /// #     let conn = SqliteConnection::establish(":memory:").unwrap();
+    /// let result = conn.read_only_transaction(|| {
+    ///     diesel::insert_into(users)
+    ///         .values(name.eq("Sean"))
+    ///         .execute(&conn)
+    /// });
+    /// assert!(result.is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn read_only_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+        E: From<Error>,
+    {
+        self.readonly_tx.set(true);
+        let result = self.transaction_sql(f, "BEGIN");
+        self.readonly_tx.set(false);
+        result
+    }
+
     fn transaction_sql<T, E, F>(&self, f: F, sql: &str) -> Result<T, E>
     where
         F: FnOnce() -> Result<T, E>,
@@ -204,13 +428,116 @@ impl SqliteConnection {
         Ok(statement)
     }
 
+    /// Returns the SQL that would be sent to SQLite for `source`, with its
+    /// bound values substituted in place, as SQLite itself expands them (see
+    /// [`sqlite3_expanded_sql`](https://www.sqlite.org/c3ref/expanded_sql.html)).
+    ///
+    /// Unlike [`debug_query`](../fn.debug_query.html), which shows the bind
+    /// values separately because most backends have no safe way to inline
+    /// them into the SQL text, this uses SQLite's own quoting logic, so the
+    /// result can be pasted directly into a SQL prompt.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     use schema::users::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let query = users.filter(name.eq("Sean"));
+    /// let sql = connection.expanded_sql(&query).unwrap().unwrap();
+    /// assert_eq!(
+    ///     "SELECT `users`.`id`, `users`.`name` FROM `users` WHERE `users`.`name` = 'Sean'",
+    ///     sql,
+    /// );
+    /// # }
+    /// ```
+    pub fn expanded_sql<T>(&self, source: &T) -> QueryResult<Option<String>>
+    where
+        T: QueryFragment<Sqlite> + QueryId,
+    {
+        let statement = self.prepare_query(source)?;
+        Ok(statement.expanded_sql())
+    }
+
+    /// The number of columns `source`'s result set will have, without
+    /// running it.
+    pub fn column_count<T>(&self, source: &T) -> QueryResult<usize>
+    where
+        T: QueryFragment<Sqlite> + QueryId,
+    {
+        let statement = self.prepare_query(source)?;
+        Ok(statement.column_count())
+    }
+
+    /// The name assigned to column `idx` (its alias if one was given) in
+    /// `source`'s result set, without running it.
+    pub fn column_name<T>(&self, source: &T, idx: usize) -> QueryResult<Option<String>>
+    where
+        T: QueryFragment<Sqlite> + QueryId,
+    {
+        let statement = self.prepare_query(source)?;
+        Ok(statement.column_name(idx))
+    }
+
+    /// The declared type of column `idx` in `source`'s result set, as
+    /// written in the `CREATE TABLE` statement for the source table (e.g.
+    /// `"INTEGER"`), without running it. Returns `None` for columns that
+    /// aren't a direct reference to a table column.
+    pub fn column_decltype<T>(&self, source: &T, idx: usize) -> QueryResult<Option<String>>
+    where
+        T: QueryFragment<Sqlite> + QueryId,
+    {
+        let statement = self.prepare_query(source)?;
+        Ok(statement.column_decltype(idx))
+    }
+
+    /// The number of bind parameters in `source`.
+    pub fn parameter_count<T>(&self, source: &T) -> QueryResult<usize>
+    where
+        T: QueryFragment<Sqlite> + QueryId,
+    {
+        let statement = self.prepare_query(source)?;
+        Ok(statement.parameter_count())
+    }
+
+    /// Whether `source` is guaranteed not to modify the database
+    /// (per [`sqlite3_stmt_readonly`](https://www.sqlite.org/c3ref/stmt_readonly.html)),
+    /// useful for validating arbitrary user-provided SQL before running it.
+    pub fn is_readonly<T>(&self, source: &T) -> QueryResult<bool>
+    where
+        T: QueryFragment<Sqlite> + QueryId,
+    {
+        let statement = self.prepare_query(source)?;
+        Ok(statement.is_readonly())
+    }
+
+    /// Returns each currently cached prepared statement's SQL text alongside its
+    /// [`StatementStatus`](struct.StatementStatus.html) performance counters, so hot,
+    /// badly-indexed queries can be identified in production without external tooling.
+    ///
+    /// `reset` controls whether each statement's counters are zeroed as they're read, same as the
+    /// `resetFlg` argument to [`sqlite3_stmt_status`](https://www.sqlite.org/c3ref/stmt_status.html)
+    /// itself.
+    pub fn statement_cache_stats(&self, reset: bool) -> Vec<(String, StatementStatus)> {
+        self.statement_cache
+            .cache
+            .borrow()
+            .values()
+            .map(|statement| (statement.sql().unwrap_or_default(), statement.status(reset)))
+            .collect()
+    }
+
     fn cached_prepared_statement<T: QueryFragment<Sqlite> + QueryId>(
         &self,
         source: &T,
     ) -> QueryResult<MaybeCached<Statement>> {
-        self.statement_cache.cached_statement(source, &[], |sql| {
-            Statement::prepare(&self.raw_connection, sql)
-        })
+        self.statement_cache
+            .cached_statement(source, &[], self.attached_schemas_salt.get(), |sql| {
+                Statement::prepare(&self.raw_connection, sql)
+            })
     }
 
     #[doc(hidden)]
@@ -228,12 +555,337 @@ impl SqliteConnection {
     {
         functions::register(&self.raw_connection, fn_name, deterministic, f)
     }
+
+    /// Like [`register_sql_function`](#method.register_sql_function), but for closures that can
+    /// fail. Returning `Err` reports the failure to SQLite via `sqlite3_result_error`, which
+    /// surfaces to the query as a [`DatabaseError`](../result/enum.Error.html#variant.DatabaseError)
+    /// instead of forcing the closure to always produce a value.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use diesel::dsl::sql;
+    /// #     use diesel::sql_types::Double;
+    /// #     let connection = SqliteConnection::establish(":memory:").unwrap();
+    /// connection.register_fallible_sql_function::<Double, Double, _, _, _>(
+    ///     "checked_sqrt",
+    ///     true,
+    ///     |x: f64| {
+    ///         if x < 0.0 {
+    ///             Err(format!("checked_sqrt of negative number {}", x).into())
+    ///         } else {
+    ///             Ok(x.sqrt())
+    ///         }
+    ///     },
+    /// )?;
+    ///
+    /// let result = diesel::select(sql::<Double>("checked_sqrt(9)")).get_result::<f64>(&connection);
+    /// assert_eq!(Ok(3.0), result);
+    ///
+    /// let result = diesel::select(sql::<Double>("checked_sqrt(-9)")).get_result::<f64>(&connection);
+    /// assert!(result.is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn register_fallible_sql_function<ArgsSqlType, RetSqlType, Args, Ret, F>(
+        &self,
+        fn_name: &str,
+        deterministic: bool,
+        f: F,
+    ) -> QueryResult<()>
+    where
+        F: FnMut(Args) -> Result<Ret, Box<StdError + Send + Sync>> + Send + 'static,
+        Args: Queryable<ArgsSqlType, Sqlite>,
+        Ret: ToSql<RetSqlType, Sqlite>,
+        Sqlite: HasSqlType<RetSqlType>,
+    {
+        functions::register_fallible(&self.raw_connection, fn_name, deterministic, f)
+    }
+
+    /// Like [`register_sql_function`](#method.register_sql_function), but `f` also receives a
+    /// [`FunctionCallContext`](raw/struct.FunctionCallContext.html) giving access to
+    /// `sqlite3_get_auxdata`/`sqlite3_set_auxdata`, so expensive preprocessing of a constant
+    /// argument (e.g. compiling a regex from a pattern that's the same on every row) can be
+    /// cached instead of redone on every call.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use diesel::dsl::sql;
+    /// #     use diesel::sql_types::{Bool, Text};
+    /// #     let connection = SqliteConnection::establish(":memory:").unwrap();
+    /// connection.register_sql_function_with_context::<(Text, Text), Bool, _, _, _>(
+    ///     "starts_with_cached",
+    ///     true,
+    ///     |call_context, (prefix, string): (String, String)| {
+    ///         let recompiled = unsafe { call_context.get_aux_data::<String>(0) } != Some(&prefix);
+    ///         if recompiled {
+    ///             call_context.set_aux_data(0, prefix.clone());
+    ///         }
+    ///         string.starts_with(&prefix)
+    ///     },
+    /// )?;
+    ///
+    /// let result = diesel::select(sql::<Bool>("starts_with_cached('foo', 'foobar')"))
+    ///     .get_result::<bool>(&connection)?;
+    /// assert!(result);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn register_sql_function_with_context<ArgsSqlType, RetSqlType, Args, Ret, F>(
+        &self,
+        fn_name: &str,
+        deterministic: bool,
+        f: F,
+    ) -> QueryResult<()>
+    where
+        F: FnMut(&raw::FunctionCallContext, Args) -> Ret + Send + 'static,
+        Args: Queryable<ArgsSqlType, Sqlite>,
+        Ret: ToSql<RetSqlType, Sqlite>,
+        Sqlite: HasSqlType<RetSqlType>,
+    {
+        functions::register_with_context(&self.raw_connection, fn_name, deterministic, f)
+    }
+
+    /// Removes a function previously registered with `register_sql_function` or one of its
+    /// variants (`register_fallible_sql_function`, `register_sql_function_with_context`,
+    /// `register_variadic_function`), given its name and arity (`-1` for a variadic function).
+    ///
+    /// The closure's boxed state is dropped immediately as part of this call, rather than
+    /// lingering until the connection closes, which makes it safe to re-register the same name
+    /// with a different implementation (e.g. to hot-swap behavior at runtime).
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use diesel::dsl::sql;
+    /// #     use diesel::sql_types::Integer;
+    /// #     let connection = SqliteConnection::establish(":memory:").unwrap();
+    /// connection.register_sql_function::<Integer, Integer, _, _, _>("answer", true, |_: i32| 41)?;
+    /// connection.unregister_sql_function("answer", 1)?;
+    /// connection.register_sql_function::<Integer, Integer, _, _, _>("answer", true, |_: i32| 42)?;
+    ///
+    /// let result = diesel::select(sql::<Integer>("answer(0)")).get_result::<i32>(&connection)?;
+    /// assert_eq!(42, result);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn unregister_sql_function(&self, fn_name: &str, num_args: i32) -> QueryResult<()> {
+        self.raw_connection.unregister_sql_function(fn_name, num_args)
+    }
+
+    /// Registers a function that takes a variable number of arguments, for cases where
+    /// [`register_sql_function`](#method.register_sql_function)'s fixed arity doesn't fit (e.g. a
+    /// custom `concat_ws` or `greatest` that accepts any number of values).
+    ///
+    /// Unlike `register_sql_function`, `f` isn't given typed, deserialized arguments — SQLite
+    /// itself doesn't know the arity up front, so there's no fixed `ArgsSqlType` to deserialize
+    /// against. Instead `f` receives one [`Option<&SqliteValue>`] per call-site argument (`None`
+    /// for a SQL `NULL`), in order, and reads each one out with whichever `SqliteValue::read_*`
+    /// method matches the type it expects.
+    ///
+    /// [`Option<&SqliteValue>`]: struct.SqliteValue.html
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// # use diesel::sqlite::SqliteValue;
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use diesel::dsl::sql;
+    /// #     use diesel::sql_types::Text;
+    /// #     let connection = SqliteConnection::establish(":memory:").unwrap();
+    /// connection.register_variadic_function::<Text, String, _>(
+    ///     "concat_ws",
+    ///     true,
+    ///     |args: &[Option<&SqliteValue>]| {
+    ///         let mut values = args.iter().filter_map(|arg| arg.map(|v| v.read_text().to_string()));
+    ///         let separator = values.next().unwrap_or_default();
+    ///         values.collect::<Vec<_>>().join(&separator)
+    ///     },
+    /// )?;
+    ///
+    /// let result = diesel::select(sql::<Text>("concat_ws('-', 'a', 'b', 'c')"))
+    ///     .get_result::<String>(&connection)?;
+    /// assert_eq!("a-b-c", result);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn register_variadic_function<RetSqlType, Ret, F>(
+        &self,
+        fn_name: &str,
+        deterministic: bool,
+        f: F,
+    ) -> QueryResult<()>
+    where
+        F: FnMut(&[Option<&SqliteValue>]) -> Ret + Send + 'static,
+        Ret: ToSql<RetSqlType, Sqlite>,
+        Sqlite: HasSqlType<RetSqlType>,
+    {
+        functions::register_variadic(&self.raw_connection, fn_name, deterministic, f)
+    }
+
+    /// Registers a single-argument function using [`DirectSqlValue`](trait.DirectSqlValue.html)
+    /// for both the argument and return type, bypassing the `Queryable`/`ToSql` byte-buffer round
+    /// trip [`register_sql_function`](#method.register_sql_function) goes through in favor of
+    /// reading and writing straight through SQLite's `sqlite3_value_*`/`sqlite3_result_*` C API.
+    /// Worthwhile for simple primitive-to-primitive transforms (e.g. a computed column) applied
+    /// over large scans, where that per-row buffer allocation is measurable. `DirectSqlValue` is
+    /// implemented for `i32`, `i64`, `f64`, and `String`; it has no `NULL` representation, so `f`
+    /// is never called with a SQL `NULL` argument — one is reported as a `DatabaseError` instead.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use diesel::dsl::sql;
+    /// #     use diesel::sql_types::Integer;
+    /// #     let connection = SqliteConnection::establish(":memory:").unwrap();
+    /// connection.register_direct_function("double_it", true, |x: i32| x * 2)?;
+    ///
+    /// let result = diesel::select(sql::<Integer>("double_it(21)")).get_result::<i32>(&connection)?;
+    /// assert_eq!(42, result);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn register_direct_function<Arg, Ret, F>(
+        &self,
+        fn_name: &str,
+        deterministic: bool,
+        f: F,
+    ) -> QueryResult<()>
+    where
+        F: FnMut(Arg) -> Ret + Send + 'static,
+        Arg: DirectSqlValue,
+        Ret: DirectSqlValue,
+    {
+        functions::register_direct(&self.raw_connection, fn_name, deterministic, f)
+    }
+
+    /// Registers a curated set of scalar functions that stock SQLite builds don't ship with, but
+    /// that application code often assumes are there: `regexp(pattern, string)`,
+    /// `power(base, exponent)`, `sqrt(x)`, `log(x)` (natural log), `md5(string)`, and `uuid()`.
+    ///
+    /// SQLite can be compiled with some of these (the math functions, behind
+    /// `SQLITE_ENABLE_MATH_FUNCTIONS`; `regexp` behind a loaded extension), but neither is
+    /// guaranteed to be present, and none of `md5`/`uuid` are ever built in. This registers Rust
+    /// implementations of all six unconditionally, so queries using them behave the same
+    /// regardless of how the underlying `libsqlite3` was built. `uuid()` is not deterministic (it
+    /// must return a different value each call), so unlike the other five it's registered with
+    /// `deterministic: false`.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use diesel::dsl::sql;
+    /// #     use diesel::sql_types::Double;
+    /// #     let connection = SqliteConnection::establish(":memory:").unwrap();
+    /// connection.register_default_functions()?;
+    ///
+    /// let result = diesel::select(sql::<Double>("power(2, 10)")).get_result::<f64>(&connection)?;
+    /// assert_eq!(1024.0, result);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn register_default_functions(&self) -> QueryResult<()> {
+        default_functions::register_default_functions(self)
+    }
+
+    /// Opens a streaming handle to a single `BLOB` value, for reading it in
+    /// fixed-size chunks through [`std::io::Read`] instead of loading the
+    /// whole column into memory.
+    ///
+    /// `table` and `column` must refer to an existing rowid table (a `BLOB`
+    /// column on a `WITHOUT ROWID` table can't be streamed this way), and
+    /// `row_id` is the row's `rowid`/`_rowid_`/`oid` value. `db_name` is
+    /// almost always `"main"`, unless the blob lives in an attached
+    /// database.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// # use std::io::Read;
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     let connection = SqliteConnection::establish(":memory:").unwrap();
+    /// connection.execute("CREATE TABLE attachments (id INTEGER PRIMARY KEY, data BLOB NOT NULL)")?;
+    /// connection.execute("INSERT INTO attachments (data) VALUES (x'0102030405')")?;
+    ///
+    /// let mut blob = connection.blob_open("main", "attachments", "data", 1, true)?;
+    /// let mut contents = Vec::new();
+    /// blob.read_to_end(&mut contents).unwrap();
+    /// assert_eq!(vec![1, 2, 3, 4, 5], contents);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn blob_open<'a>(
+        &'a self,
+        db_name: &str,
+        table: &str,
+        column: &str,
+        row_id: i64,
+        read_only: bool,
+    ) -> QueryResult<SqliteBlob<'a>> {
+        SqliteBlob::open(
+            &self.raw_connection,
+            db_name,
+            table,
+            column,
+            row_id,
+            read_only,
+        )
+    }
 }
 
 fn error_message(err_code: libc::c_int) -> &'static str {
     ffi::code_to_str(err_code)
 }
 
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_string_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -341,4 +993,23 @@ mod tests {
             .get_result::<(i32, i32, i32)>(&connection);
         assert_eq!(Ok((2, 3, 4)), added);
     }
+
+    use sql_types::Nullable;
+    sql_function!(fn nullable_shout(x: Nullable<Text>) -> Nullable<Text>);
+
+    #[test]
+    fn register_function_with_nullable_arg_and_return() {
+        let connection = SqliteConnection::establish(":memory:").unwrap();
+        nullable_shout::register_impl(&connection, |x: Option<String>| {
+            x.map(|s| s.to_uppercase())
+        }).unwrap();
+
+        let shouted = ::select(nullable_shout(None::<String>))
+            .get_result::<Option<String>>(&connection);
+        assert_eq!(Ok(None), shouted);
+
+        let shouted = ::select(nullable_shout(Some("hi")))
+            .get_result::<Option<String>>(&connection);
+        assert_eq!(Ok(Some("HI".to_string())), shouted);
+    }
 }
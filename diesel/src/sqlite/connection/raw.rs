@@ -3,6 +3,7 @@ extern crate libsqlite3_sys as ffi;
 use std::ffi::{CStr, CString};
 use std::io::{stderr, Write};
 use std::os::raw as libc;
+use std::panic::{self, AssertUnwindSafe};
 use std::{ptr, slice, str};
 
 use super::serialized_value::SerializedValue;
@@ -17,10 +18,31 @@ pub struct RawConnection {
 
 impl RawConnection {
     pub fn establish(database_url: &str) -> ConnectionResult<Self> {
+        // `SQLITE_OPEN_URI` lets `database_url` use SQLite's URI filename syntax (e.g.
+        // `file:test.db?mode=rw`), so `mode=rw` or `mode=ro` can be used to fail fast with
+        // `SQLITE_CANTOPEN` instead of silently creating an empty database when the file doesn't
+        // already exist -- the default `sqlite3_open` behavior always implies `mode=rwc`.
+        Self::establish_with_flags(
+            database_url,
+            ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE | ffi::SQLITE_OPEN_URI,
+        )
+    }
+
+    /// Like `establish`, but with the `sqlite3_open_v2` open flags spelled out explicitly, for
+    /// callers (such as [`SqliteConnectOptions`](../struct.SqliteConnectOptions.html)) that need
+    /// more control than the default `SQLITE_OPEN_READWRITE | SQLITE_OPEN_CREATE` gives them, e.g.
+    /// `SQLITE_OPEN_READONLY` for a URI that requests `mode=ro`.
+    pub fn establish_with_flags(database_url: &str, flags: libc::c_int) -> ConnectionResult<Self> {
         let mut conn_pointer = ptr::null_mut();
         let database_url = try!(CString::new(database_url));
-        let connection_status =
-            unsafe { ffi::sqlite3_open(database_url.as_ptr(), &mut conn_pointer) };
+        let connection_status = unsafe {
+            ffi::sqlite3_open_v2(
+                database_url.as_ptr(),
+                &mut conn_pointer,
+                flags | ffi::SQLITE_OPEN_URI,
+                ptr::null(),
+            )
+        };
 
         match connection_status {
             ffi::SQLITE_OK => {
@@ -67,7 +89,7 @@ impl RawConnection {
     pub fn register_sql_function<F>(
         &self,
         fn_name: &str,
-        num_args: usize,
+        num_args: i32,
         deterministic: bool,
         f: F,
     ) -> QueryResult<()>
@@ -105,6 +127,193 @@ impl RawConnection {
             ))
         }
     }
+
+    /// Same as `register_sql_function`, except `f` also receives a
+    /// [`FunctionCallContext`](struct.FunctionCallContext.html), giving it access to
+    /// `sqlite3_get_auxdata`/`sqlite3_set_auxdata` for caching expensive per-argument
+    /// preprocessing (e.g. a compiled regex) across calls on the same statement.
+    pub fn register_sql_function_with_context<F>(
+        &self,
+        fn_name: &str,
+        num_args: i32,
+        deterministic: bool,
+        f: F,
+    ) -> QueryResult<()>
+    where
+        F: FnMut(&FunctionCallContext, &[*mut ffi::sqlite3_value]) -> QueryResult<SerializedValue>
+            + Send
+            + 'static,
+    {
+        let fn_name = CString::new(fn_name)?;
+        let mut flags = ffi::SQLITE_UTF8;
+        if deterministic {
+            flags |= ffi::SQLITE_DETERMINISTIC;
+        }
+        let callback_fn = Box::into_raw(Box::new(f));
+
+        let result = unsafe {
+            ffi::sqlite3_create_function_v2(
+                self.internal_connection.as_ptr(),
+                fn_name.as_ptr(),
+                num_args as _,
+                flags,
+                callback_fn as *mut _,
+                Some(run_custom_function_with_context::<F>),
+                None,
+                None,
+                Some(destroy_boxed_fn_with_context::<F>),
+            )
+        };
+
+        if result == ffi::SQLITE_OK {
+            Ok(())
+        } else {
+            let error_message = super::error_message(result);
+            Err(DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(error_message.to_string()),
+            ))
+        }
+    }
+
+    /// Same as `register_sql_function_with_context`, except `f` returns `QueryResult<()>` rather
+    /// than a `SerializedValue`: on `Ok(())` it must already have called `sqlite3_result_*` on
+    /// the given context itself (typically through [`DirectSqlValue::write_direct`]), skipping
+    /// the `Queryable`/`ToSql` byte-buffer round trip `register_sql_function` goes through. This
+    /// is a lower-level building block for fast paths over primitive types; see
+    /// [`register_direct`](../functions/fn.register_direct.html).
+    pub fn register_sql_function_direct<F>(
+        &self,
+        fn_name: &str,
+        num_args: i32,
+        deterministic: bool,
+        f: F,
+    ) -> QueryResult<()>
+    where
+        F: FnMut(&FunctionCallContext, &[*mut ffi::sqlite3_value]) -> QueryResult<()>
+            + Send
+            + 'static,
+    {
+        let fn_name = CString::new(fn_name)?;
+        let mut flags = ffi::SQLITE_UTF8;
+        if deterministic {
+            flags |= ffi::SQLITE_DETERMINISTIC;
+        }
+        let callback_fn = Box::into_raw(Box::new(f));
+
+        let result = unsafe {
+            ffi::sqlite3_create_function_v2(
+                self.internal_connection.as_ptr(),
+                fn_name.as_ptr(),
+                num_args as _,
+                flags,
+                callback_fn as *mut _,
+                Some(run_custom_function_direct::<F>),
+                None,
+                None,
+                Some(destroy_boxed_fn_direct::<F>),
+            )
+        };
+
+        if result == ffi::SQLITE_OK {
+            Ok(())
+        } else {
+            let error_message = super::error_message(result);
+            Err(DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(error_message.to_string()),
+            ))
+        }
+    }
+
+    /// Removes a function previously registered with `register_sql_function` (or one of its
+    /// variants) under the given name and arity. This drops the closure's boxed state
+    /// immediately, via the destructor passed to `sqlite3_create_function_v2` at registration
+    /// time, rather than waiting for the connection to close.
+    ///
+    /// SQLite deletes a function by re-registering its name/arity with `xFunc`, `xStep`, `xFinal`
+    /// and `pApp` all `NULL`, which is exactly what this does.
+    pub fn unregister_sql_function(&self, fn_name: &str, num_args: i32) -> QueryResult<()> {
+        let fn_name = CString::new(fn_name)?;
+
+        let result = unsafe {
+            ffi::sqlite3_create_function_v2(
+                self.internal_connection.as_ptr(),
+                fn_name.as_ptr(),
+                num_args as _,
+                ffi::SQLITE_UTF8,
+                ptr::null_mut(),
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+
+        if result == ffi::SQLITE_OK {
+            Ok(())
+        } else {
+            let error_message = super::error_message(result);
+            Err(DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(error_message.to_string()),
+            ))
+        }
+    }
+}
+
+/// Gives a function registered through
+/// [`register_sql_function_with_context`](struct.RawConnection.html#method.register_sql_function_with_context)
+/// access to SQLite's per-argument auxiliary data slots (`sqlite3_get_auxdata`/
+/// `sqlite3_set_auxdata`), so it can cache the result of preprocessing a constant argument (e.g.
+/// compiling a regex from a pattern that doesn't change between rows) instead of redoing that
+/// work on every call.
+///
+/// `arg_index` is the 0-based position of the argument the cached data is associated with;
+/// SQLite discards the cached value whenever that argument isn't itself a compile-time constant.
+#[allow(missing_debug_implementations, missing_copy_implementations)]
+pub struct FunctionCallContext {
+    ctx: *mut ffi::sqlite3_context,
+}
+
+impl FunctionCallContext {
+    /// Retrieves data previously stored by [`set_aux_data`](#method.set_aux_data) for the given
+    /// argument index on this statement, or `None` if nothing has been stored yet.
+    ///
+    /// # Safety
+    ///
+    /// The caller must only read the data back as the same `T` it was stored as; SQLite tracks
+    /// the pointer, not its type.
+    pub unsafe fn get_aux_data<T: 'static>(&self, arg_index: i32) -> Option<&T> {
+        let ptr = ffi::sqlite3_get_auxdata(self.ctx, arg_index) as *const T;
+        ptr.as_ref()
+    }
+
+    /// Stores `data` for the given argument index on this statement. SQLite takes ownership and
+    /// will drop it (via a generated destructor) once it's no longer valid, so a later
+    /// [`get_aux_data::<T>`](#method.get_aux_data) call must use the same `T`.
+    pub fn set_aux_data<T: 'static>(&self, arg_index: i32, data: T) {
+        let boxed = Box::into_raw(Box::new(data));
+        unsafe {
+            ffi::sqlite3_set_auxdata(
+                self.ctx,
+                arg_index,
+                boxed as *mut libc::c_void,
+                Some(destroy_boxed_aux_data::<T>),
+            );
+        }
+    }
+
+    /// The raw `sqlite3_context` this call is for, for callers (within this crate) that need to
+    /// call `sqlite3_result_*` directly rather than going through a `SerializedValue`.
+    pub(crate) fn raw_ctx(&self) -> *mut ffi::sqlite3_context {
+        self.ctx
+    }
+}
+
+extern "C" fn destroy_boxed_aux_data<T>(data: *mut libc::c_void) {
+    let ptr = data as *mut T;
+    unsafe { Box::from_raw(ptr) };
 }
 
 impl Drop for RawConnection {
@@ -161,7 +370,16 @@ extern "C" fn run_custom_function<F>(
         };
 
         let args = slice::from_raw_parts(value_ptr, num_args as _);
-        match f(args) {
+        // Unwinding across the C boundary back into SQLite is undefined behavior, so a panicking
+        // function body is turned into an ordinary SQLite error instead.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(args))).unwrap_or_else(|payload| {
+            let msg = panic_message(&payload);
+            Err(DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(format!("SQLite function panicked: {}", msg)),
+            ))
+        });
+        match result {
             Ok(value) => value.result_of(ctx),
             Err(e) => {
                 let msg = e.to_string();
@@ -171,6 +389,16 @@ extern "C" fn run_custom_function<F>(
     }
 }
 
+fn panic_message(payload: &Box<::std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&'static str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Box<Any>".to_string()
+    }
+}
+
 extern "C" fn destroy_boxed_fn<F>(data: *mut libc::c_void)
 where
     F: FnMut(&[*mut ffi::sqlite3_value]) -> QueryResult<SerializedValue> + Send + 'static,
@@ -178,3 +406,109 @@ where
     let ptr = data as *mut F;
     unsafe { Box::from_raw(ptr) };
 }
+
+#[allow(warnings)]
+extern "C" fn run_custom_function_with_context<F>(
+    ctx: *mut ffi::sqlite3_context,
+    num_args: libc::c_int,
+    value_ptr: *mut *mut ffi::sqlite3_value,
+) where
+    F: FnMut(&FunctionCallContext, &[*mut ffi::sqlite3_value]) -> QueryResult<SerializedValue>
+        + Send
+        + 'static,
+{
+    const NULL_DATA_ERR: &str = "An unknown error occurred. sqlite3_user_data returned a null pointer. This should never happen.";
+    unsafe {
+        let data_ptr = ffi::sqlite3_user_data(ctx);
+        let data_ptr = data_ptr as *mut F;
+        let f = match data_ptr.as_mut() {
+            Some(f) => f,
+            None => {
+                ffi::sqlite3_result_error(
+                    ctx,
+                    NULL_DATA_ERR.as_ptr() as *const _ as *const _,
+                    NULL_DATA_ERR.len() as _,
+                );
+                return;
+            }
+        };
+
+        let args = slice::from_raw_parts(value_ptr, num_args as _);
+        let call_context = FunctionCallContext { ctx };
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&call_context, args)))
+            .unwrap_or_else(|payload| {
+                let msg = panic_message(&payload);
+                Err(DatabaseError(
+                    DatabaseErrorKind::__Unknown,
+                    Box::new(format!("SQLite function panicked: {}", msg)),
+                ))
+            });
+        match result {
+            Ok(value) => value.result_of(ctx),
+            Err(e) => {
+                let msg = e.to_string();
+                ffi::sqlite3_result_error(ctx, msg.as_ptr() as *const _, msg.len() as _);
+            }
+        }
+    }
+}
+
+extern "C" fn destroy_boxed_fn_with_context<F>(data: *mut libc::c_void)
+where
+    F: FnMut(&FunctionCallContext, &[*mut ffi::sqlite3_value]) -> QueryResult<SerializedValue>
+        + Send
+        + 'static,
+{
+    let ptr = data as *mut F;
+    unsafe { Box::from_raw(ptr) };
+}
+
+#[allow(warnings)]
+extern "C" fn run_custom_function_direct<F>(
+    ctx: *mut ffi::sqlite3_context,
+    num_args: libc::c_int,
+    value_ptr: *mut *mut ffi::sqlite3_value,
+) where
+    F: FnMut(&FunctionCallContext, &[*mut ffi::sqlite3_value]) -> QueryResult<()> + Send + 'static,
+{
+    const NULL_DATA_ERR: &str = "An unknown error occurred. sqlite3_user_data returned a null pointer. This should never happen.";
+    unsafe {
+        let data_ptr = ffi::sqlite3_user_data(ctx);
+        let data_ptr = data_ptr as *mut F;
+        let f = match data_ptr.as_mut() {
+            Some(f) => f,
+            None => {
+                ffi::sqlite3_result_error(
+                    ctx,
+                    NULL_DATA_ERR.as_ptr() as *const _ as *const _,
+                    NULL_DATA_ERR.len() as _,
+                );
+                return;
+            }
+        };
+
+        let args = slice::from_raw_parts(value_ptr, num_args as _);
+        let call_context = FunctionCallContext { ctx };
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&call_context, args)))
+            .unwrap_or_else(|payload| {
+                let msg = panic_message(&payload);
+                Err(DatabaseError(
+                    DatabaseErrorKind::__Unknown,
+                    Box::new(format!("SQLite function panicked: {}", msg)),
+                ))
+            });
+        // On `Ok(())`, `f` already reported its result via `sqlite3_result_*` itself.
+        if let Err(e) = result {
+            let msg = e.to_string();
+            ffi::sqlite3_result_error(ctx, msg.as_ptr() as *const _, msg.len() as _);
+        }
+    }
+}
+
+extern "C" fn destroy_boxed_fn_direct<F>(data: *mut libc::c_void)
+where
+    F: FnMut(&FunctionCallContext, &[*mut ffi::sqlite3_value]) -> QueryResult<()> + Send + 'static,
+{
+    let ptr = data as *mut F;
+    unsafe { Box::from_raw(ptr) };
+}
@@ -0,0 +1,451 @@
+use std::collections::{HashMap, HashSet};
+
+use super::SqliteConnection;
+use prelude::*;
+use query_builder::functions::sql_query;
+use sql_types::{Bool, HasSqlType, Integer, NotNull, Nullable, Text};
+use sqlite::{Sqlite, SqliteType};
+
+/// A table in the current database, together with its columns, indexes, and foreign keys, as
+/// returned by [`SqliteConnection::schema`](struct.SqliteConnection.html#method.schema).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteTable {
+    /// The table's name.
+    pub name: String,
+    /// The table's columns, in declaration order.
+    pub columns: Vec<SqliteColumn>,
+    /// The table's indexes, including the implicit one backing a `PRIMARY KEY` if SQLite created
+    /// one for this table.
+    pub indexes: Vec<SqliteIndex>,
+    /// The table's foreign key constraints.
+    pub foreign_keys: Vec<SqliteForeignKey>,
+    /// The exact `CREATE TABLE` statement SQLite used to create this table, as recorded in
+    /// `sqlite_master`. `CHECK` constraints aren't reported by any `PRAGMA`, so this is the only
+    /// way to recover them; they appear verbatim in this text rather than as structured data.
+    pub definition_sql: Option<String>,
+}
+
+/// A single column, as reported by `PRAGMA table_info`.
+#[derive(Debug, Clone, PartialEq, Eq, QueryableByName)]
+pub struct SqliteColumn {
+    /// The column's position in the table, starting from 0.
+    #[column_name = "cid"]
+    #[sql_type = "Integer"]
+    pub position: i32,
+    /// The column's name.
+    #[sql_type = "Text"]
+    pub name: String,
+    /// The column's declared type, exactly as written in the `CREATE TABLE` statement. SQLite's
+    /// dynamic typing means this is a hint, not an enforced constraint.
+    #[column_name = "type"]
+    #[sql_type = "Text"]
+    pub sql_type: String,
+    /// Whether the column is declared `NOT NULL`.
+    #[column_name = "notnull"]
+    #[sql_type = "Bool"]
+    pub not_null: bool,
+    /// `0` if this column is not part of the table's `PRIMARY KEY`, otherwise its 1-based
+    /// position within it.
+    #[sql_type = "Integer"]
+    pub pk: i32,
+    /// The column's declared `DEFAULT` expression, exactly as written in the `CREATE TABLE`
+    /// statement, or `None` if the column has no default.
+    #[column_name = "dflt_value"]
+    #[sql_type = "Nullable<Text>"]
+    pub default_expr: Option<String>,
+}
+
+/// A single index, as reported by `PRAGMA index_list` and `PRAGMA index_info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteIndex {
+    /// The index's name.
+    pub name: String,
+    /// Whether the index enforces uniqueness.
+    pub unique: bool,
+    /// The names of the columns covered by this index, in index order.
+    pub columns: Vec<String>,
+}
+
+/// A single foreign key constraint, as reported by `PRAGMA foreign_key_list`.
+#[derive(Debug, Clone, PartialEq, Eq, QueryableByName)]
+pub struct SqliteForeignKey {
+    /// The table this key references.
+    #[column_name = "table"]
+    #[sql_type = "Text"]
+    pub table: String,
+    /// The local column participating in this key.
+    #[column_name = "from"]
+    #[sql_type = "Text"]
+    pub from: String,
+    /// The column on `table` this key references.
+    #[column_name = "to"]
+    #[sql_type = "Text"]
+    pub to: String,
+}
+
+#[derive(QueryableByName)]
+struct TableNameRow {
+    #[sql_type = "Text"]
+    name: String,
+    #[sql_type = "Nullable<Text>"]
+    sql: Option<String>,
+}
+
+#[derive(QueryableByName)]
+struct IndexListRow {
+    #[sql_type = "Text"]
+    name: String,
+    #[sql_type = "Bool"]
+    unique: bool,
+}
+
+#[derive(QueryableByName)]
+struct IndexInfoRow {
+    #[sql_type = "Text"]
+    name: String,
+}
+
+impl SqliteConnection {
+    /// Introspects the current database, returning structured information about its tables,
+    /// columns (with their declared types and nullability), primary keys, indexes, and foreign
+    /// keys.
+    ///
+    /// This queries `sqlite_master` for the list of tables, then `PRAGMA table_info`,
+    /// `PRAGMA index_list`, `PRAGMA index_info`, and `PRAGMA foreign_key_list` for each one. It's
+    /// intended for admin UIs and schema validation tooling, not for use in query planning.
+    pub fn schema(&self) -> QueryResult<Vec<SqliteTable>> {
+        let table_names = sql_query(
+            "SELECT name, sql FROM sqlite_master \
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' \
+             ORDER BY name",
+        ).load::<TableNameRow>(self)?;
+
+        table_names
+            .into_iter()
+            .map(|table| self.table_schema(&table.name, table.sql))
+            .collect()
+    }
+
+    fn table_schema(
+        &self,
+        table_name: &str,
+        definition_sql: Option<String>,
+    ) -> QueryResult<SqliteTable> {
+        let quoted = quote_identifier(table_name);
+        let columns =
+            sql_query(format!("PRAGMA table_info({})", quoted)).load::<SqliteColumn>(self)?;
+        let indexes = self.index_schema(table_name)?;
+        let foreign_keys = sql_query(format!("PRAGMA foreign_key_list({})", quoted))
+            .load::<SqliteForeignKey>(self)?;
+
+        Ok(SqliteTable {
+            name: table_name.to_string(),
+            columns,
+            indexes,
+            foreign_keys,
+            definition_sql,
+        })
+    }
+
+    fn index_schema(&self, table_name: &str) -> QueryResult<Vec<SqliteIndex>> {
+        let quoted = quote_identifier(table_name);
+        let index_list = sql_query(format!("PRAGMA index_list({})", quoted))
+            .load::<IndexListRow>(self)?;
+
+        index_list
+            .into_iter()
+            .map(|index| {
+                let quoted_index = quote_identifier(&index.name);
+                let columns = sql_query(format!("PRAGMA index_info({})", quoted_index))
+                    .load::<IndexInfoRow>(self)?
+                    .into_iter()
+                    .map(|column| column.name)
+                    .collect();
+                Ok(SqliteIndex {
+                    name: index.name,
+                    unique: index.unique,
+                    columns,
+                })
+            })
+            .collect()
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// A discrepancy between a `table!` declaration and the live database schema, as reported by
+/// [`SqliteConnection::validate_schema`](struct.SqliteConnection.html#method.validate_schema).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaMismatch {
+    /// The table itself was not found in the database.
+    MissingTable {
+        /// The expected table name.
+        table: String,
+    },
+    /// A column declared in the `table!` macro was not found in the table.
+    MissingColumn {
+        /// The table the column was expected on.
+        table: String,
+        /// The expected column name.
+        column: &'static str,
+    },
+    /// A column's declared SQLite type affinity doesn't match what the `table!` declaration
+    /// expects.
+    TypeMismatch {
+        /// The table the column belongs to.
+        table: String,
+        /// The column with the mismatched type.
+        column: &'static str,
+        /// The type affinity (`"INTEGER"`, `"TEXT"`, `"REAL"`, `"BLOB"`, or `"NUMERIC"`)
+        /// expected from the `table!` declaration.
+        expected: &'static str,
+        /// The column's actual declared type, as written in the `CREATE TABLE` statement.
+        actual: String,
+    },
+    /// A column's `NOT NULL` constraint doesn't match what the `table!` declaration expects.
+    NullabilityMismatch {
+        /// The table the column belongs to.
+        table: String,
+        /// The column with the mismatched nullability.
+        column: &'static str,
+        /// Whether the `table!` declaration expects this column to be `NOT NULL`.
+        expected_not_null: bool,
+    },
+}
+
+/// Maps a `table!`-declared column's `SqlType` to the SQLite type affinity and nullability it's
+/// expected to have at runtime. Implemented for every SQL type Diesel knows how to bind to
+/// SQLite, and for `Nullable<T>` of any such type.
+///
+/// This is normally used through the [`validate_table_schema!`](../macro.validate_table_schema.html)
+/// macro rather than called directly.
+pub trait ExpectedSqliteColumn {
+    /// The type affinity (`"INTEGER"`, `"TEXT"`, `"REAL"`, or `"BLOB"`) this column is expected
+    /// to have.
+    fn expected_type_affinity() -> &'static str;
+    /// Whether this column is expected to allow `NULL`.
+    fn expected_nullable() -> bool;
+}
+
+impl<T> ExpectedSqliteColumn for T
+where
+    T: NotNull,
+    Sqlite: HasSqlType<T>,
+{
+    fn expected_type_affinity() -> &'static str {
+        sqlite_type_affinity(<Sqlite as HasSqlType<T>>::metadata(&()))
+    }
+
+    fn expected_nullable() -> bool {
+        false
+    }
+}
+
+impl<T> ExpectedSqliteColumn for Nullable<T>
+where
+    T: NotNull,
+    Sqlite: HasSqlType<T>,
+{
+    fn expected_type_affinity() -> &'static str {
+        sqlite_type_affinity(<Sqlite as HasSqlType<T>>::metadata(&()))
+    }
+
+    fn expected_nullable() -> bool {
+        true
+    }
+}
+
+fn sqlite_type_affinity(ty: SqliteType) -> &'static str {
+    match ty {
+        SqliteType::Binary => "BLOB",
+        SqliteType::Text => "TEXT",
+        SqliteType::Float | SqliteType::Double => "REAL",
+        SqliteType::SmallInt | SqliteType::Integer | SqliteType::Long => "INTEGER",
+    }
+}
+
+/// Determines the [type affinity](https://sqlite.org/datatype3.html#determination_of_column_affinity)
+/// SQLite assigns to a column from its declared type, following the same substring rules SQLite
+/// itself uses.
+fn declared_type_affinity(declared_type: &str) -> &'static str {
+    let upper = declared_type.to_uppercase();
+    if upper.contains("INT") {
+        "INTEGER"
+    } else if upper.contains("CHAR") || upper.contains("CLOB") || upper.contains("TEXT") {
+        "TEXT"
+    } else if upper.contains("BLOB") || upper.is_empty() {
+        "BLOB"
+    } else if upper.contains("REAL") || upper.contains("FLOA") || upper.contains("DOUB") {
+        "REAL"
+    } else {
+        "NUMERIC"
+    }
+}
+
+impl SqliteConnection {
+    /// Compares `table`'s live schema against `expected_columns` -- normally built by the
+    /// [`validate_table_schema!`](../macro.validate_table_schema.html) macro from a `table!`
+    /// declaration -- and returns every discrepancy found. An empty result means the live schema
+    /// matches what Diesel expects.
+    ///
+    /// This is meant as a startup sanity check for services that open SQLite files they don't
+    /// fully control (e.g. ones provided by users or other processes), to fail fast with a clear
+    /// error instead of hitting confusing `FromSql`/`ToSql` failures later on.
+    pub fn validate_schema(
+        &self,
+        table: &str,
+        expected_columns: &[(&'static str, &'static str, bool)],
+    ) -> QueryResult<Vec<SchemaMismatch>> {
+        let live_table = match self.schema()?.into_iter().find(|t| t.name == table) {
+            Some(t) => t,
+            None => {
+                return Ok(vec![
+                    SchemaMismatch::MissingTable {
+                        table: table.to_string(),
+                    },
+                ])
+            }
+        };
+
+        let mut mismatches = Vec::new();
+        for &(column, expected_affinity, expected_nullable) in expected_columns {
+            match live_table.columns.iter().find(|c| c.name == column) {
+                None => mismatches.push(SchemaMismatch::MissingColumn {
+                    table: table.to_string(),
+                    column,
+                }),
+                Some(live_column) => {
+                    if declared_type_affinity(&live_column.sql_type) != expected_affinity {
+                        mismatches.push(SchemaMismatch::TypeMismatch {
+                            table: table.to_string(),
+                            column,
+                            expected: expected_affinity,
+                            actual: live_column.sql_type.clone(),
+                        });
+                    }
+                    // A lone `INTEGER PRIMARY KEY` is the rowid, which is never `NULL`, although
+                    // `PRAGMA table_info` does not report it as `NOT NULL`.
+                    let rowid_alias = live_column.pk != 0
+                        && live_column.sql_type.eq_ignore_ascii_case("INTEGER")
+                        && live_table.columns.iter().filter(|c| c.pk != 0).count() == 1;
+                    if (live_column.not_null || rowid_alias) != !expected_nullable {
+                        mismatches.push(SchemaMismatch::NullabilityMismatch {
+                            table: table.to_string(),
+                            column,
+                            expected_not_null: !expected_nullable,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+impl SqliteConnection {
+    /// Deletes every row from each of `tables`, ordering the deletes using foreign key
+    /// introspection so that a table is only cleared after every other table in the list that
+    /// references it has already been cleared. This is primarily useful for resetting a test
+    /// database between test runs.
+    ///
+    /// If `tables` contains a foreign key cycle, no ordering can satisfy every constraint, so
+    /// this falls back to disabling foreign key enforcement (`PRAGMA foreign_keys = OFF`) for
+    /// the duration of the deletes.
+    pub fn delete_all_in_dependency_order(&self, tables: &[&str]) -> QueryResult<()> {
+        let all_tables = self.schema()?;
+        let table_set: HashSet<&str> = tables.iter().cloned().collect();
+
+        let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut in_degree: HashMap<&str, usize> = HashMap::new();
+        for &table in tables {
+            successors.entry(table).or_insert_with(Vec::new);
+            in_degree.entry(table).or_insert(0);
+        }
+        for schema_table in &all_tables {
+            let from = schema_table.name.as_str();
+            if !table_set.contains(from) {
+                continue;
+            }
+            for fk in &schema_table.foreign_keys {
+                let to = fk.table.as_str();
+                if to != from && table_set.contains(to) {
+                    successors.get_mut(from).unwrap().push(to);
+                    *in_degree.get_mut(to).unwrap() += 1;
+                }
+            }
+        }
+
+        let mut ready: Vec<&str> = in_degree
+            .iter()
+            .filter(|&(_, &degree)| degree == 0)
+            .map(|(&table, _)| table)
+            .collect();
+        let mut order = Vec::new();
+        while let Some(table) = ready.pop() {
+            order.push(table);
+            for &successor in &successors[table] {
+                let degree = in_degree.get_mut(successor).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(successor);
+                }
+            }
+        }
+
+        if order.len() != tables.len() {
+            return self.delete_all_with_foreign_keys_disabled(tables);
+        }
+
+        for table in order {
+            try!(self.execute(&format!("DELETE FROM {}", quote_identifier(table))));
+        }
+        Ok(())
+    }
+
+    fn delete_all_with_foreign_keys_disabled(&self, tables: &[&str]) -> QueryResult<()> {
+        try!(self.execute("PRAGMA foreign_keys = OFF"));
+        let result = (|| -> QueryResult<()> {
+            for &table in tables {
+                try!(self.execute(&format!("DELETE FROM {}", quote_identifier(table))));
+            }
+            Ok(())
+        })();
+        try!(self.execute("PRAGMA foreign_keys = ON"));
+        result
+    }
+}
+
+/// Builds the `expected_columns` argument for
+/// [`SqliteConnection::validate_schema`](sqlite/struct.SqliteConnection.html#method.validate_schema)
+/// from a `table!`-declared module and the columns to check it against.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../../doctest_setup.rs");
+/// # use schema::users;
+/// # fn main() {
+/// #     let connection = establish_connection();
+/// let mismatches = connection
+///     .validate_schema("users", &validate_table_schema!(users, [id, name]))
+///     .unwrap();
+/// assert!(mismatches.is_empty());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! validate_table_schema {
+    ($table_mod:ident, [$($column:ident),+ $(,)*]) => {
+        [
+            $((
+                stringify!($column),
+                <<$table_mod::$column as $crate::expression::Expression>::SqlType
+                    as $crate::sqlite::ExpectedSqliteColumn>::expected_type_affinity(),
+                <<$table_mod::$column as $crate::expression::Expression>::SqlType
+                    as $crate::sqlite::ExpectedSqliteColumn>::expected_nullable(),
+            )),+
+        ]
+    };
+}
@@ -5,7 +5,7 @@ use std::os::raw as libc;
 use std::{slice, str};
 
 use row::*;
-use sqlite::Sqlite;
+use sqlite::{Sqlite, SqliteType};
 use util::NonNull;
 
 #[allow(missing_debug_implementations, missing_copy_implementations)]
@@ -29,6 +29,10 @@ impl SqliteValue {
         })
     }
 
+    /// Borrows the column's text representation directly out of SQLite,
+    /// without copying it. The returned `&str` is only valid for the
+    /// lifetime of `self`; `FromSql` impls that need an owned `String`
+    /// still have to copy it out before returning.
     pub fn read_text(&self) -> &str {
         unsafe {
             let ptr = ffi::sqlite3_value_text(self.value());
@@ -38,6 +42,9 @@ impl SqliteValue {
         }
     }
 
+    /// Borrows the column's blob representation directly out of SQLite,
+    /// without copying it. See [`read_text`](#method.read_text) for the
+    /// lifetime caveat.
     pub fn read_blob(&self) -> &[u8] {
         unsafe {
             let ptr = ffi::sqlite3_value_blob(self.value());
@@ -63,6 +70,85 @@ impl SqliteValue {
         tpe == ffi::SQLITE_NULL
     }
 
+    /// Returns SQLite's own runtime storage class for this value
+    /// (`sqlite3_value_type`), for callers that need to branch on it before
+    /// deciding which `as_*`/`read_*` method to call -- e.g. a custom
+    /// `FromSql` impl or a dynamic row consumer that doesn't know the
+    /// column's declared SQL type ahead of time.
+    pub fn value_type(&self) -> Option<SqliteType> {
+        unsafe {
+            match ffi::sqlite3_value_type(self.value()) {
+                ffi::SQLITE_INTEGER => Some(SqliteType::Long),
+                ffi::SQLITE_FLOAT => Some(SqliteType::Double),
+                ffi::SQLITE_TEXT => Some(SqliteType::Text),
+                ffi::SQLITE_BLOB => Some(SqliteType::Binary),
+                _ => None,
+            }
+        }
+    }
+
+    /// Like [`read_long`](#method.read_long), but returns `None` instead of
+    /// coercing a SQL `NULL` to `0`.
+    pub fn as_i64(&self) -> Option<i64> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self.read_long())
+        }
+    }
+
+    /// Like [`read_double`](#method.read_double), but returns `None` instead
+    /// of coercing a SQL `NULL` to `0.0`.
+    pub fn as_f64(&self) -> Option<f64> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self.read_double())
+        }
+    }
+
+    /// Like [`read_text`](#method.read_text), but returns `None` for a SQL
+    /// `NULL` instead of an empty string.
+    pub fn as_str(&self) -> Option<&str> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self.read_text())
+        }
+    }
+
+    /// Like [`read_blob`](#method.read_blob), but returns `None` for a SQL
+    /// `NULL` instead of an empty slice.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self.read_blob())
+        }
+    }
+
+    /// Reads this value using SQLite's own runtime type tag, rather than a
+    /// statically declared SQL type.
+    ///
+    /// SQLite is the only backend where this is possible: a column's storage
+    /// class is attached to each individual value, unlike Postgres and MySQL
+    /// where a raw value is just untagged bytes.
+    #[cfg(feature = "serde")]
+    pub(crate) fn dynamic_value(&self) -> ::dynamic_value::DynamicValue {
+        use dynamic_value::DynamicValue;
+
+        unsafe {
+            match ffi::sqlite3_value_type(self.value()) {
+                ffi::SQLITE_NULL => DynamicValue::Null,
+                ffi::SQLITE_INTEGER => DynamicValue::Integer(self.read_long()),
+                ffi::SQLITE_FLOAT => DynamicValue::Double(self.read_double()),
+                ffi::SQLITE_TEXT => DynamicValue::Text(self.read_text().to_owned()),
+                ffi::SQLITE_BLOB => DynamicValue::Binary(self.read_blob().to_owned()),
+                _ => DynamicValue::Null,
+            }
+        }
+    }
+
     fn value(&self) -> *mut ffi::sqlite3_value {
         &self.value as *const _ as _
     }
@@ -120,4 +206,19 @@ impl<'a> NamedRow<Sqlite> for SqliteNamedRow<'a> {
             SqliteValue::new(ptr)
         }
     }
+
+    fn column_count(&self) -> usize {
+        unsafe { ffi::sqlite3_column_count(self.stmt.as_ptr()) as usize }
+    }
+
+    fn column_name(&self, index: usize) -> Option<&str> {
+        unsafe {
+            let ptr = ffi::sqlite3_column_name(self.stmt.as_ptr(), index as libc::c_int);
+            if ptr.is_null() {
+                None
+            } else {
+                str::from_utf8(std::ffi::CStr::from_ptr(ptr).to_bytes()).ok()
+            }
+        }
+    }
 }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use super::stmt::StatementUse;
+use super::SqliteValue;
 use deserialize::{FromSqlRow, Queryable, QueryableByName};
 use result::Error::DeserializationError;
 use result::QueryResult;
@@ -19,6 +20,15 @@ impl<'a, ST, T> StatementIterator<'a, ST, T> {
             _marker: PhantomData,
         }
     }
+
+    /// Returns the raw value of the current row's column at `idx`, so a
+    /// generic exporter can read most columns through `T`'s `Queryable` impl
+    /// and still inspect specific ones untyped, without a second query.
+    /// Only meaningful between a `next()` call that returned `Some` and the
+    /// next call to `next()`.
+    pub fn get_raw_value(&self, idx: usize) -> Option<&SqliteValue> {
+        self.stmt.get_raw_value(idx)
+    }
 }
 
 impl<'a, ST, T> Iterator for StatementIterator<'a, ST, T>
@@ -62,6 +72,20 @@ impl<'a, T> NamedStatementIterator<'a, T> {
             _marker: PhantomData,
         })
     }
+
+    /// Returns the raw value of the current row's column at `idx`. See
+    /// [`StatementIterator::get_raw_value`](struct.StatementIterator.html#method.get_raw_value).
+    pub fn get_raw_value(&self, idx: usize) -> Option<&SqliteValue> {
+        self.stmt.get_raw_value(idx)
+    }
+
+    /// Like [`get_raw_value`](#method.get_raw_value), but by column name
+    /// instead of index.
+    pub fn get_raw_value_by_name(&self, name: &str) -> Option<&SqliteValue> {
+        self.column_indices
+            .get(name)
+            .and_then(|&idx| self.get_raw_value(idx))
+    }
 }
 
 impl<'a, T> Iterator for NamedStatementIterator<'a, T>
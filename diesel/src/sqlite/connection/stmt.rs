@@ -1,5 +1,6 @@
 extern crate libsqlite3_sys as ffi;
 
+use std::any::Any;
 use std::ffi::{CStr, CString};
 use std::io::{stderr, Write};
 use std::os::raw as libc;
@@ -7,7 +8,7 @@ use std::ptr;
 
 use super::raw::RawConnection;
 use super::serialized_value::SerializedValue;
-use super::sqlite_value::SqliteRow;
+use super::sqlite_value::{SqliteRow, SqliteValue};
 use result::Error::DatabaseError;
 use result::*;
 use sqlite::SqliteType;
@@ -18,6 +19,191 @@ pub struct Statement {
     bind_index: libc::c_int,
 }
 
+// Not present in the bindgen output of the pinned `libsqlite3-sys` version, so
+// we declare them by hand. Values taken from the `SQLITE_STMTSTATUS_*` `#define`s
+// in `sqlite3.h`.
+const SQLITE_STMTSTATUS_VM_STEP: libc::c_int = 4;
+const SQLITE_STMTSTATUS_RUN: libc::c_int = 6;
+const SQLITE_STMTSTATUS_MEMUSED: libc::c_int = 99;
+
+// `SQLITE_BUSY_SNAPSHOT` is an extended result code kept private inside
+// `libsqlite3_sys::error` rather than being re-exported from `ffi` in the
+// pinned sys crate version, so we declare it by hand instead. Value taken
+// from `sqlite3.h`: `SQLITE_BUSY | (2<<8)`.
+const SQLITE_BUSY_SNAPSHOT: libc::c_int = ffi::SQLITE_BUSY | (2 << 8);
+
+extern "C" {
+    // Not present in the bindgen output of the pinned `libsqlite3-sys`
+    // version, so we declare it by hand. Signature taken from
+    // https://www.sqlite.org/c3ref/expanded_sql.html.
+    fn sqlite3_expanded_sql(pStmt: *mut ffi::sqlite3_stmt) -> *mut libc::c_char;
+}
+
+/// Per-statement performance counters from
+/// [`sqlite3_stmt_status`](https://www.sqlite.org/c3ref/stmt_status.html), for identifying hot,
+/// badly-indexed queries in production. See
+/// [`SqliteConnection::statement_cache_stats`](../struct.SqliteConnection.html#method.statement_cache_stats).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatementStatus {
+    /// Number of times SQLite stepped a full table scan, per `SQLITE_STMTSTATUS_FULLSCAN_STEP`.
+    pub fullscan_step: i32,
+    /// Number of sort operations, per `SQLITE_STMTSTATUS_SORT`.
+    pub sort: i32,
+    /// Number of automatic indexes created to satisfy a query, per `SQLITE_STMTSTATUS_AUTOINDEX`.
+    pub autoindex: i32,
+    /// Number of virtual machine operations executed, per `SQLITE_STMTSTATUS_VM_STEP`.
+    pub vm_step: i32,
+    /// Number of times this statement has been run to completion, per `SQLITE_STMTSTATUS_RUN`.
+    pub run: i32,
+    /// Approximate number of bytes of heap memory used by this statement, per
+    /// `SQLITE_STMTSTATUS_MEMUSED`.
+    pub mem_used: i32,
+}
+
+/// A structured mapping of SQLite's
+/// [extended result codes](https://www.sqlite.org/rescode.html#extrc), for
+/// callers that need to branch on the precise cause of a
+/// [`DatabaseError`](../../result/enum.Error.html#variant.DatabaseError)
+/// instead of only its human-readable message.
+///
+/// Obtained via
+/// [`SqliteErrorInformation::extended_code`](struct.SqliteErrorInformation.html#method.extended_code).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteErrorCode {
+    /// `SQLITE_BUSY_SNAPSHOT`: a `BEGIN CONCURRENT` (or similar snapshot-based)
+    /// transaction could not be started because the snapshot is stale.
+    BusySnapshot,
+    /// Any other `SQLITE_BUSY_*` code, or plain `SQLITE_BUSY`.
+    Busy,
+    /// Any `SQLITE_LOCKED_*` code, or plain `SQLITE_LOCKED`: this connection is blocked on a
+    /// lock held by another connection sharing the same database handle.
+    Locked,
+    /// Any `SQLITE_IOERR_*` code, or plain `SQLITE_IOERR`.
+    IoErr,
+    /// `SQLITE_CONSTRAINT_UNIQUE`.
+    ConstraintUnique,
+    /// `SQLITE_CONSTRAINT_PRIMARYKEY`.
+    ConstraintPrimaryKey,
+    /// `SQLITE_CONSTRAINT_FOREIGNKEY`.
+    ConstraintForeignKey,
+    /// `SQLITE_CONSTRAINT_NOTNULL`.
+    ConstraintNotNull,
+    /// `SQLITE_CONSTRAINT_CHECK`.
+    ConstraintCheck,
+    /// `SQLITE_CONSTRAINT_TRIGGER`.
+    ConstraintTrigger,
+    /// Any other `SQLITE_CONSTRAINT_*` code, or plain `SQLITE_CONSTRAINT`.
+    Constraint,
+    /// Any `SQLITE_CORRUPT_*` code, or plain `SQLITE_CORRUPT`.
+    Corrupt,
+    /// An extended result code this mapping doesn't recognize yet. Carries
+    /// the raw code returned by `sqlite3_extended_errcode`.
+    Unknown(i32),
+}
+
+impl SqliteErrorCode {
+    fn from_extended_errcode(code: libc::c_int) -> Self {
+        match code {
+            SQLITE_BUSY_SNAPSHOT => SqliteErrorCode::BusySnapshot,
+            ffi::SQLITE_CONSTRAINT_UNIQUE => SqliteErrorCode::ConstraintUnique,
+            ffi::SQLITE_CONSTRAINT_PRIMARYKEY => SqliteErrorCode::ConstraintPrimaryKey,
+            ffi::SQLITE_CONSTRAINT_FOREIGNKEY => SqliteErrorCode::ConstraintForeignKey,
+            ffi::SQLITE_CONSTRAINT_NOTNULL => SqliteErrorCode::ConstraintNotNull,
+            ffi::SQLITE_CONSTRAINT_CHECK => SqliteErrorCode::ConstraintCheck,
+            ffi::SQLITE_CONSTRAINT_TRIGGER => SqliteErrorCode::ConstraintTrigger,
+            _ if code & 0xff == ffi::SQLITE_CONSTRAINT => SqliteErrorCode::Constraint,
+            _ if code & 0xff == ffi::SQLITE_IOERR => SqliteErrorCode::IoErr,
+            _ if code & 0xff == ffi::SQLITE_CORRUPT => SqliteErrorCode::Corrupt,
+            _ if code & 0xff == ffi::SQLITE_BUSY => SqliteErrorCode::Busy,
+            _ if code & 0xff == ffi::SQLITE_LOCKED => SqliteErrorCode::Locked,
+            _ => SqliteErrorCode::Unknown(code as i32),
+        }
+    }
+}
+
+/// Backend-specific [`DatabaseErrorInformation`](../../result/trait.DatabaseErrorInformation.html)
+/// for SQLite, carrying the [`SqliteErrorCode`](enum.SqliteErrorCode.html) alongside the plain
+/// error message. Recovered from a `DatabaseError`'s boxed information via
+/// `.as_any().downcast_ref::<SqliteErrorInformation>()`.
+#[derive(Debug, Clone)]
+pub struct SqliteErrorInformation {
+    message: String,
+    extended_code: SqliteErrorCode,
+    sql: Option<String>,
+    table_name: Option<String>,
+    column_name: Option<String>,
+    constraint_name: Option<String>,
+}
+
+impl SqliteErrorInformation {
+    /// The extended result code SQLite reported for this error.
+    pub fn extended_code(&self) -> SqliteErrorCode {
+        self.extended_code
+    }
+
+    /// The text of the statement that failed, if one was available at the
+    /// point of failure. Where possible this is the statement with its bound
+    /// parameters substituted in place (see
+    /// [`Statement::expanded_sql`](struct.Statement.html#method.expanded_sql)), so a production
+    /// error report identifies the failing query without needing separate
+    /// logging correlation.
+    pub fn statement_sql(&self) -> Option<&str> {
+        self.sql.as_ref().map(|s| s.as_str())
+    }
+}
+
+impl DatabaseErrorInformation for SqliteErrorInformation {
+    fn message(&self) -> &str {
+        &self.message
+    }
+    fn details(&self) -> Option<&str> {
+        None
+    }
+    fn hint(&self) -> Option<&str> {
+        None
+    }
+    fn table_name(&self) -> Option<&str> {
+        self.table_name.as_ref().map(|s| s.as_str())
+    }
+    fn column_name(&self) -> Option<&str> {
+        self.column_name.as_ref().map(|s| s.as_str())
+    }
+    fn constraint_name(&self) -> Option<&str> {
+        self.constraint_name.as_ref().map(|s| s.as_str())
+    }
+    fn as_any(&self) -> &Any {
+        self
+    }
+}
+
+/// SQLite only reports the table/column a `UNIQUE` or `NOT NULL` constraint
+/// applies to as part of its error message text (e.g. `"UNIQUE constraint
+/// failed: users.email"`), rather than as separate structured fields the way
+/// Postgres does. Parses the first `table.column` pair out of such a message.
+fn parse_table_and_column(message: &str) -> (Option<String>, Option<String>) {
+    let names = match message.find(": ") {
+        Some(idx) => &message[idx + 2..],
+        None => return (None, None),
+    };
+    match names.split(',').next().map(|s| s.trim()) {
+        Some(first) => match first.rfind('.') {
+            Some(dot) => (
+                Some(first[..dot].to_string()),
+                Some(first[dot + 1..].to_string()),
+            ),
+            None => (None, None),
+        },
+        None => (None, None),
+    }
+}
+
+/// SQLite reports a `CHECK` constraint's name (or the table name, if the
+/// constraint wasn't given one) as free text after a colon, e.g. `"CHECK
+/// constraint failed: users"`.
+fn parse_constraint_name(message: &str) -> Option<String> {
+    message.find(": ").map(|idx| message[idx + 2..].trim().to_string())
+}
+
 impl Statement {
     pub fn prepare(raw_connection: &RawConnection, sql: &str) -> QueryResult<Self> {
         let mut stmt = ptr::null_mut();
@@ -32,7 +218,11 @@ impl Statement {
             )
         };
 
-        ensure_sqlite_ok(prepare_result, raw_connection.internal_connection.as_ptr()).map(|_| {
+        ensure_sqlite_ok(
+            prepare_result,
+            raw_connection.internal_connection.as_ptr(),
+            Some(sql.to_string()),
+        ).map(|_| {
             Statement {
                 inner_statement: unsafe { NonNull::new_unchecked(stmt) },
                 bind_index: 0,
@@ -52,7 +242,7 @@ impl Statement {
         };
         let result = value.bind_to(self.inner_statement, self.bind_index);
 
-        ensure_sqlite_ok(result, self.raw_connection())
+        ensure_sqlite_ok(result, self.raw_connection(), self.sql())
     }
 
     fn num_fields(&self) -> usize {
@@ -70,11 +260,99 @@ impl Statement {
         }
     }
 
+    /// The number of columns this statement's result set will have.
+    pub fn column_count(&self) -> usize {
+        self.num_fields()
+    }
+
+    /// The name assigned to column `idx` in the result set (its alias if one
+    /// was given, otherwise the column name itself).
+    pub fn column_name(&self, idx: usize) -> Option<String> {
+        unsafe {
+            self.field_name(idx)
+                .map(|s| s.to_string_lossy().into_owned())
+        }
+    }
+
+    /// The declared type of column `idx`, as written in the `CREATE TABLE`
+    /// statement for the source table -- e.g. `"INTEGER"` or `"VARCHAR(255)"`.
+    /// Returns `None` for columns that aren't a direct reference to a table
+    /// column (computed expressions, subqueries, `SELECT *` from a view, ...),
+    /// since SQLite has no declared type to report for those.
+    pub fn column_decltype(&self, idx: usize) -> Option<String> {
+        unsafe {
+            let ptr = ffi::sqlite3_column_decltype(self.inner_statement.as_ptr(), idx as libc::c_int);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    /// The number of bind parameters (e.g. `?`, `?1`, `:name`) in this
+    /// statement.
+    pub fn parameter_count(&self) -> usize {
+        unsafe { ffi::sqlite3_bind_parameter_count(self.inner_statement.as_ptr()) as usize }
+    }
+
+    /// Whether this statement is guaranteed not to modify the database,
+    /// per [`sqlite3_stmt_readonly`](https://www.sqlite.org/c3ref/stmt_readonly.html).
+    pub fn is_readonly(&self) -> bool {
+        unsafe { ffi::sqlite3_stmt_readonly(self.inner_statement.as_ptr()) != 0 }
+    }
+
+    /// The original SQL text this statement was prepared from, per
+    /// [`sqlite3_sql`](https://www.sqlite.org/c3ref/expanded_sql.html), for identifying a cached
+    /// statement in [`SqliteConnection::statement_cache_stats`](../struct.SqliteConnection.html#method.statement_cache_stats).
+    pub fn sql(&self) -> Option<String> {
+        unsafe {
+            let ptr = ffi::sqlite3_sql(self.inner_statement.as_ptr());
+            if ptr.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    /// Reads this statement's [`sqlite3_stmt_status`](https://www.sqlite.org/c3ref/stmt_status.html)
+    /// performance counters, optionally resetting them to zero afterward.
+    pub fn status(&self, reset: bool) -> StatementStatus {
+        let reset = reset as libc::c_int;
+        let stmt = self.inner_statement.as_ptr();
+        unsafe {
+            StatementStatus {
+                fullscan_step: ffi::sqlite3_stmt_status(
+                    stmt,
+                    ffi::SQLITE_STMTSTATUS_FULLSCAN_STEP,
+                    reset,
+                ),
+                sort: ffi::sqlite3_stmt_status(stmt, ffi::SQLITE_STMTSTATUS_SORT, reset),
+                autoindex: ffi::sqlite3_stmt_status(stmt, ffi::SQLITE_STMTSTATUS_AUTOINDEX, reset),
+                vm_step: ffi::sqlite3_stmt_status(stmt, SQLITE_STMTSTATUS_VM_STEP, reset),
+                run: ffi::sqlite3_stmt_status(stmt, SQLITE_STMTSTATUS_RUN, reset),
+                mem_used: ffi::sqlite3_stmt_status(stmt, SQLITE_STMTSTATUS_MEMUSED, reset),
+            }
+        }
+    }
+
+    /// Returns the raw value of column `idx` in the row the statement is
+    /// currently positioned on (i.e. after a `step` call has returned
+    /// `Some`), independent of `SqliteRow::take`'s sequential cursor.
+    unsafe fn column_value<'a>(&self, idx: usize) -> Option<&'a SqliteValue> {
+        let ptr = ffi::sqlite3_column_value(self.inner_statement.as_ptr(), idx as libc::c_int);
+        SqliteValue::new(ptr)
+    }
+
     fn step(&mut self) -> QueryResult<Option<SqliteRow>> {
         match unsafe { ffi::sqlite3_step(self.inner_statement.as_ptr()) } {
             ffi::SQLITE_DONE => Ok(None),
             ffi::SQLITE_ROW => Ok(Some(SqliteRow::new(self.inner_statement))),
-            _ => Err(last_error(self.raw_connection())),
+            _ => Err(last_error(
+                self.raw_connection(),
+                self.expanded_sql().or_else(|| self.sql()),
+            )),
         }
     }
 
@@ -83,29 +361,72 @@ impl Statement {
         unsafe { ffi::sqlite3_reset(self.inner_statement.as_ptr()) };
     }
 
+    /// Returns the SQL text of this statement with any bound parameters
+    /// substituted in place, as SQLite would expand them for `EXPLAIN` or
+    /// tracing purposes.
+    ///
+    /// Returns `None` if SQLite is unable to allocate memory for the
+    /// expanded string, or if it exceeds SQLite's `SQLITE_LIMIT_LENGTH`.
+    pub fn expanded_sql(&self) -> Option<String> {
+        let ptr = unsafe { sqlite3_expanded_sql(self.inner_statement.as_ptr()) };
+        if ptr.is_null() {
+            None
+        } else {
+            let sql = unsafe { CStr::from_ptr(ptr) }
+                .to_string_lossy()
+                .into_owned();
+            unsafe { ffi::sqlite3_free(ptr as *mut libc::c_void) };
+            Some(sql)
+        }
+    }
+
     fn raw_connection(&self) -> *mut ffi::sqlite3 {
         unsafe { ffi::sqlite3_db_handle(self.inner_statement.as_ptr()) }
     }
 }
 
-fn ensure_sqlite_ok(code: libc::c_int, raw_connection: *mut ffi::sqlite3) -> QueryResult<()> {
+fn ensure_sqlite_ok(
+    code: libc::c_int,
+    raw_connection: *mut ffi::sqlite3,
+    sql: Option<String>,
+) -> QueryResult<()> {
     if code == ffi::SQLITE_OK {
         Ok(())
     } else {
-        Err(last_error(raw_connection))
+        Err(last_error(raw_connection, sql))
     }
 }
 
-fn last_error(raw_connection: *mut ffi::sqlite3) -> Error {
+fn last_error(raw_connection: *mut ffi::sqlite3, sql: Option<String>) -> Error {
     let error_message = last_error_message(raw_connection);
-    let error_information = Box::new(error_message);
-    let error_kind = match last_error_code(raw_connection) {
-        ffi::SQLITE_CONSTRAINT_UNIQUE | ffi::SQLITE_CONSTRAINT_PRIMARYKEY => {
+    let extended_code = SqliteErrorCode::from_extended_errcode(last_error_code(raw_connection));
+    let error_kind = match extended_code {
+        SqliteErrorCode::ConstraintUnique | SqliteErrorCode::ConstraintPrimaryKey => {
             DatabaseErrorKind::UniqueViolation
         }
-        ffi::SQLITE_CONSTRAINT_FOREIGNKEY => DatabaseErrorKind::ForeignKeyViolation,
+        SqliteErrorCode::ConstraintForeignKey => DatabaseErrorKind::ForeignKeyViolation,
+        SqliteErrorCode::ConstraintNotNull => DatabaseErrorKind::NotNullViolation,
+        SqliteErrorCode::ConstraintCheck => DatabaseErrorKind::CheckViolation,
         _ => DatabaseErrorKind::__Unknown,
     };
+    let (table_name, column_name, constraint_name) = match extended_code {
+        SqliteErrorCode::ConstraintUnique
+        | SqliteErrorCode::ConstraintPrimaryKey
+        | SqliteErrorCode::ConstraintNotNull => {
+            let (table_name, column_name) = parse_table_and_column(&error_message);
+            (table_name, column_name, None)
+        }
+        SqliteErrorCode::ConstraintCheck => (None, None, parse_constraint_name(&error_message)),
+        _ => (None, None, None),
+    };
+    let error_information = Box::new(SqliteErrorInformation {
+        message: error_message,
+        extended_code,
+        sql,
+        table_name,
+        column_name,
+        constraint_name,
+    });
     DatabaseError(error_kind, error_information)
 }
 
@@ -123,8 +444,9 @@ impl Drop for Statement {
         use std::thread::panicking;
 
         let conn = self.raw_connection();
+        let sql = self.sql();
         let finalize_result = unsafe { ffi::sqlite3_finalize(self.inner_statement.as_ptr()) };
-        if let Err(e) = ensure_sqlite_ok(finalize_result, conn) {
+        if let Err(e) = ensure_sqlite_ok(finalize_result, conn, sql) {
             if panicking() {
                 write!(
                     stderr(),
@@ -164,6 +486,42 @@ impl<'a> StatementUse<'a> {
     pub fn field_name(&self, idx: usize) -> Option<&'a CStr> {
         unsafe { self.statement.field_name(idx) }
     }
+
+    /// Returns the raw value of column `idx` in the row the statement is
+    /// currently positioned on, for callers that want to read some columns
+    /// through their `FromSql` impl and inspect others (or all of them) as
+    /// raw `SqliteValue`s within the same step.
+    pub fn get_raw_value(&self, idx: usize) -> Option<&'a SqliteValue> {
+        unsafe { self.statement.column_value(idx) }
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.statement.column_count()
+    }
+
+    pub fn column_name(&self, idx: usize) -> Option<String> {
+        self.statement.column_name(idx)
+    }
+
+    pub fn column_decltype(&self, idx: usize) -> Option<String> {
+        self.statement.column_decltype(idx)
+    }
+
+    pub fn parameter_count(&self) -> usize {
+        self.statement.parameter_count()
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.statement.is_readonly()
+    }
+
+    pub fn sql(&self) -> Option<String> {
+        self.statement.sql()
+    }
+
+    pub fn status(&self, reset: bool) -> StatementStatus {
+        self.statement.status(reset)
+    }
 }
 
 impl<'a> Drop for StatementUse<'a> {
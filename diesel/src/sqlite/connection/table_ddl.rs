@@ -0,0 +1,157 @@
+use query_builder::{QueryBuilder, QueryFragment};
+use query_source::{Column, Table};
+use sql_types::{self, NotNull, Nullable};
+use sqlite::query_builder::SqliteQueryBuilder;
+use sqlite::Sqlite;
+
+/// Maps a column's SQL type to the type name SQLite expects in a `CREATE TABLE` statement.
+///
+/// SQLite is dynamically typed and mostly ignores declared column types (they only ever
+/// influence its "type affinity" rules), but a real type name still makes a generated schema
+/// readable, and lets [`create_table_ddl`](fn.create_table_ddl.html) round-trip the types
+/// declared in a `table!` block.
+pub trait SqliteDdlType {
+    /// The type name to use in the `CREATE TABLE` column definition.
+    const SQL_NAME: &'static str;
+    /// Whether this column should be declared `NOT NULL`.
+    const NOT_NULL: bool;
+}
+
+macro_rules! not_null_sqlite_ddl_type {
+    ($ty:ty => $name:expr) => {
+        impl SqliteDdlType for $ty {
+            const SQL_NAME: &'static str = $name;
+            const NOT_NULL: bool = true;
+        }
+    };
+}
+
+not_null_sqlite_ddl_type!(sql_types::Bool => "BOOLEAN");
+not_null_sqlite_ddl_type!(sql_types::SmallInt => "SMALLINT");
+not_null_sqlite_ddl_type!(sql_types::Integer => "INTEGER");
+not_null_sqlite_ddl_type!(sql_types::BigInt => "BIGINT");
+not_null_sqlite_ddl_type!(sql_types::Float => "FLOAT");
+not_null_sqlite_ddl_type!(sql_types::Double => "DOUBLE");
+not_null_sqlite_ddl_type!(sql_types::Text => "TEXT");
+not_null_sqlite_ddl_type!(sql_types::Binary => "BLOB");
+not_null_sqlite_ddl_type!(sql_types::Date => "DATE");
+not_null_sqlite_ddl_type!(sql_types::Time => "TIME");
+not_null_sqlite_ddl_type!(sql_types::Timestamp => "TIMESTAMP");
+
+impl<ST> SqliteDdlType for Nullable<ST>
+where
+    ST: NotNull + SqliteDdlType,
+{
+    const SQL_NAME: &'static str = ST::SQL_NAME;
+    const NOT_NULL: bool = false;
+}
+
+/// A column, or tuple of columns, whose SQL types are known to
+/// [`SqliteDdlType`](trait.SqliteDdlType.html), and can therefore be rendered as `CREATE TABLE`
+/// column definitions by [`create_table_ddl`](fn.create_table_ddl.html).
+pub trait DdlColumnList {
+    /// The table these columns belong to
+    type Table;
+
+    /// Render each column of this list as a `"name" TYPE [NOT NULL]` fragment.
+    fn ddl_fragments() -> Vec<String>;
+}
+
+impl<C> DdlColumnList for C
+where
+    C: Column,
+    C::SqlType: SqliteDdlType,
+{
+    type Table = <C as Column>::Table;
+
+    fn ddl_fragments() -> Vec<String> {
+        vec![format!(
+            "\"{}\" {}{}",
+            C::NAME.replace('"', "\"\""),
+            <C::SqlType as SqliteDdlType>::SQL_NAME,
+            if <C::SqlType as SqliteDdlType>::NOT_NULL {
+                " NOT NULL"
+            } else {
+                ""
+            },
+        )]
+    }
+}
+
+macro_rules! ddl_column_list_tuple {
+    ($($T:ident,)+) => {
+        impl<$($T,)+ Tab> DdlColumnList for ($($T,)+)
+        where
+            $($T: DdlColumnList<Table = Tab>,)+
+        {
+            type Table = Tab;
+
+            fn ddl_fragments() -> Vec<String> {
+                let mut result = Vec::new();
+                $(result.extend($T::ddl_fragments());)+
+                result
+            }
+        }
+    };
+}
+
+ddl_column_list_tuple!(T0,);
+ddl_column_list_tuple!(T0, T1,);
+ddl_column_list_tuple!(T0, T1, T2,);
+ddl_column_list_tuple!(T0, T1, T2, T3,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6, T7,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14,);
+ddl_column_list_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15,);
+
+/// Generates the `CREATE TABLE` statement for `table`, using `columns` (typically
+/// `table::all_columns`) to determine the column names, types, and nullability.
+///
+/// This is meant for bootstrapping fresh SQLite files or test databases straight from a
+/// `table!` declaration, without hand-writing or checking in the equivalent DDL. Primary keys,
+/// defaults, and constraints declared outside of the `table!` macro (see
+/// [`SqliteTable::definition_sql`](struct.SqliteTable.html#structfield.definition_sql)) are not
+/// represented in a `table!` block, so they are not part of the generated statement.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../../doctest_setup.rs");
+/// # use diesel::sqlite::create_table_ddl;
+/// # use schema::users;
+/// # fn main() {
+/// let ddl = create_table_ddl(users::table, users::all_columns);
+/// assert_eq!(
+///     ddl,
+///     "CREATE TABLE `users` (\"id\" INTEGER NOT NULL, \"name\" TEXT NOT NULL)"
+/// );
+/// # }
+/// ```
+pub fn create_table_ddl<Tab, Cols>(table: Tab, _columns: Cols) -> String
+where
+    Tab: Table,
+    Tab::FromClause: QueryFragment<Sqlite>,
+    Cols: DdlColumnList<Table = Tab>,
+{
+    let mut query_builder = SqliteQueryBuilder::new();
+    table
+        .from_clause()
+        .to_sql(&mut query_builder)
+        .expect("Rendering a table name should never fail");
+    let table_name = query_builder.finish();
+
+    format!(
+        "CREATE TABLE {} ({})",
+        table_name,
+        Cols::ddl_fragments().join(", "),
+    )
+}
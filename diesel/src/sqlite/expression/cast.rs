@@ -0,0 +1,24 @@
+use expression::cast::SqlTypeName;
+use sql_types::*;
+use sqlite::Sqlite;
+
+macro_rules! impl_sqlite_sql_type_name {
+    ($ty:ty, $name:expr) => {
+        impl SqlTypeName<Sqlite> for $ty {
+            const SQL_TYPE_NAME: &'static str = $name;
+        }
+    };
+}
+
+impl_sqlite_sql_type_name!(Bool, "INTEGER");
+impl_sqlite_sql_type_name!(SmallInt, "INTEGER");
+impl_sqlite_sql_type_name!(Integer, "INTEGER");
+impl_sqlite_sql_type_name!(BigInt, "INTEGER");
+impl_sqlite_sql_type_name!(Float, "REAL");
+impl_sqlite_sql_type_name!(Double, "REAL");
+impl_sqlite_sql_type_name!(Text, "TEXT");
+impl_sqlite_sql_type_name!(Binary, "BLOB");
+impl_sqlite_sql_type_name!(Date, "TEXT");
+impl_sqlite_sql_type_name!(Time, "TEXT");
+impl_sqlite_sql_type_name!(Timestamp, "TEXT");
+impl_sqlite_sql_type_name!(Numeric, "NUMERIC");
@@ -0,0 +1,121 @@
+//! SQLite specific expression DSL methods.
+
+mod cast;
+
+use expression::{AppearsOnTable, Expression, NonAggregate, SelectableExpression};
+use query_builder::{AstPass, QueryFragment};
+use result::QueryResult;
+use sql_types::Text;
+use sqlite::Sqlite;
+
+/// SQLite specific methods which are present on text expressions.
+pub trait SqliteExpressionMethods: Expression<SqlType = Text> + Sized {
+    /// Applies SQLite's `COLLATE NOCASE` to this expression, so that any
+    /// comparison built on top of it (`.eq`, `.lt`, `ORDER BY`, ...) is
+    /// case-insensitive for ASCII text.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     use diesel::sqlite::expression::SqliteExpressionMethods;
+    /// #     use schema::users::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let data = users.filter(name.collate_nocase().eq("SEAN"));
+    /// assert_eq!(Ok(1), data.count().get_result(&connection));
+    /// # }
+    /// ```
+    fn collate_nocase(self) -> CollateNoCase<Self> {
+        CollateNoCase::new(self)
+    }
+
+    /// Shorthand for `self.collate_nocase().eq(other)`.
+    fn eq_ignore_case<T>(self, other: T) -> ::expression::operators::Eq<CollateNoCase<Self>, T>
+    where
+        T: Expression<SqlType = Text>,
+    {
+        ::expression::operators::Eq::new(self.collate_nocase(), other)
+    }
+
+    /// Creates a SQLite `REGEXP` expression.
+    ///
+    /// SQLite has no built-in regular expression engine; this operator only
+    /// works once a `regexp` SQL function has been registered on the
+    /// connection (e.g. via
+    /// [`sql_function!`](../../macro.sql_function.html) and
+    /// [`SqliteConnection::register_sql_function`](../struct.SqliteConnection.html#method.register_sql_function)),
+    /// same as with SQLite's own CLI or with the `libsqlite3-regexp` case
+    /// extension.
+    fn matches_regex<T>(self, other: T) -> Regexp<Self, T>
+    where
+        T: Expression<SqlType = Text>,
+    {
+        Regexp::new(self, other)
+    }
+}
+
+impl<T: Expression<SqlType = Text>> SqliteExpressionMethods for T {}
+
+diesel_infix_operator!(Regexp, " REGEXP ", backend: Sqlite);
+
+sql_function! {
+    /// Represents SQLite's `strftime` function, formatting `timestamp`
+    /// according to `format`. See [SQLite's docs] for the supported
+    /// substitutions.
+    ///
+    /// [SQLite's docs]: https://www.sqlite.org/lang_datefunc.html
+    fn strftime(format: Text, timestamp: Text) -> Text;
+}
+
+/// See [`SqliteExpressionMethods::collate_nocase`](trait.SqliteExpressionMethods.html#method.collate_nocase).
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct CollateNoCase<Expr> {
+    expr: Expr,
+}
+
+impl<Expr> CollateNoCase<Expr> {
+    fn new(expr: Expr) -> Self {
+        CollateNoCase { expr }
+    }
+}
+
+impl<Expr> Expression for CollateNoCase<Expr>
+where
+    Expr: Expression,
+{
+    type SqlType = Expr::SqlType;
+}
+
+impl<Expr, QS> SelectableExpression<QS> for CollateNoCase<Expr> where
+    CollateNoCase<Expr>: AppearsOnTable<QS>
+{
+}
+
+impl<Expr, QS> AppearsOnTable<QS> for CollateNoCase<Expr> where CollateNoCase<Expr>: Expression {}
+
+impl<Expr> NonAggregate for CollateNoCase<Expr> where Expr: NonAggregate {}
+
+impl<Expr> QueryFragment<Sqlite> for CollateNoCase<Expr>
+where
+    Expr: QueryFragment<Sqlite>,
+{
+    fn walk_ast(&self, mut out: AstPass<Sqlite>) -> QueryResult<()> {
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(" COLLATE NOCASE");
+        Ok(())
+    }
+}
+
+/// SQLite specific expression DSL methods.
+///
+/// This module will be glob imported by [`diesel::dsl`](../../dsl/index.html)
+/// when compiled with `feature = "sqlite"`.
+pub mod dsl {
+    pub use super::SqliteExpressionMethods;
+
+    #[doc(inline)]
+    pub use super::strftime;
+}
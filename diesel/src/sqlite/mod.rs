@@ -6,10 +6,26 @@
 
 mod backend;
 mod connection;
+pub mod expression;
+mod temp_database;
 mod types;
 
 pub mod query_builder;
 
 pub use self::backend::{Sqlite, SqliteType};
-pub use self::connection::SqliteConnection;
+pub use self::connection::{
+    create_table_ddl, diff_schemas, diff_to_ddl, DdlColumnList, DirectSqlValue,
+    ExpectedSqliteColumn, SchemaDiff, SchemaMismatch, SqliteBlob, SqliteColumn, SqliteConnectOptions,
+    SqliteConnection, SqliteDdlType, SqliteErrorCode, SqliteErrorInformation, SqliteForeignKey,
+    SqliteIndex, SqliteTable, SqliteValue, StatementStatus,
+};
 pub use self::query_builder::SqliteQueryBuilder;
+pub use self::temp_database::TempSqliteDatabase;
+#[cfg(feature = "chrono")]
+pub use self::types::{SqliteJulianDay, SqliteUnixTimestamp};
+#[cfg(feature = "spatialite")]
+pub use self::types::{st_distance, st_intersects, st_within, Geometry};
+#[cfg(feature = "serde_json")]
+pub use self::types::Json;
+#[cfg(feature = "uuid")]
+pub use self::types::{random_uuid_blob, random_uuid_text, UuidBlob, UuidText};
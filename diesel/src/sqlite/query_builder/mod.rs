@@ -4,6 +4,9 @@ use super::backend::Sqlite;
 use query_builder::QueryBuilder;
 use result::QueryResult;
 
+#[cfg(feature = "postgres")]
+mod nulls_ordering;
+
 /// Constructs SQL queries for use with the SQLite backend
 #[allow(missing_debug_implementations)]
 #[derive(Default)]
@@ -0,0 +1,31 @@
+//! SQLite (since 3.30.0) understands the same `NULLS FIRST`/`NULLS LAST`
+//! syntax as PostgreSQL, so the `NullsFirst`/`NullsLast` wrapper types are
+//! reused here rather than introduced a second time. This module only
+//! exists when both the `postgres` and `sqlite` features are enabled, since
+//! that's where those types currently live.
+use sqlite::backend::Sqlite;
+use pg::expression::operators::{NullsFirst, NullsLast};
+use query_builder::{AstPass, QueryFragment};
+use result::QueryResult;
+
+impl<Expr> QueryFragment<Sqlite> for NullsFirst<Expr>
+where
+    Expr: QueryFragment<Sqlite>,
+{
+    fn walk_ast(&self, mut out: AstPass<Sqlite>) -> QueryResult<()> {
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(" NULLS FIRST");
+        Ok(())
+    }
+}
+
+impl<Expr> QueryFragment<Sqlite> for NullsLast<Expr>
+where
+    Expr: QueryFragment<Sqlite>,
+{
+    fn walk_ast(&self, mut out: AstPass<Sqlite>) -> QueryResult<()> {
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(" NULLS LAST");
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use connection::Connection;
+use result::{ConnectionResult, QueryResult};
+use sqlite::SqliteConnection;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A uniquely named SQLite database that's torn down when this value is dropped, instead of every
+/// test suite that needs WAL or multi-connection behavior (which a plain `":memory:"` connection
+/// can't give you, since it's private to a single connection) hand-rolling the same unique file
+/// name and cleanup.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("../doctest_setup.rs");
+/// use diesel::sqlite::TempSqliteDatabase;
+///
+/// # fn main() {
+/// #     run_test().unwrap();
+/// # }
+/// #
+/// # fn run_test() -> Result<(), Box<::std::error::Error>> {
+/// let db = TempSqliteDatabase::new()?;
+/// db.execute("CREATE TABLE users (id INTEGER PRIMARY KEY)")?;
+/// let second_connection = SqliteConnection::establish(db.database_url())?;
+/// #     Ok(())
+/// # }
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct TempSqliteDatabase {
+    database_url: String,
+    path: Option<PathBuf>,
+    conn: SqliteConnection,
+}
+
+impl TempSqliteDatabase {
+    /// Creates a uniquely named on-disk database in the system temp directory. The file (and its
+    /// `-wal`/`-shm` companions, if WAL mode was used) is deleted when the returned value drops.
+    pub fn new() -> ConnectionResult<Self> {
+        let path = env::temp_dir().join(format!("diesel-temp-db-{}.sqlite3", unique_name()));
+        let database_url = path.to_string_lossy().into_owned();
+        let conn = SqliteConnection::establish(&database_url)?;
+        Ok(TempSqliteDatabase {
+            database_url: database_url,
+            path: Some(path),
+            conn: conn,
+        })
+    }
+
+    /// Creates a uniquely named database in shared memory (see
+    /// [`SqliteConnection::establish_shared_memory`][establish_shared_memory]), so other
+    /// connections can attach to it by [`database_url`](#method.database_url) without ever
+    /// touching disk. Since there's no file, there's nothing on disk to clean up on drop -- but
+    /// the database itself still disappears once every connection to it (including this one) is
+    /// closed, same as it would for an on-disk database that got deleted.
+    ///
+    /// [establish_shared_memory]: struct.SqliteConnection.html#method.establish_shared_memory
+    pub fn new_shared_memory() -> ConnectionResult<Self> {
+        let name = format!("diesel-temp-db-{}", unique_name());
+        let conn = SqliteConnection::establish_shared_memory(&name)?;
+        Ok(TempSqliteDatabase {
+            database_url: format!("file:{}?mode=memory&cache=shared", name),
+            path: None,
+            conn: conn,
+        })
+    }
+
+    /// The URL a second connection should `establish` to reach this same database.
+    pub fn database_url(&self) -> &str {
+        &self.database_url
+    }
+
+    /// The connection this fixture opened when it was created.
+    pub fn connection(&self) -> &SqliteConnection {
+        &self.conn
+    }
+
+    /// Runs `sql` (e.g. `CREATE TABLE` statements, or a migration's `up.sql`) against
+    /// [`connection`](#method.connection) to set up schema before the test uses this database.
+    pub fn execute(&self, sql: &str) -> QueryResult<()> {
+        self.conn.execute(sql).map(|_| ())
+    }
+}
+
+impl Drop for TempSqliteDatabase {
+    fn drop(&mut self) {
+        if let Some(ref path) = self.path {
+            let _ = fs::remove_file(path);
+            let _ = fs::remove_file(format!("{}-wal", path.display()));
+            let _ = fs::remove_file(format!("{}-shm", path.display()));
+        }
+    }
+}
+
+fn unique_name() -> String {
+    let jitter = RandomState::new().build_hasher().finish();
+    format!(
+        "{}-{}-{}",
+        process::id(),
+        NEXT_ID.fetch_add(1, Ordering::SeqCst),
+        jitter
+    )
+}
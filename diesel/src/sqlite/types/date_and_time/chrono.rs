@@ -1,6 +1,6 @@
 extern crate chrono;
 
-use self::chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use self::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use std::io::Write;
 
 use backend::Backend;
@@ -11,6 +11,58 @@ use sqlite::Sqlite;
 
 const SQLITE_DATE_FORMAT: &str = "%F";
 
+/// Parses `text` as a `NaiveDateTime`, tolerating every format SQLite's own
+/// `datetime()` function can produce (with or without a fractional-seconds
+/// component, `Z`-suffixed or `%:z`-offset, `T`- or space-separated), as well
+/// as a Julian day real number and a Unix epoch integer (seconds since
+/// 1970-01-01). This is what backs [`FromSql<Timestamp, Sqlite>`], and is
+/// also used by [`SqliteJulianDay`] and [`SqliteUnixTimestamp`] so that a
+/// value written by one representation can always be read back regardless of
+/// which representation the reader asks for.
+fn parse_sqlite_datetime(text: &str) -> deserialize::Result<NaiveDateTime> {
+    let sqlite_datetime_formats = &[
+        // Most likely format
+        "%F %T%.f",
+        // Other formats in order of appearance in docs
+        "%F %R",
+        "%F %RZ",
+        "%F %R%:z",
+        "%F %T%.fZ",
+        "%F %T%.f%:z",
+        "%FT%R",
+        "%FT%RZ",
+        "%FT%R%:z",
+        "%FT%T%.f",
+        "%FT%T%.fZ",
+        "%FT%T%.f%:z",
+    ];
+
+    for format in sqlite_datetime_formats {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(text, format) {
+            return Ok(dt);
+        }
+    }
+
+    if let Ok(unix_timestamp) = text.parse::<i64>() {
+        if let Some(dt) = NaiveDateTime::from_timestamp_opt(unix_timestamp, 0) {
+            return Ok(dt);
+        }
+    }
+
+    if let Ok(julian_days) = text.parse::<f64>() {
+        let epoch_in_julian_days = 2_440_587.5;
+        let seconds_in_day = 86400.0;
+        let timestamp = (julian_days - epoch_in_julian_days) * seconds_in_day;
+        let seconds = timestamp as i64;
+        let nanos = (timestamp.fract() * 1E9) as u32;
+        if let Some(timestamp) = NaiveDateTime::from_timestamp_opt(seconds, nanos) {
+            return Ok(timestamp);
+        }
+    }
+
+    Err(format!("Invalid datetime {}", text).into())
+}
+
 impl FromSql<Date, Sqlite> for NaiveDate {
     fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
         let text_ptr = <*const str as FromSql<Date, Sqlite>>::from_sql(value)?;
@@ -62,48 +114,82 @@ impl FromSql<Timestamp, Sqlite> for NaiveDateTime {
     fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
         let text_ptr = <*const str as FromSql<Date, Sqlite>>::from_sql(value)?;
         let text = unsafe { &*text_ptr };
+        parse_sqlite_datetime(text)
+    }
+}
 
-        let sqlite_datetime_formats = &[
-            // Most likely format
-            "%F %T%.f",
-            // Other formats in order of appearance in docs
-            "%F %R",
-            "%F %RZ",
-            "%F %R%:z",
-            "%F %T%.fZ",
-            "%F %T%.f%:z",
-            "%FT%R",
-            "%FT%RZ",
-            "%FT%R%:z",
-            "%FT%T%.f",
-            "%FT%T%.fZ",
-            "%FT%T%.f%:z",
-        ];
+impl ToSql<Timestamp, Sqlite> for NaiveDateTime {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let s = self.format("%F %T%.f").to_string();
+        ToSql::<Text, Sqlite>::to_sql(&s, out)
+    }
+}
 
-        for format in sqlite_datetime_formats {
-            if let Ok(dt) = Self::parse_from_str(text, format) {
-                return Ok(dt);
-            }
-        }
+impl FromSql<Timestamp, Sqlite> for DateTime<Utc> {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        let naive = <NaiveDateTime as FromSql<Timestamp, Sqlite>>::from_sql(value)?;
+        Ok(DateTime::from_utc(naive, Utc))
+    }
+}
 
-        if let Ok(julian_days) = text.parse::<f64>() {
-            let epoch_in_julian_days = 2_440_587.5;
-            let seconds_in_day = 86400.0;
-            let timestamp = (julian_days - epoch_in_julian_days) * seconds_in_day;
-            let seconds = timestamp as i64;
-            let nanos = (timestamp.fract() * 1E9) as u32;
-            if let Some(timestamp) = Self::from_timestamp_opt(seconds, nanos) {
-                return Ok(timestamp);
-            }
-        }
+impl ToSql<Timestamp, Sqlite> for DateTime<Utc> {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        ToSql::<Timestamp, Sqlite>::to_sql(&self.naive_utc(), out)
+    }
+}
 
-        Err(format!("Invalid datetime {}", text).into())
+/// Stores a [`NaiveDateTime`] as a Unix epoch integer (whole seconds since
+/// 1970-01-01) instead of the default ISO-8601 text representation used by
+/// the `NaiveDateTime` impl.
+///
+/// SQLite's `ToSql`/`FromSql` impls have no access to per-connection state,
+/// so there's no such thing as a connection-wide "default timestamp format"
+/// the way there might be for a backend with real configuration hooks.
+/// Instead, pick the representation you want at the type level: use this
+/// wrapper (or [`SqliteJulianDay`]) as the field type on your `Queryable`
+/// or `Insertable` struct instead of `NaiveDateTime` directly. Reading back
+/// is unaffected by which wrapper wrote the value -- [`FromSql`] here
+/// tolerates every format [`FromSql<Timestamp, Sqlite> for NaiveDateTime`]
+/// does, including the other wrapper's representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, FromSqlRow, AsExpression)]
+#[sql_type = "Timestamp"]
+pub struct SqliteUnixTimestamp(pub NaiveDateTime);
+
+impl FromSql<Timestamp, Sqlite> for SqliteUnixTimestamp {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        <NaiveDateTime as FromSql<Timestamp, Sqlite>>::from_sql(value).map(SqliteUnixTimestamp)
     }
 }
 
-impl ToSql<Timestamp, Sqlite> for NaiveDateTime {
+impl ToSql<Timestamp, Sqlite> for SqliteUnixTimestamp {
     fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
-        let s = self.format("%F %T%.f").to_string();
+        let s = self.0.timestamp().to_string();
+        ToSql::<Text, Sqlite>::to_sql(&s, out)
+    }
+}
+
+/// Stores a [`NaiveDateTime`] as a Julian day real number instead of the
+/// default ISO-8601 text representation used by the `NaiveDateTime` impl.
+///
+/// See [`SqliteUnixTimestamp`] for why this is a type-level choice rather
+/// than a connection-level setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, FromSqlRow, AsExpression)]
+#[sql_type = "Timestamp"]
+pub struct SqliteJulianDay(pub NaiveDateTime);
+
+impl FromSql<Timestamp, Sqlite> for SqliteJulianDay {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        <NaiveDateTime as FromSql<Timestamp, Sqlite>>::from_sql(value).map(SqliteJulianDay)
+    }
+}
+
+impl ToSql<Timestamp, Sqlite> for SqliteJulianDay {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let epoch_in_julian_days = 2_440_587.5;
+        let seconds_in_day = 86400.0;
+        let seconds = self.0.timestamp() as f64 + f64::from(self.0.timestamp_subsec_nanos()) / 1E9;
+        let julian_day = epoch_in_julian_days + seconds / seconds_in_day;
+        let s = julian_day.to_string();
         ToSql::<Text, Sqlite>::to_sql(&s, out)
     }
 }
@@ -113,13 +199,14 @@ mod tests {
     extern crate chrono;
     extern crate dotenv;
 
-    use self::chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+    use self::chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
     use self::dotenv::dotenv;
 
     use dsl::{now, sql};
     use prelude::*;
     use select;
     use sql_types::{Date, Text, Time, Timestamp};
+    use sqlite::{SqliteJulianDay, SqliteUnixTimestamp};
 
     sql_function!(fn datetime(x: Text) -> Timestamp);
     sql_function!(fn time(x: Text) -> Time);
@@ -380,4 +467,47 @@ mod tests {
         let query = select(datetime("9999-01-08 00:00:00.000000").eq(distant_future));
         assert!(query.get_result::<bool>(&connection).unwrap());
     }
+
+    #[test]
+    fn timestamp_decodes_correctly_from_unix_epoch_integer() {
+        let connection = connection();
+        let time = NaiveDate::from_ymd(2000, 1, 1).and_hms(1, 1, 1);
+        let query = select(sql::<Timestamp>("946688461"));
+        assert_eq!(Ok(time), query.get_result::<NaiveDateTime>(&connection));
+    }
+
+    #[test]
+    fn datetime_utc_round_trips_through_sqlite() {
+        let connection = connection();
+        let time = DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2000, 1, 1).and_hms(1, 1, 1), Utc);
+        let query = select(datetime("2000-01-01 01:01:01.000000"));
+        assert_eq!(Ok(time), query.get_result::<DateTime<Utc>>(&connection));
+
+        let query = select(datetime("2000-01-01 01:01:01.000000").eq(time));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+
+    #[test]
+    fn sqlite_unix_timestamp_round_trips_through_sqlite() {
+        let connection = connection();
+        let time = SqliteUnixTimestamp(NaiveDate::from_ymd(2000, 1, 1).and_hms(1, 1, 1));
+        let query = select(sql::<Timestamp>("946688461"));
+        assert_eq!(Ok(time), query.get_result::<SqliteUnixTimestamp>(&connection));
+
+        let query = select(sql::<Timestamp>("strftime('%s', '2000-01-01 01:01:01')").eq(time));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+
+    #[test]
+    fn sqlite_julian_day_round_trips_through_sqlite() {
+        let connection = connection();
+        let time = SqliteJulianDay(NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0));
+        let query = select(sql::<Timestamp>("2451544.5"));
+        assert_eq!(Ok(time), query.get_result::<SqliteJulianDay>(&connection));
+
+        let query = select(
+            sql::<Timestamp>("CAST(julianday('2000-01-01 00:00:00') AS TEXT)").eq(time),
+        );
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
 }
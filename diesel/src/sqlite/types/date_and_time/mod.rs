@@ -8,6 +8,10 @@ use sqlite::Sqlite;
 
 #[cfg(feature = "chrono")]
 mod chrono;
+#[cfg(feature = "chrono")]
+pub use self::chrono::{SqliteJulianDay, SqliteUnixTimestamp};
+#[cfg(feature = "time_03")]
+mod time;
 
 /// The returned pointer is *only* valid for the lifetime to the argument of
 /// `from_sql`. This impl is intended for uses where you want to write a new
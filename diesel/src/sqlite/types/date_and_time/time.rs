@@ -0,0 +1,124 @@
+extern crate time_03;
+
+use self::time_03::{format_description, Date, OffsetDateTime, PrimitiveDateTime, Time as ClockTime};
+use std::io::Write;
+
+use backend::Backend;
+use deserialize::{self, FromSql};
+use serialize::{self, Output, ToSql};
+use sql_types::{Date as DateTy, Text, Time as TimeTy, Timestamp};
+use sqlite::Sqlite;
+
+const SQLITE_DATE_FORMAT: &str = "[year]-[month]-[day]";
+const SQLITE_TIMESTAMP_FORMAT: &str = "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond digits:1+]";
+
+fn parse_sqlite_timestamp(text: &str) -> deserialize::Result<PrimitiveDateTime> {
+    let formats = &[
+        "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond digits:1+]",
+        "[year]-[month]-[day] [hour]:[minute]:[second]",
+        "[year]-[month]-[day] [hour]:[minute]",
+        "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:1+]",
+        "[year]-[month]-[day]T[hour]:[minute]:[second]",
+        "[year]-[month]-[day]T[hour]:[minute]",
+    ];
+
+    for format in formats {
+        if let Ok(description) = format_description::parse(format) {
+            if let Ok(dt) = PrimitiveDateTime::parse(text, &description) {
+                return Ok(dt);
+            }
+        }
+    }
+
+    if let Ok(unix_timestamp) = text.parse::<i64>() {
+        if let Ok(dt) = OffsetDateTime::from_unix_timestamp(unix_timestamp) {
+            return Ok(PrimitiveDateTime::new(dt.date(), dt.time()));
+        }
+    }
+
+    if let Ok(julian_days) = text.parse::<f64>() {
+        let epoch_in_julian_days = 2_440_587.5;
+        let seconds_in_day = 86400.0;
+        let unix_seconds = ((julian_days - epoch_in_julian_days) * seconds_in_day) as i64;
+        if let Ok(dt) = OffsetDateTime::from_unix_timestamp(unix_seconds) {
+            return Ok(PrimitiveDateTime::new(dt.date(), dt.time()));
+        }
+    }
+
+    Err(format!("Invalid datetime {}", text).into())
+}
+
+impl FromSql<DateTy, Sqlite> for Date {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        let text_ptr = <*const str as FromSql<DateTy, Sqlite>>::from_sql(value)?;
+        let text = unsafe { &*text_ptr };
+        let description = format_description::parse(SQLITE_DATE_FORMAT)?;
+        Ok(Date::parse(text, &description)?)
+    }
+}
+
+impl ToSql<DateTy, Sqlite> for Date {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let description = format_description::parse(SQLITE_DATE_FORMAT)?;
+        let s = self.format(&description)?;
+        ToSql::<Text, Sqlite>::to_sql(&s, out)
+    }
+}
+
+impl FromSql<TimeTy, Sqlite> for ClockTime {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        let text_ptr = <*const str as FromSql<DateTy, Sqlite>>::from_sql(value)?;
+        let text = unsafe { &*text_ptr };
+        let formats = &[
+            "[hour]:[minute]:[second].[subsecond digits:1+]",
+            "[hour]:[minute]:[second]",
+            "[hour]:[minute]",
+        ];
+        for format in formats {
+            if let Ok(description) = format_description::parse(format) {
+                if let Ok(t) = ClockTime::parse(text, &description) {
+                    return Ok(t);
+                }
+            }
+        }
+        Err(format!("Invalid time {}", text).into())
+    }
+}
+
+impl ToSql<TimeTy, Sqlite> for ClockTime {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let description = format_description::parse("[hour]:[minute]:[second].[subsecond digits:6]")?;
+        let s = self.format(&description)?;
+        ToSql::<Text, Sqlite>::to_sql(&s, out)
+    }
+}
+
+impl FromSql<Timestamp, Sqlite> for PrimitiveDateTime {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        let text_ptr = <*const str as FromSql<DateTy, Sqlite>>::from_sql(value)?;
+        let text = unsafe { &*text_ptr };
+        parse_sqlite_timestamp(text)
+    }
+}
+
+impl ToSql<Timestamp, Sqlite> for PrimitiveDateTime {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let description = format_description::parse(SQLITE_TIMESTAMP_FORMAT)?;
+        let s = self.format(&description)?;
+        ToSql::<Text, Sqlite>::to_sql(&s, out)
+    }
+}
+
+impl FromSql<Timestamp, Sqlite> for OffsetDateTime {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        let naive = <PrimitiveDateTime as FromSql<Timestamp, Sqlite>>::from_sql(value)?;
+        Ok(naive.assume_utc())
+    }
+}
+
+impl ToSql<Timestamp, Sqlite> for OffsetDateTime {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let naive = PrimitiveDateTime::new(self.date(), self.time());
+        ToSql::<Timestamp, Sqlite>::to_sql(&naive, out)
+    }
+}
@@ -0,0 +1,34 @@
+extern crate rust_decimal;
+
+use std::io::Write;
+use std::str::FromStr;
+
+use self::rust_decimal::Decimal;
+
+use deserialize::{self, FromSql};
+use serialize::{self, Output, ToSql};
+use sql_types::Numeric;
+use sqlite::connection::SqliteValue;
+use sqlite::Sqlite;
+
+/// SQLite has no native decimal storage, so [`rust_decimal::Decimal`] is
+/// stored as `TEXT`. Like the [`BigDecimal`] impl in `sqlite::types::numeric`,
+/// this conversion goes straight to and from the decimal's own string
+/// representation, so values survive a round trip exactly.
+///
+/// [`BigDecimal`]: /bigdecimal/struct.BigDecimal.html
+impl FromSql<Numeric, Sqlite> for Decimal {
+    fn from_sql(bytes: Option<&SqliteValue>) -> deserialize::Result<Self> {
+        let text_ptr = <*const str as FromSql<::sql_types::Text, Sqlite>>::from_sql(bytes)?;
+        let text = unsafe { &*text_ptr };
+        Decimal::from_str(text)
+            .map_err(|_| Box::from(format!("{:?} is not valid decimal number ", text)))
+    }
+}
+
+impl ToSql<Numeric, Sqlite> for Decimal {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let s = self.to_string();
+        ToSql::<::sql_types::Text, Sqlite>::to_sql(&s, out)
+    }
+}
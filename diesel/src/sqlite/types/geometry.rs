@@ -0,0 +1,59 @@
+use std::io::Write;
+
+use deserialize::{self, FromSql};
+use serialize::{self, Output, ToSql};
+use sqlite::connection::SqliteValue;
+use sqlite::Sqlite;
+
+/// A SpatiaLite geometry value (point, linestring, polygon, or any other
+/// [OGC Simple Features] type), stored as a well-known-binary (WKB) `BLOB`.
+///
+/// SpatiaLite doesn't distinguish between geometry subtypes at the column
+/// level -- a `POINT`, `LINESTRING`, and `POLYGON` column are all just
+/// `BLOB` under the hood -- so this single SQL type covers all of them.
+/// Diesel hands you the raw WKB bytes as a `Vec<u8>`; parsing them into a
+/// richer geometry representation is left to a crate like `geo-types` or
+/// `wkb`, since Diesel does not otherwise depend on one.
+///
+/// This assumes the SpatiaLite extension has already been loaded into the
+/// connection (e.g. through `SqliteConnection`'s extension-loading API);
+/// Diesel does not load it for you.
+///
+/// [OGC Simple Features]: https://www.ogc.org/standards/sfa
+#[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+#[sqlite_type = "Binary"]
+pub struct Geometry;
+
+impl FromSql<Geometry, Sqlite> for Vec<u8> {
+    fn from_sql(bytes: Option<&SqliteValue>) -> deserialize::Result<Self> {
+        let bytes_ptr = <*const [u8] as FromSql<::sql_types::Binary, Sqlite>>::from_sql(bytes)?;
+        Ok(unsafe { &*bytes_ptr }.to_vec())
+    }
+}
+
+impl ToSql<Geometry, Sqlite> for Vec<u8> {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        ToSql::<::sql_types::Binary, Sqlite>::to_sql(self, out)
+    }
+}
+
+sql_function! {
+    /// The SpatiaLite `ST_Distance` function, returning the planar distance
+    /// between two geometries.
+    #[sql_name = "ST_Distance"]
+    fn st_distance(a: Geometry, b: Geometry) -> ::sql_types::Double;
+}
+
+sql_function! {
+    /// The SpatiaLite `ST_Within` function, evaluating to `true` if `a` is
+    /// entirely contained within `b`.
+    #[sql_name = "ST_Within"]
+    fn st_within(a: Geometry, b: Geometry) -> ::sql_types::Bool;
+}
+
+sql_function! {
+    /// The SpatiaLite `ST_Intersects` function, evaluating to `true` if `a`
+    /// and `b` share any points.
+    #[sql_name = "ST_Intersects"]
+    fn st_intersects(a: Geometry, b: Geometry) -> ::sql_types::Bool;
+}
@@ -0,0 +1,37 @@
+extern crate serde_json;
+
+use std::io::Write;
+
+use backend::Backend;
+use deserialize::{self, FromSql};
+use serialize::{self, Output, ToSql};
+use sqlite::Sqlite;
+
+/// The JSON SQL type, stored as `TEXT` on SQLite.
+///
+/// Unlike `sql_types::Json`, which only exists when the `postgres` feature is
+/// enabled (it's declared alongside Postgres's native `json` OID), this type
+/// is available whenever `sqlite` and `serde_json` are both enabled, and has
+/// no relationship to Postgres's wire format. It gives the [JSON1
+/// extension](https://www.sqlite.org/json1.html) functions (`json_extract`,
+/// `json_each`, and friends) a natural Rust-side type: declare a column or
+/// `sql_function!` argument/return as `sql_type = "Json"` and bind/read it as
+/// a [`serde_json::Value`].
+#[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+#[sqlite_type = "Text"]
+pub struct Json;
+
+impl FromSql<Json, Sqlite> for serde_json::Value {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        let text_ptr = <*const str as FromSql<::sql_types::Text, Sqlite>>::from_sql(value)?;
+        let text = unsafe { &*text_ptr };
+        serde_json::from_str(text).map_err(Into::into)
+    }
+}
+
+impl ToSql<Json, Sqlite> for serde_json::Value {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let s = serde_json::to_string(self)?;
+        ToSql::<::sql_types::Text, Sqlite>::to_sql(&s, out)
+    }
+}
@@ -1,5 +1,24 @@
 mod date_and_time;
+#[cfg(feature = "decimal")]
+mod decimal;
+#[cfg(feature = "spatialite")]
+mod geometry;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "network-address")]
+mod network_address;
 mod numeric;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "chrono")]
+pub use self::date_and_time::{SqliteJulianDay, SqliteUnixTimestamp};
+#[cfg(feature = "spatialite")]
+pub use self::geometry::{st_distance, st_intersects, st_within, Geometry};
+#[cfg(feature = "serde_json")]
+pub use self::json::Json;
+#[cfg(feature = "uuid")]
+pub use self::uuid::{random_uuid_blob, random_uuid_text, UuidBlob, UuidText};
 
 use std::io::prelude::*;
 
@@ -0,0 +1,90 @@
+extern crate ipnetwork;
+
+use std::fmt::Write as FmtWrite;
+use std::io::Write;
+use std::str::FromStr;
+
+use self::ipnetwork::IpNetwork;
+
+use deserialize::{self, FromSql};
+use serialize::{self, Output, ToSql};
+use sql_types::{Cidr, HasSqlType, Inet, MacAddr};
+use sqlite::connection::SqliteValue;
+use sqlite::{Sqlite, SqliteType};
+
+/// SQLite has no native network address types, so `Inet`, `Cidr`, and
+/// `MacAddr` are all stored as `TEXT`, using the same textual notation
+/// Postgres itself accepts (e.g. `"10.1.9.32/32"`, `"08:00:2b:01:02:03"`).
+impl HasSqlType<Inet> for Sqlite {
+    fn metadata(_: &()) -> SqliteType {
+        SqliteType::Text
+    }
+}
+
+impl HasSqlType<Cidr> for Sqlite {
+    fn metadata(_: &()) -> SqliteType {
+        SqliteType::Text
+    }
+}
+
+impl HasSqlType<MacAddr> for Sqlite {
+    fn metadata(_: &()) -> SqliteType {
+        SqliteType::Text
+    }
+}
+
+macro_rules! impl_ip_network_via_text {
+    ($ty:ty) => {
+        impl FromSql<$ty, Sqlite> for IpNetwork {
+            fn from_sql(bytes: Option<&SqliteValue>) -> deserialize::Result<Self> {
+                let text_ptr = <*const str as FromSql<::sql_types::Text, Sqlite>>::from_sql(bytes)?;
+                let text = unsafe { &*text_ptr };
+                IpNetwork::from_str(text)
+                    .map_err(|_| Box::from(format!("{:?} is not a valid network address", text)))
+            }
+        }
+
+        impl ToSql<$ty, Sqlite> for IpNetwork {
+            fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+                let s = self.to_string();
+                ToSql::<::sql_types::Text, Sqlite>::to_sql(&s, out)
+            }
+        }
+    };
+}
+
+impl_ip_network_via_text!(Inet);
+impl_ip_network_via_text!(Cidr);
+
+impl FromSql<MacAddr, Sqlite> for [u8; 6] {
+    fn from_sql(bytes: Option<&SqliteValue>) -> deserialize::Result<Self> {
+        let text_ptr = <*const str as FromSql<::sql_types::Text, Sqlite>>::from_sql(bytes)?;
+        let text = unsafe { &*text_ptr };
+        let mut octets = [0u8; 6];
+        let mut parts = text.split(':');
+        for octet in &mut octets {
+            let part = parts
+                .next()
+                .ok_or_else(|| format!("{:?} is not a valid MAC address", text))?;
+            *octet = u8::from_str_radix(part, 16)
+                .map_err(|_| format!("{:?} is not a valid MAC address", text))?;
+        }
+        if parts.next().is_some() {
+            return Err(format!("{:?} is not a valid MAC address", text).into());
+        }
+        Ok(octets)
+    }
+}
+
+impl ToSql<MacAddr, Sqlite> for [u8; 6] {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let mut s = String::with_capacity(17);
+        for (i, octet) in self.iter().enumerate() {
+            if i > 0 {
+                s.push(':');
+            }
+            write!(s, "{:02x}", octet)?;
+        }
+        ToSql::<::sql_types::Text, Sqlite>::to_sql(&s, out)
+    }
+}
@@ -2,16 +2,32 @@
 
 extern crate bigdecimal;
 
+use std::io::Write;
+use std::str::FromStr;
+
 use self::bigdecimal::BigDecimal;
 
 use deserialize::{self, FromSql};
-use sql_types::{Double, Numeric};
+use serialize::{self, Output, ToSql};
+use sql_types::Numeric;
 use sqlite::connection::SqliteValue;
 use sqlite::Sqlite;
 
+/// SQLite has no native decimal storage, so `BigDecimal` is stored as `TEXT`,
+/// which round-trips exactly (unlike storing as `Double`, which would lose
+/// precision for values that don't fit in an `f64`).
 impl FromSql<Numeric, Sqlite> for BigDecimal {
     fn from_sql(bytes: Option<&SqliteValue>) -> deserialize::Result<Self> {
-        let data = <f64 as FromSql<Double, Sqlite>>::from_sql(bytes)?;
-        Ok(data.into())
+        let text_ptr = <*const str as FromSql<::sql_types::Text, Sqlite>>::from_sql(bytes)?;
+        let text = unsafe { &*text_ptr };
+        BigDecimal::from_str(text)
+            .map_err(|_| Box::from(format!("{:?} is not valid decimal number ", text)))
+    }
+}
+
+impl ToSql<Numeric, Sqlite> for BigDecimal {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let s = self.to_string();
+        ToSql::<::sql_types::Text, Sqlite>::to_sql(&s, out)
     }
 }
@@ -0,0 +1,118 @@
+extern crate uuid;
+
+use std::io::Write;
+
+use backend::Backend;
+use deserialize::{self, FromSql};
+use expression::{Expression, NonAggregate};
+use query_builder::{AstPass, QueryFragment};
+use result::QueryResult;
+use serialize::{self, IsNull, Output, ToSql};
+use sqlite::Sqlite;
+
+/// The 16-byte `BLOB` representation of a [`uuid::Uuid`], for columns
+/// declared with `sql_type = "UuidBlob"`.
+///
+/// See [`UuidText`] for the alternative hyphenated-text representation, and
+/// [`random_uuid_blob`] for a SQL-side expression that generates one.
+///
+/// [Uuid]: https://doc.rust-lang.org/uuid/uuid/struct.Uuid.html
+#[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+#[sqlite_type = "Binary"]
+pub struct UuidBlob;
+
+/// The hyphenated-text representation of a [`uuid::Uuid`], for columns
+/// declared with `sql_type = "UuidText"`.
+///
+/// See [`UuidBlob`] for the more compact 16-byte binary representation, and
+/// [`random_uuid_text`] for a SQL-side expression that generates one.
+#[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+#[sqlite_type = "Text"]
+pub struct UuidText;
+
+impl FromSql<UuidBlob, Sqlite> for uuid::Uuid {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        let bytes = <*const [u8] as FromSql<::sql_types::Binary, Sqlite>>::from_sql(value)?;
+        let bytes = unsafe { &*bytes };
+        uuid::Uuid::from_bytes(bytes).map_err(Into::into)
+    }
+}
+
+impl ToSql<UuidBlob, Sqlite> for uuid::Uuid {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        out.write_all(self.as_bytes())
+            .map(|_| IsNull::No)
+            .map_err(Into::into)
+    }
+}
+
+impl FromSql<UuidText, Sqlite> for uuid::Uuid {
+    fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+        let text_ptr = <*const str as FromSql<::sql_types::Text, Sqlite>>::from_sql(value)?;
+        let text = unsafe { &*text_ptr };
+        uuid::Uuid::parse_str(text).map_err(Into::into)
+    }
+}
+
+impl ToSql<UuidText, Sqlite> for uuid::Uuid {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+        let s = self.hyphenated().to_string();
+        ToSql::<::sql_types::Text, Sqlite>::to_sql(&s, out)
+    }
+}
+
+/// A SQL-side expression that generates a random (v4) UUID as a 16-byte
+/// `BLOB`, using SQLite's built-in `randomblob` function.
+///
+/// This can be used anywhere an expression of type [`UuidBlob`] is expected,
+/// for example to populate a default value on insert:
+/// `.values(id.eq(random_uuid_blob))`.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, QueryId)]
+pub struct random_uuid_blob;
+
+impl Expression for random_uuid_blob {
+    type SqlType = UuidBlob;
+}
+
+impl NonAggregate for random_uuid_blob {}
+
+impl QueryFragment<Sqlite> for random_uuid_blob {
+    fn walk_ast(&self, mut out: AstPass<Sqlite>) -> QueryResult<()> {
+        out.push_sql("randomblob(16)");
+        Ok(())
+    }
+}
+
+impl_selectable_expression!(random_uuid_blob);
+
+/// A SQL-side expression that generates a random (v4) UUID as hyphenated
+/// text, built out of SQLite's `randomblob`/`hex` functions since SQLite has
+/// no built-in UUID generator of its own.
+///
+/// This can be used anywhere an expression of type [`UuidText`] is expected,
+/// for example to populate a default value on insert:
+/// `.values(id.eq(random_uuid_text))`.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, QueryId)]
+pub struct random_uuid_text;
+
+impl Expression for random_uuid_text {
+    type SqlType = UuidText;
+}
+
+impl NonAggregate for random_uuid_text {}
+
+impl QueryFragment<Sqlite> for random_uuid_text {
+    fn walk_ast(&self, mut out: AstPass<Sqlite>) -> QueryResult<()> {
+        out.push_sql(
+            "(lower(hex(randomblob(4)) || '-' || hex(randomblob(2)) || '-4' || \
+             substr(hex(randomblob(2)), 2) || '-' || \
+             substr('89ab', abs(random()) % 4 + 1, 1) || substr(hex(randomblob(2)), 2) || \
+             '-' || hex(randomblob(6))))",
+        );
+        Ok(())
+    }
+}
+
+impl_selectable_expression!(random_uuid_text);
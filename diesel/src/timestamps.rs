@@ -0,0 +1,92 @@
+//! Support for automatically maintained `created_at` / `updated_at` columns.
+//!
+//! Diesel's `insert_into`/`update` don't know which columns (if any) a table uses for this, and
+//! there's no hook that lets a table rewrite an arbitrary changeset passed to `.set(...)`, so
+//! implementing [`Timestamped`] doesn't change what a plain `insert_into(table).values(record)`
+//! or `update(target).set(changes)` does. Instead, use [`insert_with_timestamps`] in place of
+//! `insert_into(table).values(...)`, and [`touch`] or [`update_with_timestamp`] in place of
+//! `update(target).set(...)`.
+//!
+//! The timestamp itself is [`dsl::now`](../dsl/struct.now.html), evaluated by the database, so
+//! this works the same way on SQLite, Pg and MySQL.
+
+use dsl::{now, Eq, Update};
+use expression_methods::*;
+use query_builder::{AsChangeset, InsertStatement, IntoUpdateTarget};
+use query_source::{Column, Table};
+use sql_types::Timestamp;
+
+/// Opt-in marker for tables with `created_at`/`updated_at` columns that should be maintained by
+/// the functions in this module rather than set by hand.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # use diesel::timestamps::Timestamped;
+/// #
+/// table! {
+///     posts {
+///         id -> Integer,
+///         title -> Text,
+///         created_at -> Timestamp,
+///         updated_at -> Timestamp,
+///     }
+/// }
+///
+/// impl Timestamped for posts::table {
+///     type CreatedAtColumn = posts::created_at;
+///     type UpdatedAtColumn = posts::updated_at;
+/// }
+/// #
+/// # fn main() {}
+/// ```
+pub trait Timestamped: Table + Sized {
+    /// The column set to [`now`](../dsl/struct.now.html) by [`insert_with_timestamps`].
+    type CreatedAtColumn: Column<Table = Self, SqlType = Timestamp> + Default;
+
+    /// The column set to [`now`](../dsl/struct.now.html) by [`touch`] and
+    /// [`update_with_timestamp`], and by [`insert_with_timestamps`] on insert.
+    type UpdatedAtColumn: Column<Table = Self, SqlType = Timestamp> + Default;
+}
+
+/// Inserts `values` into `table`, additionally setting `created_at` and `updated_at` to
+/// [`now`](../dsl/struct.now.html).
+pub fn insert_with_timestamps<T, U>(
+    table: T,
+    values: U,
+) -> InsertStatement<T, <(U, Eq<T::CreatedAtColumn, now>, Eq<T::UpdatedAtColumn, now>) as ::insertable::Insertable<T>>::Values>
+where
+    T: Timestamped,
+    (U, Eq<T::CreatedAtColumn, now>, Eq<T::UpdatedAtColumn, now>): ::insertable::Insertable<T>,
+{
+    let created_at = T::CreatedAtColumn::default();
+    let updated_at = T::UpdatedAtColumn::default();
+    ::insert_into(table).values((values, created_at.eq(now), updated_at.eq(now)))
+}
+
+/// Sets `updated_at` to [`now`](../dsl/struct.now.html) on every row targeted by `target`,
+/// without changing anything else.
+pub fn touch<T>(target: T) -> Update<T, Eq<<T::Table as Timestamped>::UpdatedAtColumn, now>>
+where
+    T: IntoUpdateTarget,
+    T::Table: Timestamped,
+{
+    let updated_at = <T::Table as Timestamped>::UpdatedAtColumn::default();
+    ::update(target).set(updated_at.eq(now))
+}
+
+/// Applies `changes` to every row targeted by `target`, additionally setting `updated_at` to
+/// [`now`](../dsl/struct.now.html).
+pub fn update_with_timestamp<T, V>(
+    target: T,
+    changes: V,
+) -> Update<T, (V, Eq<<T::Table as Timestamped>::UpdatedAtColumn, now>)>
+where
+    T: IntoUpdateTarget,
+    T::Table: Timestamped,
+    (V, Eq<<T::Table as Timestamped>::UpdatedAtColumn, now>): AsChangeset<Target = T::Table>,
+{
+    let updated_at = <T::Table as Timestamped>::UpdatedAtColumn::default();
+    ::update(target).set((changes, updated_at.eq(now)))
+}
@@ -21,6 +21,11 @@ table! {
         notnull -> Bool,
         dflt_value -> Nullable<VarChar>,
         pk -> Bool,
+        // Only present when queried via `PRAGMA TABLE_XINFO`, which we use instead of
+        // `PRAGMA TABLE_INFO` so that hidden columns on virtual tables (e.g. FTS5's `rank`
+        // column, or a virtual table's aliased `rowid`) are included rather than silently
+        // dropped. `0` means the column is a normal column.
+        hidden -> Integer,
     }
 }
 
@@ -92,7 +97,10 @@ pub fn get_table_data(
     conn: &SqliteConnection,
     table: &TableName,
 ) -> QueryResult<Vec<ColumnInformation>> {
-    let query = format!("PRAGMA TABLE_INFO('{}')", &table.name);
+    // `TABLE_XINFO` (rather than `TABLE_INFO`) also reports hidden columns, which is how
+    // virtual tables such as FTS5 tables expose their special columns (e.g. `rank`, or an
+    // aliased `rowid`) -- `TABLE_INFO` silently omits them.
+    let query = format!("PRAGMA TABLE_XINFO('{}')", &table.name);
     sql::<pragma_table_info::SqlType>(&query).load(conn)
 }
 
@@ -103,10 +111,11 @@ struct FullTableInfo {
     _not_null: bool,
     _dflt_value: Option<String>,
     primary_key: bool,
+    _hidden: i32,
 }
 
 impl Queryable<pragma_table_info::SqlType, Sqlite> for FullTableInfo {
-    type Row = (i32, String, String, bool, Option<String>, bool);
+    type Row = (i32, String, String, bool, Option<String>, bool, i32);
 
     fn build(row: Self::Row) -> Self {
         FullTableInfo {
@@ -116,6 +125,7 @@ impl Queryable<pragma_table_info::SqlType, Sqlite> for FullTableInfo {
             _not_null: row.3,
             _dflt_value: row.4,
             primary_key: row.5,
+            _hidden: row.6,
         }
     }
 }
@@ -149,7 +159,7 @@ impl Queryable<pragma_foreign_key_list::SqlType, Sqlite> for ForeignKeyListRow {
 }
 
 pub fn get_primary_keys(conn: &SqliteConnection, table: &TableName) -> QueryResult<Vec<String>> {
-    let query = format!("PRAGMA TABLE_INFO('{}')", &table.name);
+    let query = format!("PRAGMA TABLE_XINFO('{}')", &table.name);
     let results = try!(sql::<pragma_table_info::SqlType>(&query).load::<FullTableInfo>(conn));
     Ok(results
         .into_iter()
@@ -302,6 +312,29 @@ fn load_table_names_output_is_ordered() {
     assert_eq!(vec!["aaa", "bbb", "ccc"], table_names);
 }
 
+#[test]
+fn load_table_names_includes_virtual_tables() {
+    let conn = SqliteConnection::establish(":memory:").unwrap();
+    conn.execute("CREATE VIRTUAL TABLE docs USING fts5(body)")
+        .unwrap();
+    let table_names = load_table_names(&conn, None).unwrap();
+    assert!(table_names.contains(&TableName::from_name("docs")));
+}
+
+#[test]
+fn get_table_data_includes_hidden_columns_on_virtual_tables() {
+    let conn = SqliteConnection::establish(":memory:").unwrap();
+    conn.execute("CREATE VIRTUAL TABLE docs USING fts5(body)")
+        .unwrap();
+    let columns = get_table_data(&conn, &TableName::from_name("docs")).unwrap();
+    let column_names = columns
+        .iter()
+        .map(|c| c.column_name.as_str())
+        .collect::<Vec<_>>();
+    assert!(column_names.contains(&"body"));
+    assert!(column_names.contains(&"rank"));
+}
+
 #[test]
 fn load_foreign_key_constraints_loads_foreign_keys() {
     let connection = SqliteConnection::establish(":memory:").unwrap();
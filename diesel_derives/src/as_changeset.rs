@@ -25,10 +25,16 @@ pub fn derive(item: syn::DeriveInput) -> Result<proc_macro2::TokenStream, Diagno
     impl_generics.params.push(parse_quote!('update));
     let (impl_generics, _, _) = impl_generics.split_for_impl();
 
+    // As with `#[derive(Insertable)]`, fields marked `#[diesel(generated_column)]` back a
+    // `GENERATED ALWAYS` column and are never written to. Fields marked
+    // `#[diesel(version_column)]` back an optimistic-locking version column, which is only ever
+    // bumped by `optimistic_lock::update_with_version_check`, never set directly.
     let fields_for_update = model
         .fields()
         .iter()
         .filter(|f| !model.primary_key_names.contains(&f.column_name()))
+        .filter(|f| !f.has_flag("generated_column"))
+        .filter(|f| !f.has_flag("version_column"))
         .collect::<Vec<_>>();
     let ref_changeset_ty = fields_for_update.iter().map(|field| {
         field_changeset_ty(
@@ -96,7 +102,13 @@ fn field_changeset_ty(
     lifetime: Option<proc_macro2::TokenStream>,
 ) -> syn::Type {
     let column_name = field.column_name();
-    if !treat_none_as_null && is_option_ty(&field.ty) {
+    if is_option_ty(&field.ty) && is_option_ty(inner_of_option_ty(&field.ty)) {
+        // `Option<Option<T>>`: the outer `Option` decides whether the column
+        // is touched at all, and the inner `Option` is bound directly, so
+        // `None` sets the column to `NULL` rather than being skipped.
+        let field_ty = inner_of_option_ty(&field.ty);
+        parse_quote!(std::option::Option<diesel::dsl::Eq<#table_name::#column_name, #lifetime #field_ty>>)
+    } else if !field_treats_none_as_null(field, treat_none_as_null) && is_option_ty(&field.ty) {
         let field_ty = inner_of_option_ty(&field.ty);
         parse_quote!(std::option::Option<diesel::dsl::Eq<#table_name::#column_name, #lifetime #field_ty>>)
     } else {
@@ -113,7 +125,13 @@ fn field_changeset_expr(
 ) -> syn::Expr {
     let field_access = field.name.access();
     let column_name = field.column_name();
-    if !treat_none_as_null && is_option_ty(&field.ty) {
+    if is_option_ty(&field.ty) && is_option_ty(inner_of_option_ty(&field.ty)) {
+        if lifetime.is_some() {
+            parse_quote!(self#field_access.as_ref().map(|x| #table_name::#column_name.eq(x)))
+        } else {
+            parse_quote!(self#field_access.map(|x| #table_name::#column_name.eq(x)))
+        }
+    } else if !field_treats_none_as_null(field, treat_none_as_null) && is_option_ty(&field.ty) {
         if lifetime.is_some() {
             parse_quote!(self#field_access.as_ref().map(|x| #table_name::#column_name.eq(x)))
         } else {
@@ -123,3 +141,11 @@ fn field_changeset_expr(
         parse_quote!(#table_name::#column_name.eq(#lifetime self#field_access))
     }
 }
+
+/// Whether `None` on this field should be bound as SQL `NULL`, rather than
+/// skipping the column, either because the whole struct opted in via
+/// `#[changeset_options(treat_none_as_null = "true")]`, or because this
+/// field opted in on its own via `#[diesel(treat_none_as_null)]`.
+fn field_treats_none_as_null(field: &Field, treat_none_as_null: bool) -> bool {
+    treat_none_as_null || field.has_flag("treat_none_as_null")
+}
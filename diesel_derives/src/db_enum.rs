@@ -0,0 +1,148 @@
+use proc_macro2;
+use proc_macro2::*;
+use syn;
+
+use meta::*;
+use model::camel_to_snake;
+use util::*;
+
+/// Maps a fieldless enum to a Pg enum type, a MySQL `ENUM`, or SQLite `TEXT`.
+///
+/// Each variant is stored as its `snake_case` name by default; use
+/// `#[db_rename = "..."]` on a variant to override that. Postgres additionally
+/// requires the enum's SQL type name, given via `#[postgres(type_name = "...")]`
+/// on the enum itself, since Diesel has to look up its OID at runtime; without
+/// it, only the SQLite and MySQL impls are generated.
+pub fn derive(item: syn::DeriveInput) -> Result<proc_macro2::TokenStream, Diagnostic> {
+    let enum_name = &item.ident;
+
+    if !item.generics.params.is_empty() {
+        return Err(item
+            .ident
+            .span()
+            .error("#[derive(DbEnum)] does not support generic enums"));
+    }
+
+    let variants = match item.data {
+        syn::Data::Enum(ref data) => &data.variants,
+        _ => {
+            return Err(item
+                .ident
+                .span()
+                .error("#[derive(DbEnum)] can only be used on enums"))
+        }
+    };
+
+    let mut variant_idents = Vec::with_capacity(variants.len());
+    let mut db_values = Vec::with_capacity(variants.len());
+
+    for variant in variants {
+        match variant.fields {
+            syn::Fields::Unit => {}
+            _ => {
+                return Err(variant
+                    .ident
+                    .span()
+                    .error("#[derive(DbEnum)] does not support variants with fields"))
+            }
+        }
+
+        let db_value = MetaItem::with_name(&variant.attrs, "db_rename")
+            .map(|attr| attr.expect_str_value())
+            .unwrap_or_else(|| camel_to_snake(&variant.ident.to_string()));
+
+        variant_idents.push(&variant.ident);
+        db_values.push(db_value);
+    }
+
+    let from_sql_arms = variant_idents
+        .iter()
+        .zip(&db_values)
+        .map(|(variant_ident, db_value)| {
+            quote!(#db_value => Ok(#enum_name::#variant_ident),)
+        }).collect::<Vec<_>>();
+    let to_sql_arms = variant_idents
+        .iter()
+        .zip(&db_values)
+        .map(|(variant_ident, db_value)| {
+            quote!(#enum_name::#variant_ident => #db_value,)
+        }).collect::<Vec<_>>();
+
+    let pg_tokens = pg_tokens(&item, enum_name);
+
+    let dummy_name = format!("_impl_db_enum_for_{}", enum_name);
+    Ok(wrap_in_dummy_mod(
+        Ident::new(&dummy_name.to_lowercase(), Span::call_site()),
+        quote! {
+            use diesel::backend::Backend;
+            use diesel::deserialize::{self, FromSql};
+            use diesel::serialize::{self, Output, ToSql};
+            use diesel::sql_types::{NotNull, SingleValue};
+            use std::io::Write;
+
+            impl NotNull for #enum_name {}
+            impl SingleValue for #enum_name {}
+
+            #[cfg(feature = "sqlite")]
+            impl diesel::sql_types::HasSqlType<#enum_name> for diesel::sqlite::Sqlite {
+                fn metadata(_: &()) -> diesel::sqlite::SqliteType {
+                    diesel::sqlite::SqliteType::Text
+                }
+            }
+
+            #[cfg(feature = "mysql")]
+            impl diesel::sql_types::HasSqlType<#enum_name> for diesel::mysql::Mysql {
+                fn metadata(_: &()) -> diesel::mysql::MysqlType {
+                    diesel::mysql::MysqlType::String
+                }
+            }
+
+            #pg_tokens
+
+            impl<__DB> FromSql<#enum_name, __DB> for #enum_name
+            where
+                __DB: Backend,
+                String: FromSql<diesel::sql_types::Text, __DB>,
+            {
+                fn from_sql(bytes: Option<&__DB::RawValue>) -> deserialize::Result<Self> {
+                    let s = String::from_sql(bytes)?;
+                    match s.as_str() {
+                        #(#from_sql_arms)*
+                        _ => Err(format!("Unrecognized variant for {}: {:?}", stringify!(#enum_name), s).into()),
+                    }
+                }
+            }
+
+            impl<__DB> ToSql<#enum_name, __DB> for #enum_name
+            where
+                __DB: Backend,
+                str: ToSql<diesel::sql_types::Text, __DB>,
+            {
+                fn to_sql<W: Write>(&self, out: &mut Output<W, __DB>) -> serialize::Result {
+                    let s = match *self {
+                        #(#to_sql_arms)*
+                    };
+                    ToSql::<diesel::sql_types::Text, __DB>::to_sql(s, out)
+                }
+            }
+        },
+    ))
+}
+
+fn pg_tokens(item: &syn::DeriveInput, enum_name: &syn::Ident) -> Option<proc_macro2::TokenStream> {
+    if cfg!(not(feature = "postgres")) {
+        return None;
+    }
+
+    let type_name = MetaItem::with_name(&item.attrs, "postgres")
+        .and_then(|attr| attr.nested_item("type_name").ok())
+        .map(|attr| attr.expect_str_value())?;
+
+    Some(quote! {
+        impl diesel::sql_types::HasSqlType<#enum_name> for diesel::pg::Pg {
+            fn metadata(lookup: &diesel::pg::PgMetadataLookup) -> diesel::pg::PgTypeMetadata {
+                lookup.lookup_type(#type_name)
+            }
+        }
+    })
+}
@@ -0,0 +1,120 @@
+use proc_macro2;
+use proc_macro2::*;
+use syn;
+
+use util::*;
+
+/// Forwards `AsExpression`, `ToSql`, `FromSql`, `FromSqlRow` and `Queryable`
+/// through a single-field tuple struct to its wrapped type, for any SQL type
+/// and backend the wrapped type itself supports.
+///
+/// This only works on newtypes with exactly one unnamed field, e.g.
+/// `struct UserId(i64);`.
+pub fn derive(item: syn::DeriveInput) -> Result<proc_macro2::TokenStream, Diagnostic> {
+    let struct_name = &item.ident;
+
+    if !item.generics.params.is_empty() {
+        return Err(item
+            .ident
+            .span()
+            .error("#[derive(DieselNewType)] does not support generic types"));
+    }
+
+    let field_ty = match item.data {
+        syn::Data::Struct(ref data) => match data.fields {
+            syn::Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                &fields.unnamed[0].ty
+            }
+            _ => {
+                return Err(item.ident.span().error(
+                    "#[derive(DieselNewType)] can only be used on tuple structs with one field",
+                ))
+            }
+        },
+        _ => {
+            return Err(item
+                .ident
+                .span()
+                .error("#[derive(DieselNewType)] can only be used on structs"))
+        }
+    };
+
+    let dummy_name = format!("_impl_diesel_new_type_for_{}", struct_name);
+    Ok(wrap_in_dummy_mod(
+        Ident::new(&dummy_name.to_lowercase(), Span::call_site()),
+        quote! {
+            use diesel::backend::Backend;
+            use diesel::deserialize::{self, FromSql, FromSqlRow, Queryable};
+            use diesel::expression::AsExpression;
+            use diesel::expression::bound::Bound;
+            use diesel::serialize::{self, Output, ToSql};
+            use std::io::Write;
+
+            impl<__ST> AsExpression<__ST> for #struct_name
+            where
+                #field_ty: AsExpression<__ST>,
+            {
+                type Expression = <#field_ty as AsExpression<__ST>>::Expression;
+
+                fn as_expression(self) -> Self::Expression {
+                    AsExpression::<__ST>::as_expression(self.0)
+                }
+            }
+
+            impl<'expr, __ST> AsExpression<__ST> for &'expr #struct_name
+            where
+                &'expr #field_ty: AsExpression<__ST>,
+            {
+                type Expression = <&'expr #field_ty as AsExpression<__ST>>::Expression;
+
+                fn as_expression(self) -> Self::Expression {
+                    AsExpression::<__ST>::as_expression(&self.0)
+                }
+            }
+
+            impl<__ST, __DB> ToSql<__ST, __DB> for #struct_name
+            where
+                __DB: Backend,
+                #field_ty: ToSql<__ST, __DB>,
+            {
+                fn to_sql<W: Write>(&self, out: &mut Output<W, __DB>) -> serialize::Result {
+                    ToSql::<__ST, __DB>::to_sql(&self.0, out)
+                }
+            }
+
+            impl<__ST, __DB> FromSql<__ST, __DB> for #struct_name
+            where
+                __DB: Backend,
+                #field_ty: FromSql<__ST, __DB>,
+            {
+                fn from_sql(bytes: Option<&__DB::RawValue>) -> deserialize::Result<Self> {
+                    FromSql::<__ST, __DB>::from_sql(bytes).map(#struct_name)
+                }
+            }
+
+            impl<__ST, __DB> FromSqlRow<__ST, __DB> for #struct_name
+            where
+                __DB: Backend,
+                Self: FromSql<__ST, __DB>,
+            {
+                fn build_from_row<R: diesel::row::Row<__DB>>(
+                    row: &mut R,
+                ) -> deserialize::Result<Self> {
+                    FromSql::<__ST, __DB>::from_sql(row.take())
+                }
+            }
+
+            impl<__ST, __DB> Queryable<__ST, __DB> for #struct_name
+            where
+                __DB: Backend,
+                Self: FromSql<__ST, __DB>,
+            {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> Self {
+                    row
+                }
+            }
+        },
+    ))
+}
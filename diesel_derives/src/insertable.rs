@@ -26,8 +26,16 @@ pub fn derive(item: syn::DeriveInput) -> Result<proc_macro2::TokenStream, Diagno
     impl_generics.params.push(parse_quote!('insert));
     let (impl_generics, ..) = impl_generics.split_for_impl();
 
-    let (direct_field_ty, direct_field_assign): (Vec<_>, Vec<_>) = model
+    // Fields marked `#[diesel(generated_column)]` back a `GENERATED ALWAYS` column. The
+    // database computes their value, so they're excluded here rather than inserted (they can
+    // still be read back through `#[derive(Queryable)]`/`#[derive(QueryableByName)]`).
+    let insertable_fields = model
         .fields()
+        .iter()
+        .filter(|f| !f.has_flag("generated_column"))
+        .collect::<Vec<_>>();
+
+    let (direct_field_ty, direct_field_assign): (Vec<_>, Vec<_>) = insertable_fields
         .iter()
         .map(|f| {
             (
@@ -37,8 +45,7 @@ pub fn derive(item: syn::DeriveInput) -> Result<proc_macro2::TokenStream, Diagno
         })
         .unzip();
 
-    let (ref_field_ty, ref_field_assign): (Vec<_>, Vec<_>) = model
-        .fields()
+    let (ref_field_ty, ref_field_assign): (Vec<_>, Vec<_>) = insertable_fields
         .iter()
         .map(|f| {
             (
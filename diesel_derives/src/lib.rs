@@ -32,6 +32,8 @@ mod util;
 mod as_changeset;
 mod as_expression;
 mod associations;
+mod db_enum;
+mod diesel_newtype;
 mod diesel_numeric_ops;
 mod from_sql_row;
 mod identifiable;
@@ -60,6 +62,16 @@ pub fn derive_associations(input: TokenStream) -> TokenStream {
     expand_derive(input, associations::derive)
 }
 
+#[proc_macro_derive(DbEnum, attributes(db_rename, postgres))]
+pub fn derive_db_enum(input: TokenStream) -> TokenStream {
+    expand_derive(input, db_enum::derive)
+}
+
+#[proc_macro_derive(DieselNewType)]
+pub fn derive_diesel_new_type(input: TokenStream) -> TokenStream {
+    expand_derive(input, diesel_newtype::derive)
+}
+
 #[proc_macro_derive(DieselNumericOps)]
 pub fn derive_diesel_numeric_ops(input: TokenStream) -> TokenStream {
     expand_derive(input, diesel_numeric_ops::derive)
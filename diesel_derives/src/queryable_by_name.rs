@@ -62,9 +62,22 @@ fn field_expr(field: &Field, model: &Model) -> syn::FieldValue {
     } else {
         let column_name = field.column_name();
         let st = sql_type(field, model);
-        field
-            .name
-            .assign(parse_quote!(row.get::<#st, _>(stringify!(#column_name))?))
+        if is_option_ty(&field.ty) {
+            // A column that's simply missing from the result set (as opposed
+            // to present and `NULL`) is treated the same as `NULL` for
+            // `Option` fields, so ad-hoc queries can select a subset of
+            // columns without having to list every optional field.
+            field.name.assign(parse_quote!(
+                match diesel::row::NamedRow::index_of(row, stringify!(#column_name)) {
+                    std::option::Option::Some(_) => row.get::<#st, _>(stringify!(#column_name))?,
+                    std::option::Option::None => std::option::Option::None,
+                }
+            ))
+        } else {
+            field
+                .name
+                .assign(parse_quote!(row.get::<#st, _>(stringify!(#column_name))?))
+        }
     }
 }
 
@@ -447,3 +447,72 @@ fn option_fields_are_assigned_null_when_specified() {
     let actual = users::table.order(users::id).load(&connection);
     assert_eq!(Ok(expected), actual);
 }
+
+#[test]
+fn option_field_is_assigned_null_when_flagged_individually() {
+    #[derive(AsChangeset)]
+    #[table_name = "users"]
+    struct UserForm<'a> {
+        name: &'a str,
+        #[diesel(treat_none_as_null)]
+        hair_color: Option<&'a str>,
+    }
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    update(users::table.find(1))
+        .set(&UserForm {
+            name: "Jim",
+            hair_color: Some("blue"),
+        })
+        .execute(&connection)
+        .unwrap();
+    update(users::table.find(2))
+        .set(&UserForm {
+            name: "Ruby",
+            hair_color: None,
+        })
+        .execute(&connection)
+        .unwrap();
+
+    let expected = vec![
+        (1, String::from("Jim"), Some(String::from("blue"))),
+        (2, String::from("Ruby"), None),
+    ];
+    let actual = users::table.order(users::id).load(&connection);
+    assert_eq!(Ok(expected), actual);
+}
+
+#[test]
+fn nested_option_field_chooses_skip_or_null_per_update() {
+    #[derive(AsChangeset)]
+    #[table_name = "users"]
+    struct UserForm<'a> {
+        name: &'a str,
+        hair_color: Option<Option<&'a str>>,
+    }
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    update(users::table.find(1))
+        .set(&UserForm {
+            name: "Jim",
+            hair_color: None,
+        })
+        .execute(&connection)
+        .unwrap();
+    update(users::table.find(2))
+        .set(&UserForm {
+            name: "Ruby",
+            hair_color: Some(None),
+        })
+        .execute(&connection)
+        .unwrap();
+
+    let expected = vec![
+        (1, String::from("Jim"), Some(String::from("black"))),
+        (2, String::from("Ruby"), None),
+    ];
+    let actual = users::table.order(users::id).load(&connection);
+    assert_eq!(Ok(expected), actual);
+}
@@ -120,3 +120,27 @@ fn embedded_option() {
     let data = sql_query("SELECT 1 AS foo, NULL AS bar").get_result(&conn);
     assert_eq!(Ok(A { foo: 1, b: None }), data);
 }
+
+#[test]
+fn optional_field_absent_from_result_set() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, QueryableByName)]
+    struct MyStruct {
+        #[sql_type = "IntSql"]
+        foo: IntRust,
+        #[sql_type = "diesel::sql_types::Nullable<IntSql>"]
+        bar: Option<IntRust>,
+    }
+
+    let conn = connection();
+    let data = sql_query("SELECT 1 AS foo").get_result(&conn);
+    assert_eq!(Ok(MyStruct { foo: 1, bar: None }), data);
+
+    let data = sql_query("SELECT 1 AS foo, 2 AS bar").get_result(&conn);
+    assert_eq!(
+        Ok(MyStruct {
+            foo: 1,
+            bar: Some(2),
+        }),
+        data
+    );
+}
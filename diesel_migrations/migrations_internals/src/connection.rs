@@ -5,7 +5,7 @@ use diesel::prelude::*;
 use diesel::query_builder::{InsertStatement, ValuesClause};
 use diesel::query_dsl::methods::ExecuteDsl;
 use diesel::sql_types::VarChar;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
 use super::schema::__diesel_schema_migrations::dsl::*;
@@ -16,8 +16,11 @@ use super::schema::__diesel_schema_migrations::dsl::*;
 /// should be useable where this trait is required.
 pub trait MigrationConnection: Connection {
     fn previously_run_migration_versions(&self) -> QueryResult<HashSet<String>>;
+    /// Maps the version of each previously run migration to the checksum that was recorded for
+    /// it, so callers can detect when an already-applied migration's SQL has since been edited.
+    fn migration_checksums(&self) -> QueryResult<HashMap<String, String>>;
     fn latest_run_migration_version(&self) -> QueryResult<Option<String>>;
-    fn insert_new_migration(&self, version: &str) -> QueryResult<()>;
+    fn insert_new_migration(&self, version: &str, checksum: &str) -> QueryResult<()>;
 }
 
 impl<T> MigrationConnection for T
@@ -28,7 +31,10 @@ where
     for<'a> InsertStatement<
         __diesel_schema_migrations,
         ValuesClause<
-            ColumnInsertValue<version, &'a Bound<VarChar, &'a str>>,
+            (
+                ColumnInsertValue<version, &'a Bound<VarChar, &'a str>>,
+                ColumnInsertValue<checksum, &'a Bound<VarChar, &'a str>>,
+            ),
             __diesel_schema_migrations,
         >,
     >: ExecuteDsl<T>,
@@ -40,15 +46,22 @@ where
             .map(FromIterator::from_iter)
     }
 
+    fn migration_checksums(&self) -> QueryResult<HashMap<String, String>> {
+        __diesel_schema_migrations
+            .select((version, checksum))
+            .load(self)
+            .map(FromIterator::from_iter)
+    }
+
     fn latest_run_migration_version(&self) -> QueryResult<Option<String>> {
         use diesel::dsl::max;
         __diesel_schema_migrations.select(max(version)).first(self)
     }
 
-    fn insert_new_migration(&self, ver: &str) -> QueryResult<()> {
+    fn insert_new_migration(&self, ver: &str, sum: &str) -> QueryResult<()> {
         try!(
             ::diesel::insert_into(__diesel_schema_migrations)
-                .values(&version.eq(ver))
+                .values(&(version.eq(ver), checksum.eq(sum)))
                 .execute(self)
         );
         Ok(())
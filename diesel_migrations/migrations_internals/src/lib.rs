@@ -93,6 +93,7 @@ pub use self::connection::MigrationConnection;
 pub use self::migration::*;
 pub use diesel::migration::*;
 
+use std::collections::HashMap;
 use std::fs::DirEntry;
 use std::io::{stdout, Write};
 
@@ -105,6 +106,47 @@ use std::path::{Path, PathBuf};
 
 pub static TIMESTAMP_FORMAT: &str = "%Y-%m-%d-%H%M%S";
 
+/// A source of migrations that can be handed to
+/// [`run_pending_migrations_from_source`](fn.run_pending_migrations_from_source.html) and
+/// [`revert_latest_migration_from_source`](fn.revert_latest_migration_from_source.html).
+///
+/// This lets applications choose at compile time (or even at runtime) whether migrations are
+/// read from a directory on disk (see [`FileBasedMigrations`](struct.FileBasedMigrations.html))
+/// or compiled into the binary via [`embed_migrations!`](../macro.embed_migrations.html),
+/// without duplicating the code that walks the pending list and records what's been run.
+pub trait MigrationSource {
+    /// Returns the list of migrations this source knows about, in no particular order.
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError>;
+}
+
+/// A [`MigrationSource`](trait.MigrationSource.html) backed by a directory of
+/// `{version}_{name}/{up,down}.sql` folders on disk.
+#[derive(Debug, Clone)]
+pub struct FileBasedMigrations {
+    migrations_dir: PathBuf,
+}
+
+impl FileBasedMigrations {
+    /// Searches for the migrations directory relative to the current working directory, the
+    /// same way [`find_migrations_directory`](fn.find_migrations_directory.html) does.
+    pub fn find() -> Result<Self, MigrationError> {
+        find_migrations_directory().map(Self::from_path)
+    }
+
+    /// Uses the given directory as the source of migrations, without searching for it.
+    pub fn from_path<P: Into<PathBuf>>(path: P) -> Self {
+        FileBasedMigrations {
+            migrations_dir: path.into(),
+        }
+    }
+}
+
+impl MigrationSource for FileBasedMigrations {
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError> {
+        migrations_in_directory(&self.migrations_dir)
+    }
+}
+
 /// Runs all migrations that have not yet been run. This function will print all progress to
 /// stdout. This function will return an `Err` if some error occurs reading the migrations, or if
 /// any migration fails to run. Each migration is run in its own transaction, so some migrations
@@ -126,6 +168,49 @@ where
     run_pending_migrations_in_directory(conn, &migrations_dir, &mut stdout())
 }
 
+/// Runs all migrations returned by `source` that have not yet been run, writing progress to
+/// `output`. See [`run_pending_migrations`](fn.run_pending_migrations.html) for the version
+/// that always reads from the on-disk `migrations` directory.
+pub fn run_pending_migrations_from_source<Conn, S>(
+    conn: &Conn,
+    source: &S,
+    output: &mut Write,
+) -> Result<(), RunMigrationsError>
+where
+    Conn: MigrationConnection,
+    S: MigrationSource,
+{
+    let all_migrations = source.migrations()?;
+    run_migrations(conn, all_migrations, output)
+}
+
+/// Reverts the last migration returned by `source` that was run. Returns the version that was
+/// reverted. Returns an `Err` if no migrations have ever been run. See
+/// [`revert_latest_migration`](fn.revert_latest_migration.html) for the version that always
+/// reads from the on-disk `migrations` directory.
+pub fn revert_latest_migration_from_source<Conn, S>(
+    conn: &Conn,
+    source: &S,
+) -> Result<String, RunMigrationsError>
+where
+    Conn: MigrationConnection,
+    S: MigrationSource,
+{
+    try!(setup_database(conn));
+    let latest_migration_version = conn.latest_run_migration_version()?
+        .ok_or_else(|| RunMigrationsError::MigrationError(MigrationError::NoMigrationRun))?;
+    let all_migrations = source.migrations()?;
+    let migration = all_migrations
+        .into_iter()
+        .find(|m| m.version() == latest_migration_version)
+        .ok_or_else(|| {
+            RunMigrationsError::MigrationError(MigrationError::UnknownMigrationVersion(
+                latest_migration_version.clone(),
+            ))
+        })?;
+    revert_migration(conn, &migration, &mut stdout()).map(|_| latest_migration_version)
+}
+
 #[doc(hidden)]
 pub fn run_pending_migrations_in_directory<Conn>(
     conn: &Conn,
@@ -209,7 +294,7 @@ where
 }
 
 #[doc(hidden)]
-pub fn revert_migration_with_version<Conn: Connection>(
+pub fn revert_migration_with_version<Conn: MigrationConnection>(
     conn: &Conn,
     migrations_dir: &Path,
     ver: &str,
@@ -253,12 +338,19 @@ pub fn setup_database<Conn: Connection>(conn: &Conn) -> QueryResult<usize> {
 }
 
 fn create_schema_migrations_table_if_needed<Conn: Connection>(conn: &Conn) -> QueryResult<usize> {
-    conn.execute(
+    let rows = conn.execute(
         "CREATE TABLE IF NOT EXISTS __diesel_schema_migrations (\
          version VARCHAR(50) PRIMARY KEY NOT NULL,\
-         run_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP\
+         run_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,\
+         checksum VARCHAR(50) NOT NULL DEFAULT ''\
          )",
-    )
+    )?;
+    // The table may already exist from before checksums were tracked. Adding the column is
+    // best-effort: if it's already there (or the backend doesn't like `IF NOT EXISTS` here),
+    // we just fall back to migrations recorded without one, which simply opts them out of
+    // tamper detection until they're re-run.
+    let _ = conn.execute("ALTER TABLE __diesel_schema_migrations ADD COLUMN checksum VARCHAR(50) NOT NULL DEFAULT ''");
+    Ok(rows)
 }
 
 #[doc(hidden)]
@@ -287,6 +379,143 @@ fn migrations_in_directory(path: &Path) -> Result<Vec<Box<Migration>>, Migration
         .collect()
 }
 
+/// The versions affected by a single call to [`revert_to`](fn.revert_to.html),
+/// [`redo`](fn.redo.html), or [`migrate_to`](fn.migrate_to.html).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationVersions {
+    /// Versions that were reverted, most recently run first.
+    pub reverted: Vec<String>,
+    /// Versions that were run, in ascending order.
+    pub run: Vec<String>,
+}
+
+/// Reverts all migrations newer than `target_version`, leaving `target_version` itself applied.
+/// Returns the versions that were reverted, most recently run first.
+pub fn revert_to<Conn>(
+    conn: &Conn,
+    migrations_dir: &Path,
+    target_version: &str,
+) -> Result<MigrationVersions, RunMigrationsError>
+where
+    Conn: MigrationConnection,
+{
+    try!(setup_database(conn));
+    let mut to_revert: Vec<_> = try!(conn.previously_run_migration_versions())
+        .into_iter()
+        .filter(|v| v.as_str() > target_version)
+        .collect();
+    to_revert.sort();
+    to_revert.reverse();
+
+    let mut reverted = Vec::new();
+    for ver in to_revert {
+        try!(revert_migration_with_version(
+            conn,
+            migrations_dir,
+            &ver,
+            &mut stdout()
+        ));
+        reverted.push(ver);
+    }
+    Ok(MigrationVersions {
+        reverted,
+        run: Vec::new(),
+    })
+}
+
+/// Reverts the last `n` migrations that were run, then re-runs them. Returns the versions that
+/// were reverted (most recently run first) and the versions that were re-run (ascending order).
+pub fn redo<Conn>(
+    conn: &Conn,
+    migrations_dir: &Path,
+    n: usize,
+) -> Result<MigrationVersions, RunMigrationsError>
+where
+    Conn: MigrationConnection,
+{
+    try!(setup_database(conn));
+    let mut already_run: Vec<_> = try!(conn.previously_run_migration_versions())
+        .into_iter()
+        .collect();
+    already_run.sort();
+    let to_redo: Vec<_> = already_run.into_iter().rev().take(n).collect();
+
+    let mut reverted = Vec::new();
+    for ver in &to_redo {
+        try!(revert_migration_with_version(
+            conn,
+            migrations_dir,
+            ver,
+            &mut stdout()
+        ));
+        reverted.push(ver.clone());
+    }
+
+    let mut run = Vec::new();
+    for ver in to_redo.into_iter().rev() {
+        try!(run_migration_with_version(
+            conn,
+            migrations_dir,
+            &ver,
+            &mut stdout()
+        ));
+        run.push(ver);
+    }
+
+    Ok(MigrationVersions { reverted, run })
+}
+
+/// Runs or reverts migrations as needed so that exactly the migrations up to and including
+/// `target_version` have been run. Returns the versions that were reverted (most recently run
+/// first) and the versions that were run (ascending order).
+pub fn migrate_to<Conn>(
+    conn: &Conn,
+    migrations_dir: &Path,
+    target_version: &str,
+) -> Result<MigrationVersions, RunMigrationsError>
+where
+    Conn: MigrationConnection,
+{
+    try!(setup_database(conn));
+    let already_run = try!(conn.previously_run_migration_versions());
+    let all_migrations = try!(migrations_in_directory(migrations_dir));
+
+    let mut to_revert: Vec<_> = already_run
+        .iter()
+        .filter(|v| v.as_str() > target_version)
+        .cloned()
+        .collect();
+    to_revert.sort();
+    to_revert.reverse();
+
+    let mut reverted = Vec::new();
+    for ver in to_revert {
+        try!(revert_migration_with_version(
+            conn,
+            migrations_dir,
+            &ver,
+            &mut stdout()
+        ));
+        reverted.push(ver);
+    }
+
+    let mut to_run: Vec<_> = all_migrations
+        .into_iter()
+        .filter(|m| {
+            m.version() <= target_version && !already_run.contains(&m.version().to_string())
+        })
+        .collect();
+    to_run.sort_by(|a, b| a.version().cmp(b.version()));
+
+    let mut run = Vec::new();
+    for migration in to_run {
+        try!(run_migration(conn, &*migration, &mut stdout()));
+        run.push(migration.version().to_string());
+    }
+
+    Ok(MigrationVersions { reverted, run })
+}
+
 /// Run all pending migrations in the given list. Apps should likely be calling
 /// `run_pending_migrations` or `run_pending_migrations_in_directory` instead.
 pub fn run_migrations<Conn, List>(
@@ -301,7 +530,16 @@ where
 {
     try!(setup_database(conn));
     let already_run = try!(conn.previously_run_migration_versions());
-    let mut pending_migrations: Vec<_> = migrations
+    let previous_checksums = try!(conn.migration_checksums());
+    let all_migrations: Vec<_> = migrations.into_iter().collect();
+
+    for migration in &all_migrations {
+        if already_run.contains(&migration.version().to_string()) {
+            verify_checksum(migration, &previous_checksums)?;
+        }
+    }
+
+    let mut pending_migrations: Vec<_> = all_migrations
         .into_iter()
         .filter(|m| !already_run.contains(&m.version().to_string()))
         .collect();
@@ -313,6 +551,28 @@ where
     Ok(())
 }
 
+/// Returns an error if `migration` was already run under a different checksum than it has now,
+/// meaning its `up.sql` was edited after the fact. Migrations recorded before checksums were
+/// tracked (or that opt out by returning an empty checksum) are left unchecked.
+fn verify_checksum(
+    migration: &Migration,
+    previous_checksums: &HashMap<String, String>,
+) -> Result<(), RunMigrationsError> {
+    let current_checksum = migration.checksum();
+    if current_checksum.is_empty() {
+        return Ok(());
+    }
+    match previous_checksums.get(migration.version()) {
+        Some(stored_checksum) if stored_checksum.is_empty() => Ok(()),
+        Some(stored_checksum) if stored_checksum != &current_checksum => Err(
+            RunMigrationsError::MigrationError(MigrationError::ChecksumMismatch(
+                migration.version().to_string(),
+            )),
+        ),
+        _ => Ok(()),
+    }
+}
+
 fn run_migration<Conn>(
     conn: &Conn,
     migration: &Migration,
@@ -321,7 +581,7 @@ fn run_migration<Conn>(
 where
     Conn: MigrationConnection,
 {
-    conn.transaction(|| {
+    let body = || -> Result<(), RunMigrationsError> {
         if migration.version() != "00000000000000" {
             try!(writeln!(output, "Running migration {}", name(&migration)));
         }
@@ -333,17 +593,24 @@ where
             ));
             return Err(e);
         }
-        try!(conn.insert_new_migration(migration.version()));
+        try!(conn.insert_new_migration(migration.version(), &migration.checksum()));
         Ok(())
-    })
+    };
+
+    if migration.run_in_transaction() {
+        conn.transaction(body)
+    } else {
+        body()
+    }
 }
 
-fn revert_migration<Conn: Connection>(
+fn revert_migration<Conn: MigrationConnection>(
     conn: &Conn,
     migration: &Migration,
     output: &mut Write,
 ) -> Result<(), RunMigrationsError> {
-    conn.transaction(|| {
+    let body = || -> Result<(), RunMigrationsError> {
+        verify_checksum(migration, &conn.migration_checksums()?)?;
         try!(writeln!(
             output,
             "Rolling back migration {}",
@@ -360,7 +627,13 @@ fn revert_migration<Conn: Connection>(
         let target = __diesel_schema_migrations.filter(version.eq(migration.version()));
         try!(::diesel::delete(target).execute(conn));
         Ok(())
-    })
+    };
+
+    if migration.run_in_transaction() {
+        conn.transaction(body)
+    } else {
+        body()
+    }
 }
 
 /// Returns the directory containing migrations. Will look at for
@@ -128,6 +128,55 @@ impl Migration for SqlFileMigration {
     fn revert(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
         run_sql_from_file(conn, &self.0.join("down.sql"))
     }
+
+    fn checksum(&self) -> String {
+        File::open(self.0.join("up.sql"))
+            .and_then(|mut file| {
+                let mut sql = String::new();
+                file.read_to_string(&mut sql)?;
+                Ok(sql)
+            })
+            .map(|sql| checksum_sql(&sql))
+            .unwrap_or_default()
+    }
+
+    fn run_in_transaction(&self) -> bool {
+        File::open(self.0.join("up.sql"))
+            .and_then(|mut file| {
+                let mut sql = String::new();
+                file.read_to_string(&mut sql)?;
+                Ok(sql)
+            })
+            .map(|sql| migration_runs_in_transaction(&sql))
+            .unwrap_or(true)
+    }
+}
+
+/// A simple, dependency-free (FNV-1a) checksum of a migration's SQL, used to detect that an
+/// already-applied migration's `up.sql` has been edited since it was run. This isn't a
+/// cryptographic hash -- it only needs to catch accidental drift, not deliberate tampering.
+pub fn checksum_sql(sql: &str) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in sql.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    format!("{:016x}", hash)
+}
+
+/// SQL migrations that begin with this marker (ignoring leading whitespace) are run without
+/// being wrapped in a transaction. This is needed for statements that some backends refuse to
+/// run inside one, such as `CREATE INDEX CONCURRENTLY` on Pg or certain `PRAGMA` changes on
+/// SQLite.
+pub const NO_TRANSACTION_MARKER: &str = "-- diesel:no_transaction";
+
+/// Returns whether `sql` opts out of transactional wrapping via the
+/// [`NO_TRANSACTION_MARKER`](constant.NO_TRANSACTION_MARKER.html).
+pub fn migration_runs_in_transaction(sql: &str) -> bool {
+    !sql.trim_start().starts_with(NO_TRANSACTION_MARKER)
 }
 
 fn run_sql_from_file(conn: &SimpleConnection, path: &Path) -> Result<(), RunMigrationsError> {
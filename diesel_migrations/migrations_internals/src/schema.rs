@@ -2,5 +2,6 @@ table! {
     __diesel_schema_migrations (version) {
         version -> VarChar,
         run_on -> Timestamp,
+        checksum -> VarChar,
     }
 }
@@ -46,6 +46,14 @@ pub fn derive_embed_migrations(input: &syn::DeriveInput) -> quote::Tokens {
             fn revert(&self, _conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
                 unreachable!()
             }
+
+            fn checksum(&self) -> String {
+                checksum_sql(self.up_sql)
+            }
+
+            fn run_in_transaction(&self) -> bool {
+                migration_runs_in_transaction(self.up_sql)
+            }
         }
     );
 
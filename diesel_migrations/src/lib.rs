@@ -80,28 +80,42 @@ extern crate migrations_macros;
 #[doc(inline)]
 pub use migrations_internals::any_pending_migrations;
 #[doc(inline)]
+pub use migrations_internals::checksum_sql;
+#[doc(inline)]
 pub use migrations_internals::find_migrations_directory;
 #[doc(inline)]
 pub use migrations_internals::mark_migrations_in_directory;
 #[doc(inline)]
+pub use migrations_internals::migrate_to;
+#[doc(inline)]
 pub use migrations_internals::migration_from;
 #[doc(inline)]
 pub use migrations_internals::migration_paths_in_directory;
 #[doc(inline)]
+pub use migrations_internals::migration_runs_in_transaction;
+#[doc(inline)]
 pub use migrations_internals::name;
 #[doc(inline)]
+pub use migrations_internals::redo;
+#[doc(inline)]
 pub use migrations_internals::revert_latest_migration;
 #[doc(inline)]
+pub use migrations_internals::revert_latest_migration_from_source;
+#[doc(inline)]
 pub use migrations_internals::revert_latest_migration_in_directory;
 #[doc(inline)]
 pub use migrations_internals::revert_migration_with_version;
 #[doc(inline)]
+pub use migrations_internals::revert_to;
+#[doc(inline)]
 pub use migrations_internals::run_migration_with_version;
 #[doc(inline)]
 pub use migrations_internals::run_migrations;
 #[doc(inline)]
 pub use migrations_internals::run_pending_migrations;
 #[doc(inline)]
+pub use migrations_internals::run_pending_migrations_from_source;
+#[doc(inline)]
 pub use migrations_internals::run_pending_migrations_in_directory;
 #[doc(inline)]
 pub use migrations_internals::search_for_migrations_directory;
@@ -110,6 +124,8 @@ pub use migrations_internals::setup_database;
 #[doc(inline)]
 pub use migrations_internals::version_from_path;
 #[doc(inline)]
+pub use migrations_internals::FileBasedMigrations;
+#[doc(inline)]
 pub use migrations_internals::Migration;
 #[doc(inline)]
 pub use migrations_internals::MigrationConnection;
@@ -118,6 +134,10 @@ pub use migrations_internals::MigrationError;
 #[doc(inline)]
 pub use migrations_internals::MigrationName;
 #[doc(inline)]
+pub use migrations_internals::MigrationSource;
+#[doc(inline)]
+pub use migrations_internals::MigrationVersions;
+#[doc(inline)]
 pub use migrations_internals::RunMigrationsError;
 #[doc(hidden)]
 pub use migrations_macros::*;
@@ -41,3 +41,31 @@ fn distinct_on() {
 
     assert_eq!(expected_data, data);
 }
+
+#[cfg(feature = "postgres")]
+#[test]
+fn distinct_on_tuple() {
+    use schema::users::dsl::*;
+
+    let connection = connection();
+    connection
+        .execute(
+            "INSERT INTO users (name, hair_color) VALUES ('Sean', 'black'), ('Sean', 'brown'), ('Tess', NULL)",
+        )
+        .unwrap();
+
+    // `distinct_on` accepts a tuple, so "latest row per group" style queries
+    // can key on more than one column without dropping to raw SQL.
+    let source = users
+        .select((name, hair_color))
+        .order((name, hair_color))
+        .distinct_on((name, hair_color));
+    let expected_data = vec![
+        ("Sean".to_string(), Some("black".to_string())),
+        ("Sean".to_string(), Some("brown".to_string())),
+        ("Tess".to_string(), None),
+    ];
+    let data: Vec<_> = source.load(&connection).unwrap();
+
+    assert_eq!(expected_data, data);
+}
@@ -425,6 +425,29 @@ fn filter_subselect_referencing_outer_table() {
     assert_eq!(expected, users_with_published_posts);
 }
 
+#[test]
+fn filter_boxed_exists_referencing_outer_table() {
+    use diesel::dsl::exists;
+
+    let conn = connection_with_sean_and_tess_in_users_table();
+    let sean = find_user_by_name("Sean", &conn);
+
+    insert_into(posts::table)
+        .values(&sean.new_post("Hello", None))
+        .execute(&conn)
+        .unwrap();
+
+    let expected = Ok(vec![sean]);
+    let users_with_published_posts = users::table
+        .filter(exists(
+            posts::table
+                .filter(posts::user_id.eq(users::id))
+                .into_boxed(),
+        ))
+        .load(&conn);
+    assert_eq!(expected, users_with_published_posts);
+}
+
 #[test]
 fn filter_subselect_with_boxed_query() {
     use schema::users::dsl::*;
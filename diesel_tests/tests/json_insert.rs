@@ -0,0 +1,58 @@
+#[macro_use]
+extern crate serde_json;
+
+use diesel::query_builder::json_insert::{JsonColumn, JsonColumnType, JsonInsert};
+use diesel::*;
+use schema::*;
+
+#[test]
+fn json_insert_writes_known_columns() {
+    let conn = connection();
+    let columns = [
+        JsonColumn::new("id", JsonColumnType::BigInt),
+        JsonColumn::new("name", JsonColumnType::Text),
+        JsonColumn::nullable("hair_color", JsonColumnType::Text),
+    ];
+    let value = serde_json::json!({ "id": 1, "name": "Sean", "hair_color": "black" });
+
+    let insert = JsonInsert::<TestBackend>::new("users", &columns, &value).unwrap();
+    insert.execute(&conn).unwrap();
+
+    let sean = find_user_by_name("Sean", &conn);
+    assert_eq!(User::with_hair_color(1, "Sean", "black"), sean);
+}
+
+#[test]
+fn json_insert_omits_missing_keys() {
+    let conn = connection();
+    let columns = [
+        JsonColumn::new("id", JsonColumnType::BigInt),
+        JsonColumn::new("name", JsonColumnType::Text),
+        JsonColumn::nullable("hair_color", JsonColumnType::Text),
+    ];
+    let value = serde_json::json!({ "id": 1, "name": "Sean" });
+
+    let insert = JsonInsert::<TestBackend>::new("users", &columns, &value).unwrap();
+    insert.execute(&conn).unwrap();
+
+    let sean = find_user_by_name("Sean", &conn);
+    assert_eq!(User::new(1, "Sean"), sean);
+}
+
+#[test]
+fn json_insert_rejects_unknown_column() {
+    let columns = [JsonColumn::new("name", JsonColumnType::Text)];
+    let value = serde_json::json!({ "name": "Sean", "nickname": "The Sean-inator" });
+
+    let error = JsonInsert::<TestBackend>::new("users", &columns, &value).unwrap_err();
+    assert!(format!("{}", error).contains("nickname"));
+}
+
+#[test]
+fn json_insert_rejects_wrong_type() {
+    let columns = [JsonColumn::new("name", JsonColumnType::Text)];
+    let value = serde_json::json!({ "name": 1 });
+
+    let error = JsonInsert::<TestBackend>::new("users", &columns, &value).unwrap_err();
+    assert!(format!("{}", error).contains("name"));
+}
@@ -37,6 +37,7 @@ mod insert;
 mod insert_from_select;
 mod internal_details;
 mod joins;
+mod json_insert;
 mod macros;
 mod order;
 mod perf_details;
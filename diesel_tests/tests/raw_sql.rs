@@ -1,3 +1,4 @@
+use diesel::dynamic_value::DynamicValue;
 use diesel::*;
 use schema::*;
 
@@ -52,3 +53,117 @@ fn sql_query_can_take_bind_params() {
 
     assert_eq!(Ok(expected), users);
 }
+
+#[test]
+fn sql_query_can_deserialize_into_dynamic_value() {
+    use diesel::sql_types::Nullable;
+    use diesel::sql_types::Text as SqlText;
+
+    #[derive(QueryableByName, Debug, PartialEq)]
+    struct NameAndHairColor {
+        #[sql_type = "SqlText"]
+        name: DynamicValue,
+        #[sql_type = "Nullable<SqlText>"]
+        hair_color: DynamicValue,
+    }
+
+    let conn = connection();
+    diesel::insert_into(users::table)
+        .values(&NewUser::new("Sean", Some("black")))
+        .execute(&conn)
+        .unwrap();
+    diesel::insert_into(users::table)
+        .values(&NewUser::new("Tess", None))
+        .execute(&conn)
+        .unwrap();
+
+    let rows = sql_query("SELECT name, hair_color FROM users ORDER BY id")
+        .load::<NameAndHairColor>(&conn);
+    let expected = vec![
+        NameAndHairColor {
+            name: DynamicValue::Text("Sean".into()),
+            hair_color: DynamicValue::Text("black".into()),
+        },
+        NameAndHairColor {
+            name: DynamicValue::Text("Tess".into()),
+            hair_color: DynamicValue::Null,
+        },
+    ];
+
+    assert_eq!(Ok(expected), rows);
+}
+
+#[test]
+fn named_row_exposes_column_count_and_names() {
+    use diesel::backend::Backend;
+    use diesel::deserialize::{self, QueryableByName};
+    use diesel::row::NamedRow;
+
+    struct ColumnNames {
+        count: usize,
+        names: Vec<String>,
+    }
+
+    impl<DB: Backend> QueryableByName<DB> for ColumnNames {
+        fn build<R: NamedRow<DB>>(row: &R) -> deserialize::Result<Self> {
+            Ok(ColumnNames {
+                count: row.column_count(),
+                names: row.column_names().into_iter().map(String::from).collect(),
+            })
+        }
+    }
+
+    let conn = connection_with_sean_and_tess_in_users_table();
+    let rows = sql_query("SELECT name, hair_color FROM users ORDER BY id")
+        .load::<ColumnNames>(&conn)
+        .unwrap();
+
+    for row in rows {
+        assert_eq!(2, row.count);
+        assert_eq!(vec!["name".to_string(), "hair_color".to_string()], row.names);
+    }
+}
+
+#[test]
+#[cfg(feature = "sqlite")]
+fn sql_query_can_deserialize_into_serde_row() {
+    #[macro_use]
+    extern crate serde_derive;
+
+    use diesel::serde_row::SerdeRow;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct NameAndHairColor {
+        name: String,
+        hair_color: Option<String>,
+    }
+
+    let conn = connection();
+    diesel::insert_into(users::table)
+        .values(&NewUser::new("Sean", Some("black")))
+        .execute(&conn)
+        .unwrap();
+    diesel::insert_into(users::table)
+        .values(&NewUser::new("Tess", None))
+        .execute(&conn)
+        .unwrap();
+
+    let rows = sql_query("SELECT name, hair_color FROM users ORDER BY id")
+        .load::<SerdeRow<NameAndHairColor>>(&conn)
+        .unwrap()
+        .into_iter()
+        .map(|row| row.0)
+        .collect::<Vec<_>>();
+    let expected = vec![
+        NameAndHairColor {
+            name: "Sean".into(),
+            hair_color: Some("black".into()),
+        },
+        NameAndHairColor {
+            name: "Tess".into(),
+            hair_color: None,
+        },
+    ];
+
+    assert_eq!(expected, rows);
+}